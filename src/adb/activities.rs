@@ -0,0 +1,240 @@
+/// Prints the `ResumedActivity` lines of the activity manager dump; `true`
+/// keeps grep's exit status from failing the call when nothing is resumed.
+pub const RESUMED_ACTIVITY_COMMAND: &str =
+    "dumpsys activity activities | grep ResumedActivity; true";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    /// `package/.Class`, as the activity manager prints it.
+    pub component: String,
+    /// `RESUMED`, `PAUSED`, `STOPPED`...; absent when not printed.
+    pub state: Option<String>,
+}
+
+impl Activity {
+    pub fn package(&self) -> &str {
+        self.component
+            .split_once('/')
+            .map_or(self.component.as_str(), |(package, _)| package)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub id: u32,
+    /// The root task (stack before Android 12) holding this one; what
+    /// `am stack remove` takes.
+    pub root_id: u32,
+    /// `standard`, `home`, `recents`...
+    pub kind: Option<String>,
+    /// Top to bottom.
+    pub activities: Vec<Activity>,
+}
+
+impl Task {
+    /// The package of the task's bottom activity, which started it.
+    pub fn package(&self) -> Option<&str> {
+        self.activities.last().map(Activity::package)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Display {
+    pub id: u32,
+    /// Top to bottom; tasks holding only other tasks are left out.
+    pub tasks: Vec<Task>,
+}
+
+/// The component in an `ActivityRecord{hash u0 package/.Class t12}` line.
+fn record_component(line: &str) -> Option<&str> {
+    let record = line.split_once("ActivityRecord{")?.1;
+    record
+        .split_whitespace()
+        .map(|token| token.trim_end_matches('}'))
+        .find(|token| token.contains('/'))
+}
+
+/// The activity in `grep ResumedActivity` output. `topResumedActivity` wins
+/// on releases that print one per display area as well.
+pub fn parse_resumed_activity(output: &str) -> Option<String> {
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_by_key(|line| !line.contains("topResumedActivity"));
+    lines
+        .into_iter()
+        .find_map(record_component)
+        .map(str::to_string)
+}
+
+/// The value of a `key=value` token in a dump line.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split(|c: char| c.is_whitespace() || c == '{' || c == '}')
+        .find_map(|token| token.strip_prefix(key))
+}
+
+/// The number in the `#12` token of a task or stack line.
+fn hash_number(line: &str) -> Option<u32> {
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix('#'))
+        .and_then(|n| n.trim_end_matches(':').parse().ok())
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Parses `dumpsys activity activities` into displays, tasks and activities.
+/// Handles both the stack / `TaskRecord` layout before Android 12 and the
+/// nested `Task{}` layout after it.
+pub fn parse_activity_stack(output: &str) -> Vec<Display> {
+    let mut displays: Vec<Display> = Vec::new();
+    // The enclosing `Stack #N` before Android 12
+    let mut stack_id = None;
+    // `(indent, id)` of the `Task{}` lines enclosing the current line
+    let mut open_tasks: Vec<(usize, u32)> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Display #") {
+            let id = rest
+                .split(|c: char| !c.is_ascii_digit())
+                .next()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            displays.push(Display {
+                id,
+                tasks: Vec::new(),
+            });
+            stack_id = None;
+            open_tasks.clear();
+            continue;
+        }
+        let Some(display) = displays.last_mut() else {
+            continue;
+        };
+
+        if trimmed.starts_with("Stack #") {
+            stack_id = hash_number(trimmed);
+        } else if trimmed.starts_with("* Task{") || trimmed.starts_with("* TaskRecord{") {
+            let Some(id) = hash_number(trimmed) else {
+                continue;
+            };
+            let depth = indent(line);
+            while open_tasks.last().is_some_and(|(d, _)| *d >= depth) {
+                open_tasks.pop();
+            }
+            let root_id = field(trimmed, "StackId=")
+                .and_then(|n| n.parse().ok())
+                .or(stack_id)
+                .or(open_tasks.first().map(|(_, id)| *id))
+                .unwrap_or(id);
+            open_tasks.push((depth, id));
+            display.tasks.push(Task {
+                id,
+                root_id,
+                kind: field(trimmed, "type=").map(str::to_string),
+                activities: Vec::new(),
+            });
+        } else if trimmed.starts_with("* Hist") {
+            let (Some(task), Some(component)) =
+                (display.tasks.last_mut(), record_component(trimmed))
+            else {
+                continue;
+            };
+            task.activities.push(Activity {
+                component: component.to_string(),
+                state: None,
+            });
+        } else if let Some(state) = trimmed
+            .split_whitespace()
+            .find_map(|token| token.strip_prefix("state="))
+            && let Some(activity) = display
+                .tasks
+                .last_mut()
+                .and_then(|task| task.activities.last_mut())
+            && activity.state.is_none()
+        {
+            activity.state = Some(state.to_string());
+        }
+    }
+
+    for display in &mut displays {
+        display.tasks.retain(|task| !task.activities.is_empty());
+    }
+    displays.retain(|display| !display.tasks.is_empty());
+    displays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumed_activity_prefers_the_top_one() {
+        let output = "  mResumedActivity: ActivityRecord{1a u0 com.android.settings/.Settings t9}\n\
+                      topResumedActivity=ActivityRecord{2b u0 com.example.app/.MainActivity t12}\n";
+        assert_eq!(
+            parse_resumed_activity(output).as_deref(),
+            Some("com.example.app/.MainActivity")
+        );
+        assert_eq!(parse_resumed_activity("  mResumedActivity: null\n"), None);
+    }
+
+    #[test]
+    fn parses_nested_tasks() {
+        let output = "\
+ACTIVITY MANAGER ACTIVITIES (dumpsys activity activities)
+Display #0 (activities from top to bottom):
+  * Task{2b1c3f0 #12 type=standard A=10123:com.example.app U=0 visible=true sz=2}
+    topResumedActivity=ActivityRecord{f0a u0 com.example.app/.DetailActivity t12}
+    * Hist  #1: ActivityRecord{f0a u0 com.example.app/.DetailActivity t12}
+      packageName=com.example.app processName=com.example.app
+      state=RESUMED stopped=false finishing=false
+    * Hist  #0: ActivityRecord{e09 u0 com.example.app/.MainActivity t12}
+      mLastState=RESUMED
+      state=STOPPED stopped=true finishing=false
+  * Task{77ab #1 type=home U=0 visible=false sz=1}
+    * Task{88cd #7 type=home I=com.android.launcher3/.Launcher U=0 sz=1}
+      * Hist  #0: ActivityRecord{91 u0 com.android.launcher3/.Launcher t7}
+        state=STOPPED
+";
+        let displays = parse_activity_stack(output);
+        assert_eq!(displays.len(), 1);
+        let tasks = &displays[0].tasks;
+        assert_eq!(tasks.len(), 2);
+        assert_eq!((tasks[0].id, tasks[0].root_id), (12, 12));
+        assert_eq!(tasks[0].kind.as_deref(), Some("standard"));
+        assert_eq!(tasks[0].package(), Some("com.example.app"));
+        let states: Vec<_> = tasks[0]
+            .activities
+            .iter()
+            .map(|a| a.state.as_deref())
+            .collect();
+        assert_eq!(states, [Some("RESUMED"), Some("STOPPED")]);
+        assert_eq!((tasks[1].id, tasks[1].root_id), (7, 1));
+    }
+
+    #[test]
+    fn parses_stacks_before_android_12() {
+        let output = "\
+Display #0 (activities from top to bottom):
+  Stack #3: type=standard mode=fullscreen
+    Task id #25
+    * TaskRecord{abc #25 A=com.example.app U=0 StackId=3 sz=1}
+      * Hist #0: ActivityRecord{def u0 com.example.app/.MainActivity t25}
+          state=PAUSED stopped=false
+Display #1 (activities from top to bottom):
+  Stack #8: type=standard mode=fullscreen
+    * TaskRecord{123 #30 A=com.example.cast U=0 sz=1}
+      * Hist #0: ActivityRecord{456 u0 com.example.cast/.Presentation t30}
+";
+        let displays = parse_activity_stack(output);
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].tasks[0].root_id, 3);
+        assert_eq!(
+            displays[0].tasks[0].activities[0].state.as_deref(),
+            Some("PAUSED")
+        );
+        assert_eq!((displays[1].id, displays[1].tasks[0].root_id), (1, 8));
+        assert_eq!(displays[1].tasks[0].activities[0].state, None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt;
+
+/// `BatteryManager.BATTERY_STATUS_*` values accepted by `dumpsys battery set status`.
+pub const STATUS_CHARGING: u32 = 2;
+pub const STATUS_DISCHARGING: u32 = 3;
+pub const STATUS_NOT_CHARGING: u32 = 4;
+pub const STATUS_FULL: u32 = 5;
+
+const SIMULATED_STATUSES: [u32; 4] = [
+    STATUS_CHARGING,
+    STATUS_DISCHARGING,
+    STATUS_NOT_CHARGING,
+    STATUS_FULL,
+];
+
+/// The status `set status` cycles to after `current`.
+pub fn next_status(current: u32) -> u32 {
+    match SIMULATED_STATUSES.iter().position(|s| *s == current) {
+        Some(i) => SIMULATED_STATUSES[(i + 1) % SIMULATED_STATUSES.len()],
+        None => STATUS_CHARGING,
+    }
+}
+
+pub fn status_name(code: u32) -> &'static str {
+    match code {
+        STATUS_CHARGING => "charging",
+        STATUS_DISCHARGING => "discharging",
+        STATUS_NOT_CHARGING => "not charging",
+        STATUS_FULL => "full",
+        _ => "unknown",
+    }
+}
+
+/// `BatteryManager.BATTERY_HEALTH_*` as printed by `dumpsys battery`.
+pub fn health_name(code: u32) -> &'static str {
+    match code {
+        2 => "good",
+        3 => "overheat",
+        4 => "dead",
+        5 => "over voltage",
+        6 => "failure",
+        7 => "cold",
+        _ => "unknown",
+    }
+}
+
+/// A simulated battery change. Every one but `Reset` stops the device from
+/// reporting its real battery until it is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryChange {
+    Level(u8),
+    Status(u32),
+    Ac(bool),
+    Usb(bool),
+    /// Reports every charger as unplugged.
+    Unplug,
+    /// Returns to the real battery state.
+    Reset,
+}
+
+impl BatteryChange {
+    /// Arguments after `adb shell`.
+    pub fn shell_args(&self) -> Vec<String> {
+        let mut args = vec!["dumpsys".to_string(), "battery".to_string()];
+        let flag = |on: bool| if on { "1" } else { "0" }.to_string();
+        match *self {
+            Self::Level(level) => args.extend(["set".into(), "level".into(), level.to_string()]),
+            Self::Status(code) => args.extend(["set".into(), "status".into(), code.to_string()]),
+            Self::Ac(on) => args.extend(["set".into(), "ac".into(), flag(on)]),
+            Self::Usb(on) => args.extend(["set".into(), "usb".into(), flag(on)]),
+            Self::Unplug => args.push("unplug".into()),
+            Self::Reset => args.push("reset".into()),
+        }
+        args
+    }
+}
+
+impl fmt::Display for BatteryChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let on_off = |on: bool| if on { "plugged" } else { "unplugged" };
+        match *self {
+            Self::Level(level) => write!(f, "battery level {level}%"),
+            Self::Status(code) => write!(f, "battery status {}", status_name(code)),
+            Self::Ac(on) => write!(f, "AC charger {}", on_off(on)),
+            Self::Usb(on) => write!(f, "USB charger {}", on_off(on)),
+            Self::Unplug => write!(f, "all chargers unplugged"),
+            Self::Reset => write!(f, "real battery state restored"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_map_to_dumpsys_battery_args() {
+        assert_eq!(
+            BatteryChange::Level(15).shell_args(),
+            ["dumpsys", "battery", "set", "level", "15"]
+        );
+        assert_eq!(
+            BatteryChange::Usb(false).shell_args(),
+            ["dumpsys", "battery", "set", "usb", "0"]
+        );
+        assert_eq!(
+            BatteryChange::Reset.shell_args(),
+            ["dumpsys", "battery", "reset"]
+        );
+    }
+
+    #[test]
+    fn status_cycles_through_simulated_values() {
+        assert_eq!(next_status(STATUS_CHARGING), STATUS_DISCHARGING);
+        assert_eq!(next_status(STATUS_FULL), STATUS_CHARGING);
+        assert_eq!(next_status(1), STATUS_CHARGING);
+    }
+}
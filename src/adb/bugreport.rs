@@ -0,0 +1,313 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+use color_eyre::{Result, eyre::eyre};
+use zip::ZipArchive;
+
+/// Bytes read from the start of the main entry to find the header fields.
+const HEADER_BYTES: u64 = 16 * 1024;
+
+/// The dumpstate section holding the main logcat buffer.
+pub const MAIN_LOG_SECTION: &str = "SYSTEM LOG";
+
+/// One line of `bugreportz -p` output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BugreportEvent {
+    /// The remote path the zip is being written to.
+    Begin(String),
+    Progress(u64, u64),
+    /// The finished zip's remote path.
+    Ok(String),
+    Fail(String),
+}
+
+pub fn parse_line(line: &str) -> Option<BugreportEvent> {
+    let (kind, rest) = line.trim().split_once(':')?;
+    match kind {
+        "BEGIN" => Some(BugreportEvent::Begin(rest.to_string())),
+        "PROGRESS" => {
+            let (done, total) = rest.split_once('/')?;
+            Some(BugreportEvent::Progress(
+                done.trim().parse().ok()?,
+                total.trim().parse().ok()?,
+            ))
+        }
+        "OK" => Some(BugreportEvent::Ok(rest.to_string())),
+        "FAIL" => Some(BugreportEvent::Fail(rest.trim().to_string())),
+        _ => None,
+    }
+}
+
+/// A captured report in the local archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BugreportSummary {
+    pub path: PathBuf,
+    /// From the file name written by `capture_file_name`.
+    pub device: String,
+    pub fingerprint: Option<String>,
+    /// The dumpstate start time, or the file's modification time.
+    pub time: String,
+    pub modified: SystemTime,
+}
+
+/// The text of a report's main entry, split at its dumpstate sections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BugreportContents {
+    pub text: String,
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub name: String,
+    /// Byte range of the section in the report text, header line included.
+    pub range: Range<usize>,
+}
+
+impl BugreportContents {
+    pub fn section_text(&self, section: &Section) -> &str {
+        &self.text[section.range.clone()]
+    }
+
+    pub fn main_log(&self) -> Option<usize> {
+        self.sections
+            .iter()
+            .position(|s| s.name.starts_with(MAIN_LOG_SECTION))
+    }
+}
+
+/// `Pixel_7_20250101-120000.zip` -> `Pixel 7`.
+fn device_from_file_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match stem.rsplit_once('_') {
+        Some((device, _)) => device.replace('_', " "),
+        None => stem,
+    }
+}
+
+/// The dumpstate start time and build fingerprint from the top of a report.
+pub fn parse_header(text: &str) -> (Option<String>, Option<String>) {
+    let mut time = None;
+    let mut fingerprint = None;
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("== dumpstate:") {
+            time.get_or_insert_with(|| rest.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix("Build fingerprint:") {
+            fingerprint.get_or_insert_with(|| rest.trim().trim_matches('\'').to_string());
+        }
+        if time.is_some() && fingerprint.is_some() {
+            break;
+        }
+    }
+    (time, fingerprint)
+}
+
+/// The name of the main text entry: listed in `main_entry.txt`, or the first
+/// `bugreport*.txt` on releases before it existed.
+fn main_entry_name(archive: &mut ZipArchive<File>) -> Result<String> {
+    if let Ok(mut entry) = archive.by_name("main_entry.txt") {
+        let mut name = String::new();
+        entry.read_to_string(&mut name)?;
+        return Ok(name.trim().to_string());
+    }
+    archive
+        .file_names()
+        .find(|n| n.starts_with("bugreport") && n.ends_with(".txt"))
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("No bugreport text in the archive"))
+}
+
+fn read_main_entry(path: &Path, limit: Option<u64>) -> Result<String> {
+    let file = File::open(path).map_err(|e| eyre!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(file)?;
+    let name = main_entry_name(&mut archive)?;
+    let mut entry = archive.by_name(&name)?;
+    let mut bytes = Vec::new();
+    match limit {
+        Some(limit) => (&mut entry).take(limit).read_to_end(&mut bytes)?,
+        None => entry.read_to_end(&mut bytes)?,
+    };
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+pub fn read_summary(path: &Path) -> Result<BugreportSummary> {
+    let modified = fs::metadata(path)?.modified()?;
+    let (time, fingerprint) = parse_header(&read_main_entry(path, Some(HEADER_BYTES))?);
+    Ok(BugreportSummary {
+        path: path.to_path_buf(),
+        device: device_from_file_name(path),
+        fingerprint,
+        time: time.unwrap_or_else(|| {
+            DateTime::<Local>::from(modified)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        }),
+        modified,
+    })
+}
+
+/// The reports in `dir`, newest first. Zips that cannot be read are listed
+/// without header fields so they can still be deleted.
+pub fn list_bugreports(dir: &Path) -> Result<Vec<BugreportSummary>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(eyre!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut reports = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "zip") {
+            continue;
+        }
+        let summary = read_summary(&path).unwrap_or_else(|e| BugreportSummary {
+            device: device_from_file_name(&path),
+            fingerprint: Some(format!("unreadable: {e}")),
+            time: String::new(),
+            modified: SystemTime::UNIX_EPOCH,
+            path,
+        });
+        reports.push(summary);
+    }
+    reports.sort_by_key(|r| std::cmp::Reverse(r.modified));
+    Ok(reports)
+}
+
+pub fn read_contents(path: &Path) -> Result<BugreportContents> {
+    let text = read_main_entry(path, None)?;
+    let sections = parse_sections(&text);
+    Ok(BugreportContents { text, sections })
+}
+
+/// The name in a `------ NAME (command) ------` section header. Footers such
+/// as `------ 0.5s was the duration of 'NAME' ------` do not count.
+fn section_name(line: &str) -> Option<&str> {
+    let name = line
+        .trim_end()
+        .strip_prefix("------ ")?
+        .strip_suffix(" ------")?
+        .trim();
+    (!name.is_empty() && !name.contains("was the duration of")).then_some(name)
+}
+
+/// Splits a report at its section headers. Text before the first one is the
+/// dumpstate header.
+pub fn parse_sections(text: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut name = "HEADER".to_string();
+    let mut start = 0;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if let Some(next) = section_name(line) {
+            if offset > start {
+                sections.push(Section {
+                    name,
+                    range: start..offset,
+                });
+            }
+            name = next.to_string();
+            start = offset;
+        }
+        offset += line.len();
+    }
+    if offset > start {
+        sections.push(Section {
+            name,
+            range: start..offset,
+        });
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    const REPORT: &str = "\
+========================================================
+== dumpstate: 2025-03-09 14:05:07
+========================================================
+
+Build: UP1A.231005.007
+Build fingerprint: 'google/sdk_gphone64/emu64xa:14/UP1A/1:userdebug/dev-keys'
+------ UPTIME (uptime) ------
+up 2 days
+------ 0.010s was the duration of 'UPTIME' ------
+------ SYSTEM LOG (logcat -v threadtime -d *:v) ------
+03-09 14:05:00.000 I ActivityManager: Start proc
+";
+
+    #[test]
+    fn parses_progress_lines() {
+        assert_eq!(
+            parse_line("PROGRESS:120/4000\n"),
+            Some(BugreportEvent::Progress(120, 4000))
+        );
+        assert_eq!(
+            parse_line("OK:/bugreports/b.zip"),
+            Some(BugreportEvent::Ok("/bugreports/b.zip".into()))
+        );
+        assert_eq!(
+            parse_line("FAIL: no space left"),
+            Some(BugreportEvent::Fail("no space left".into()))
+        );
+        assert_eq!(parse_line("PROGRESS:x/4000"), None);
+        assert_eq!(parse_line("dumpstate started"), None);
+    }
+
+    #[test]
+    fn splits_sections_and_finds_the_main_log() {
+        let sections = parse_sections(REPORT);
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "HEADER",
+                "UPTIME (uptime)",
+                "SYSTEM LOG (logcat -v threadtime -d *:v)"
+            ]
+        );
+        let contents = BugreportContents {
+            text: REPORT.to_string(),
+            sections,
+        };
+        let uptime = contents.section_text(&contents.sections[1]);
+        assert!(uptime.starts_with("------ UPTIME") && uptime.contains("was the duration"));
+        assert_eq!(contents.main_log(), Some(2));
+    }
+
+    #[test]
+    fn reads_the_archive_header() {
+        let dir = std::env::temp_dir().join(format!("lazyadb-bugreport-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Pixel_7_Pro_20250309-140507.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file("main_entry.txt", options).unwrap();
+        zip.write_all(b"bugreport-emu64xa.txt").unwrap();
+        zip.start_file("bugreport-emu64xa.txt", options).unwrap();
+        zip.write_all(REPORT.as_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        let reports = list_bugreports(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].device, "Pixel 7 Pro");
+        assert_eq!(reports[0].time, "2025-03-09 14:05:07");
+        assert_eq!(
+            reports[0].fingerprint.as_deref(),
+            Some("google/sdk_gphone64/emu64xa:14/UP1A/1:userdebug/dev-keys")
+        );
+    }
+}
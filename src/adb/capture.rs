@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::graphics::RgbaImage;
+
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    pub path: PathBuf,
+    pub image: RgbaImage,
+}
+
+/// `Pixel_7_20250101-120000.png`: the device name made filesystem-safe,
+/// then the local capture time.
+pub fn capture_file_name(device_name: &str, time: DateTime<Local>, extension: &str) -> String {
+    let name: String = device_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}_{}.{extension}", time.format("%Y%m%d-%H%M%S"))
+}
+
+/// Writes a capture into `dir`, creating it if needed.
+pub fn save_capture(dir: &Path, file_name: &str, data: &[u8]) -> Result<PathBuf> {
+    fs::create_dir_all(dir).map_err(|e| eyre!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(file_name);
+    fs::write(&path, data).map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn file_name_is_sanitized_and_timestamped() {
+        let time = Local.with_ymd_and_hms(2025, 3, 9, 14, 5, 7).unwrap();
+        assert_eq!(
+            capture_file_name("Pixel 7 Pro", time, "png"),
+            "Pixel_7_Pro_20250309-140507.png"
+        );
+        assert_eq!(
+            capture_file_name("192.168.1.5:5555", time, "mp4"),
+            "192_168_1_5_5555_20250309-140507.mp4"
+        );
+    }
+}
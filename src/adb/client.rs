@@ -1,13 +1,50 @@
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
 
 use color_eyre::{Result, eyre::eyre};
 use tracing::info;
 
+use super::activities::{
+    Display, RESUMED_ACTIVITY_COMMAND, parse_activity_stack, parse_resumed_activity,
+};
+use super::battery::BatteryChange;
+use super::bugreport::{BugreportEvent, parse_line};
+use super::console::{Console, ConsoleCommand, console_port, read_auth_token};
+use super::cpu::{parse_cpu_stat, parse_cpufreq, parse_thermal, sysfs_command};
+use super::dev_options::{DevOption, parse_state, state_command};
 use super::device::{ConnectionType, Device, parse_device_list};
 use super::device_info::*;
+use super::display::{DISPLAY_STATE_COMMAND, DisplayChange, DisplayState, parse_display_state};
+use super::dumpsys::parse_service_list;
 use super::emulator::{Avd, parse_avd_list};
+use super::forward::{PortRule, parse_forward_list, parse_reverse_list};
+use super::input::{ADB_KEYBOARD_IME, InputEvent, is_adb_keyboard};
+use super::intent::{AmResult, Intent, parse_am_output};
+use super::locale::{
+    LOCALE_STATE_COMMAND, LocaleScope, LocaleState, app_locale_command, parse_locale_state,
+    system_locale_command, timezone_command,
+};
+use super::network::{
+    NetworkInfo, host_proxy, host_proxy_rule, parse_active_network, parse_http_proxy,
+    parse_ip_addr, parse_routes,
+};
+use super::notifications::{DISMISS_SNOOZE_MS, Notification, parse_notifications};
+use super::permissions::{
+    AppOpMode, PackagePermissions, PermissionKind, parse_appops, parse_package_list,
+    parse_package_permissions,
+};
+use super::processes::{ProcessInfo, Signal, parse_ps, parse_top_cpu};
+use super::screenrecord::{RecordOptions, STOP_TIMEOUT, interrupt_args, remote_segment_path};
+use super::settings::{SettingChange, SettingsNamespace, parse_settings_list};
+use super::shell::ShellSession;
+use super::ui_hierarchy::{UI_DUMP_PATH, UiNode, parse_hierarchy};
 
+#[derive(Clone)]
 pub struct AdbClient {
     adb_path: String,
     emulator_path: String,
@@ -55,6 +92,12 @@ impl AdbClient {
     }
 
     pub fn run_for_device(&self, serial: &str, args: &[&str]) -> Result<String> {
+        let stdout = self.run_for_device_raw(serial, args)?;
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+
+    /// Like `run_for_device`, for commands whose output is binary.
+    pub fn run_for_device_raw(&self, serial: &str, args: &[&str]) -> Result<Vec<u8>> {
         let output = Command::new(&self.adb_path)
             .arg("-s")
             .arg(serial)
@@ -67,7 +110,7 @@ impl AdbClient {
             return Err(eyre!("adb command failed: {}", stderr.trim()));
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(output.stdout)
     }
 
     pub fn list_avds(&self) -> Result<Vec<String>> {
@@ -160,11 +203,22 @@ impl AdbClient {
         self.run_for_device(serial, &["emu", "kill"])
     }
 
+    /// Runs a command on the console of an `emulator-NNNN` device, which
+    /// listens on localhost at port NNNN.
+    pub fn emulator_console(&self, serial: &str, command: &ConsoleCommand) -> Result<String> {
+        let port = console_port(serial).ok_or_else(|| eyre!("{} is not an emulator", serial))?;
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+        let mut console = Console::connect(addr, read_auth_token().as_deref())?;
+        console.send(&command.line())
+    }
+
     pub fn fetch_device_info(&self, device: &Device) -> Result<DeviceInfo> {
         let serial = &device.serial;
 
         // Batch property read
-        let props_output = self.run_for_device(serial, &["shell", "getprop"]).unwrap_or_default();
+        let props_output = self
+            .run_for_device(serial, &["shell", "getprop"])
+            .unwrap_or_default();
         let props = parse_getprop(&props_output);
 
         // Battery
@@ -185,6 +239,15 @@ impl AdbClient {
             .ok()
             .and_then(|out| parse_ram(&out));
 
+        // CPU load, frequencies and thermal zones
+        let cpu = self
+            .run_for_device(serial, &["shell", "cat", "/proc/stat"])
+            .ok()
+            .and_then(|out| parse_cpu_stat(&out));
+        let sysfs = self
+            .run_for_device(serial, &["shell", &sysfs_command()])
+            .unwrap_or_default();
+
         // Screen
         let screen = {
             let size = self
@@ -251,14 +314,665 @@ impl AdbClient {
             } else {
                 props.locale
             },
+            timezone: if props.timezone.is_empty() {
+                "N/A".to_string()
+            } else {
+                props.timezone
+            },
             battery,
             storage,
             ram,
             screen,
             wifi,
+            cpu,
+            cpu_freqs: parse_cpufreq(&sysfs),
+            thermal: parse_thermal(&sysfs),
+        })
+    }
+
+    pub fn packages(&self, serial: &str) -> Result<Vec<String>> {
+        let output = self.run_for_device(serial, &["shell", "pm", "list", "packages"])?;
+        Ok(parse_package_list(&output))
+    }
+
+    pub fn package_permissions(&self, serial: &str, package: &str) -> Result<PackagePermissions> {
+        let dump = self.run_for_device(serial, &["shell", "dumpsys", "package", package])?;
+        let permissions = parse_package_permissions(&dump);
+
+        // appops is missing on very old releases; show permissions regardless
+        let app_ops = self
+            .run_for_device(serial, &["shell", "appops", "get", package])
+            .map(|out| parse_appops(&out))
+            .unwrap_or_default();
+
+        Ok(PackagePermissions {
+            serial: serial.to_string(),
+            package: package.to_string(),
+            permissions,
+            app_ops,
+        })
+    }
+
+    pub fn set_permission(
+        &self,
+        serial: &str,
+        package: &str,
+        permission: &str,
+        grant: bool,
+    ) -> Result<()> {
+        let verb = if grant { "grant" } else { "revoke" };
+        self.run_for_device(serial, &["shell", "pm", verb, package, permission])?;
+        Ok(())
+    }
+
+    /// Revokes every changeable runtime permission and clears the user-set flags,
+    /// so the next request shows the system dialog again.
+    pub fn reset_runtime_permissions(&self, serial: &str, package: &str) -> Result<()> {
+        let current = self.package_permissions(serial, package)?;
+        for perm in current
+            .permissions
+            .iter()
+            .filter(|p| p.kind == PermissionKind::Runtime && p.is_changeable())
+        {
+            if perm.granted == Some(true) {
+                self.set_permission(serial, package, &perm.name, false)?;
+            }
+            // Not available before Android 10
+            let _ = self.run_for_device(
+                serial,
+                &[
+                    "shell",
+                    "pm",
+                    "clear-permission-flags",
+                    package,
+                    &perm.name,
+                    "user-set",
+                    "user-fixed",
+                ],
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_app_op(&self, serial: &str, package: &str, op: &str, mode: AppOpMode) -> Result<()> {
+        self.run_for_device(
+            serial,
+            &["shell", "appops", "set", package, op, mode.as_str()],
+        )?;
+        Ok(())
+    }
+
+    pub fn processes(&self, serial: &str) -> Result<Vec<ProcessInfo>> {
+        let ps = self.run_for_device(
+            serial,
+            &["shell", "ps", "-A", "-o", "PID,PPID,USER,RSS,VSZ,S,NAME"],
+        )?;
+        let mut processes = parse_ps(&ps);
+
+        // CPU usage is best effort; older toolbox `top` has a different layout
+        if let Ok(top) = self.run_for_device(serial, &["shell", "top", "-b", "-n", "1"]) {
+            let cpu = parse_top_cpu(&top);
+            for process in &mut processes {
+                if let Some(value) = cpu.get(&process.pid) {
+                    process.cpu = *value;
+                }
+            }
+        }
+
+        Ok(processes)
+    }
+
+    pub fn kill_process(&self, serial: &str, pid: u32, signal: Signal) -> Result<()> {
+        let pid = pid.to_string();
+        self.run_for_device(serial, &["shell", "kill", "-s", signal.as_str(), &pid])?;
+        Ok(())
+    }
+
+    pub fn process_meminfo(&self, serial: &str, pid: u32) -> Result<String> {
+        let pid = pid.to_string();
+        self.run_for_device(serial, &["shell", "dumpsys", "meminfo", &pid])
+    }
+
+    pub fn properties(&self, serial: &str) -> Result<BTreeMap<String, String>> {
+        let output = self.run_for_device(serial, &["shell", "getprop"])?;
+        Ok(parse_all_props(&output))
+    }
+
+    pub fn set_property(&self, serial: &str, key: &str, value: &str) -> Result<()> {
+        let output = self.run_for_device(
+            serial,
+            &["shell", "setprop", &shell_quote(key), &shell_quote(value)],
+        )?;
+        // Older adb doesn't propagate the exit status of the remote command
+        if output.contains("Failed to set property") {
+            return Err(eyre!("setprop {} failed: {}", key, output.trim()));
+        }
+        Ok(())
+    }
+
+    pub fn settings(
+        &self,
+        serial: &str,
+        namespace: SettingsNamespace,
+    ) -> Result<BTreeMap<String, String>> {
+        let output =
+            self.run_for_device(serial, &["shell", "settings", "list", namespace.as_str()])?;
+        Ok(parse_settings_list(&output))
+    }
+
+    pub fn put_setting(
+        &self,
+        serial: &str,
+        namespace: SettingsNamespace,
+        key: &str,
+        value: &str,
+    ) -> Result<()> {
+        let (key, value) = (shell_quote(key), shell_quote(value));
+        self.run_for_device(
+            serial,
+            &["shell", "settings", "put", namespace.as_str(), &key, &value],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_setting(
+        &self,
+        serial: &str,
+        namespace: SettingsNamespace,
+        key: &str,
+    ) -> Result<()> {
+        let key = shell_quote(key);
+        self.run_for_device(
+            serial,
+            &["shell", "settings", "delete", namespace.as_str(), &key],
+        )?;
+        Ok(())
+    }
+
+    /// Puts back every original value, continuing past failures so one bad
+    /// key doesn't leave the rest changed. Returns the changes that could not
+    /// be undone.
+    pub fn revert_settings(&self, serial: &str, changes: &[SettingChange]) -> Vec<SettingChange> {
+        changes
+            .iter()
+            .filter(|change| {
+                let result = match change.original {
+                    Some(ref value) => {
+                        self.put_setting(serial, change.namespace, &change.key, value)
+                    }
+                    None => self.delete_setting(serial, change.namespace, &change.key),
+                };
+                result.is_err()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// A PNG of the current screen; `exec-out` keeps the bytes intact.
+    pub fn screenshot(&self, serial: &str) -> Result<Vec<u8>> {
+        let data = self.run_for_device_raw(serial, &["exec-out", "screencap", "-p"])?;
+        if !data.starts_with(b"\x89PNG") {
+            let text = String::from_utf8_lossy(&data);
+            return Err(eyre!("screencap failed: {}", text.trim()));
+        }
+        Ok(data)
+    }
+
+    /// Records the screen into one or more remote files until `stop` is set or
+    /// the time limit runs out, starting a new segment each time `screenrecord`
+    /// hits its own limit. Returns the remote paths of the finished segments in
+    /// order, along with the error that cut the recording short; the caller
+    /// pulls and removes them either way.
+    pub fn screenrecord(
+        &self,
+        serial: &str,
+        options: &RecordOptions,
+        stamp: &str,
+        stop: &AtomicBool,
+    ) -> (Vec<String>, Result<()>) {
+        let mut segments = Vec::new();
+        let mut recorded = 0;
+        while let Some(secs) = options.next_segment(recorded) {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let remote = remote_segment_path(stamp, segments.len());
+            match self.record_segment(serial, options, secs, &remote, stop) {
+                Ok(stopped) => {
+                    segments.push(remote);
+                    if stopped {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = self.remove_file(serial, &remote);
+                    return (segments, Err(e));
+                }
+            }
+            recorded += secs;
+        }
+        (segments, Ok(()))
+    }
+
+    /// Runs one `screenrecord` of up to `secs` into `remote`; true when `stop`
+    /// ended it early.
+    fn record_segment(
+        &self,
+        serial: &str,
+        options: &RecordOptions,
+        secs: u64,
+        remote: &str,
+        stop: &AtomicBool,
+    ) -> Result<bool> {
+        let mut child = Command::new(&self.adb_path)
+            .args(["-s", serial, "shell"])
+            .args(options.args(secs, remote))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| eyre!("Failed to run 'adb shell screenrecord': {}", e))?;
+
+        let stopped = match self.wait_for_segment(serial, &mut child, remote, stop) {
+            Ok(stopped) => stopped,
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(e);
+            }
+        };
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let reason = if stderr.trim().is_empty() {
+                stdout
+            } else {
+                stderr
+            };
+            return Err(eyre!("screenrecord failed: {}", reason.trim()));
+        }
+        Ok(stopped)
+    }
+
+    fn wait_for_segment(
+        &self,
+        serial: &str,
+        child: &mut Child,
+        remote: &str,
+        stop: &AtomicBool,
+    ) -> Result<bool> {
+        let mut signalled: Option<Instant> = None;
+        while child.try_wait()?.is_none() {
+            match signalled {
+                // SIGINT lets screenrecord write the MP4 index; killing adb would not
+                None if stop.load(Ordering::Relaxed) => {
+                    let args = interrupt_args(remote);
+                    let args: Vec<&str> = ["shell"]
+                        .into_iter()
+                        .chain(args.iter().map(String::as_str))
+                        .collect();
+                    // pkill finds nothing when the segment ended on its own meanwhile
+                    if let Err(e) = self.run_for_device(serial, &args)
+                        && child.try_wait()?.is_none()
+                    {
+                        return Err(e);
+                    }
+                    signalled = Some(Instant::now());
+                }
+                Some(at) if at.elapsed() > STOP_TIMEOUT => {
+                    return Err(eyre!("screenrecord did not stop in time"));
+                }
+                _ => {}
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        Ok(signalled.is_some())
+    }
+
+    /// Runs `bugreportz -p`, calling `on_progress` with each `(done, total)`
+    /// it reports, and returns the remote path of the finished zip. Setting
+    /// `cancel` kills it.
+    pub fn bugreport(
+        &self,
+        serial: &str,
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> Result<String> {
+        let mut child = Command::new(&self.adb_path)
+            .args(["-s", serial, "shell", "bugreportz", "-p"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| eyre!("Failed to run 'adb shell bugreportz': {}", e))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| eyre!("bugreportz has no output"))?;
+        // Read on another thread so a quiet bugreportz can still be cancelled
+        let (line_tx, line_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if line_tx.send(line).is_err() {
+                    return;
+                }
+            }
+        });
+
+        let mut outcome = Err(eyre!("bugreportz ended without a report"));
+        loop {
+            let line = match line_rx.recv_timeout(Duration::from_millis(100)) {
+                _ if cancel.load(Ordering::Relaxed) => Err(eyre!("Bugreport cancelled")),
+                Ok(line) => line.map_err(Into::into),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(e);
+                }
+            };
+            match parse_line(&line) {
+                Some(BugreportEvent::Progress(done, total)) => on_progress(done, total),
+                Some(BugreportEvent::Ok(path)) => outcome = Ok(path),
+                Some(BugreportEvent::Fail(reason)) => {
+                    outcome = Err(eyre!("bugreportz failed: {}", reason));
+                }
+                Some(BugreportEvent::Begin(_)) | None => {}
+            }
+        }
+        child.wait()?;
+        outcome
+    }
+
+    pub fn pull(&self, serial: &str, remote: &str, local: &Path) -> Result<()> {
+        let local = local.to_string_lossy();
+        self.run_for_device(serial, &["pull", remote, &local])?;
+        Ok(())
+    }
+
+    pub fn remove_file(&self, serial: &str, remote: &str) -> Result<()> {
+        self.run_for_device(serial, &["shell", "rm", "-f", &shell_quote(remote)])?;
+        Ok(())
+    }
+
+    pub fn port_rules(&self, serial: &str) -> Result<Vec<PortRule>> {
+        let forwards = self.run_for_device(serial, &["forward", "--list"])?;
+        let reverses = self.run_for_device(serial, &["reverse", "--list"])?;
+        let mut rules = parse_forward_list(&forwards, serial);
+        rules.extend(parse_reverse_list(&reverses));
+        Ok(rules)
+    }
+
+    pub fn add_port_rule(&self, serial: &str, rule: &PortRule) -> Result<()> {
+        let [from, to] = rule.specs();
+        self.run_for_device(serial, &[rule.direction.as_str(), from, to])?;
+        Ok(())
+    }
+
+    pub fn remove_port_rule(&self, serial: &str, rule: &PortRule) -> Result<()> {
+        let args = [rule.direction.as_str(), "--remove", rule.listener()];
+        self.run_for_device(serial, &args)?;
+        Ok(())
+    }
+
+    /// Adds every rule, carrying on past failures; returns how many took.
+    pub fn apply_port_rules(&self, serial: &str, rules: &[PortRule]) -> Result<usize> {
+        let mut failed = Vec::new();
+        for rule in rules {
+            if let Err(e) = self.add_port_rule(serial, rule) {
+                failed.push(format!("{} {rule} ({e})", rule.direction));
+            }
+        }
+        if !failed.is_empty() {
+            return Err(eyre!("Failed to apply {}", failed.join(", ")));
+        }
+        Ok(rules.len())
+    }
+
+    pub fn simulate_battery(&self, serial: &str, change: BatteryChange) -> Result<()> {
+        let args = change.shell_args();
+        let mut cmd = vec!["shell"];
+        cmd.extend(args.iter().map(String::as_str));
+        self.run_for_device(serial, &cmd)?;
+        Ok(())
+    }
+
+    pub fn network_info(&self, serial: &str) -> Result<NetworkInfo> {
+        let addr = self.run_for_device(serial, &["shell", "ip", "addr"])?;
+        // Older toybox builds only know the main table
+        let routes = self
+            .run_for_device(serial, &["shell", "ip", "route", "show", "table", "all"])
+            .or_else(|_| self.run_for_device(serial, &["shell", "ip", "route"]))
+            .unwrap_or_default();
+        let connectivity = self
+            .run_for_device(serial, &["shell", "dumpsys", "connectivity"])
+            .unwrap_or_default();
+        Ok(NetworkInfo {
+            interfaces: parse_ip_addr(&addr),
+            routes: parse_routes(&routes),
+            active: parse_active_network(&connectivity),
+            http_proxy: self.http_proxy(serial)?,
         })
     }
 
+    /// The global `http_proxy` setting; `None` when unset or cleared.
+    pub fn http_proxy(&self, serial: &str) -> Result<Option<String>> {
+        let output = self.run_for_device(
+            serial,
+            &["shell", "settings", "get", "global", "http_proxy"],
+        )?;
+        Ok(parse_http_proxy(&output))
+    }
+
+    /// Points the device's global HTTP proxy at `port` on the host through
+    /// `adb reverse`, or clears it and drops the reverse.
+    pub fn set_host_proxy(&self, serial: &str, port: u16, enable: bool) -> Result<()> {
+        let rule = host_proxy_rule(port);
+        if enable {
+            self.add_port_rule(serial, &rule)?;
+            self.put_setting(
+                serial,
+                SettingsNamespace::Global,
+                "http_proxy",
+                &host_proxy(port),
+            )
+        } else {
+            // `:0` clears the proxy without a reboot, unlike deleting the key
+            self.put_setting(serial, SettingsNamespace::Global, "http_proxy", ":0")?;
+            // The reverse may already be gone, e.g. after a reconnect
+            self.remove_port_rule(serial, &rule).ok();
+            Ok(())
+        }
+    }
+
+    pub fn display_state(&self, serial: &str) -> Result<DisplayState> {
+        let output = self.run_for_device(serial, &["shell", DISPLAY_STATE_COMMAND])?;
+        Ok(parse_display_state(&output))
+    }
+
+    /// Makes the changes in order, stopping at the first that fails.
+    pub fn change_display(&self, serial: &str, changes: &[DisplayChange]) -> Result<()> {
+        for change in changes {
+            self.run_for_device(serial, &["shell", &change.shell_command()])?;
+        }
+        Ok(())
+    }
+
+    /// Current values of every developer option, keyed by `Store::key`.
+    pub fn dev_options(&self, serial: &str) -> Result<BTreeMap<String, String>> {
+        let output = self.run_for_device(serial, &["shell", &state_command()])?;
+        Ok(parse_state(&output))
+    }
+
+    pub fn set_dev_option(&self, serial: &str, option: &DevOption, value: &str) -> Result<()> {
+        self.run_for_device(serial, &["shell", &option.write_command(value)])?;
+        Ok(())
+    }
+
+    pub fn dumpsys_services(&self, serial: &str) -> Result<Vec<String>> {
+        let output = self.run_for_device(serial, &["shell", "dumpsys", "-l"])?;
+        Ok(parse_service_list(&output))
+    }
+
+    pub fn dumpsys(&self, serial: &str, service: &str) -> Result<String> {
+        self.run_for_device(serial, &["shell", "dumpsys", &shell_quote(service)])
+    }
+
+    /// The `package/.Class` of the activity in the foreground, if any.
+    pub fn resumed_activity(&self, serial: &str) -> Result<Option<String>> {
+        let output = self.run_for_device(serial, &["shell", RESUMED_ACTIVITY_COMMAND])?;
+        Ok(parse_resumed_activity(&output))
+    }
+
+    pub fn locale_state(&self, serial: &str) -> Result<LocaleState> {
+        let output = self.run_for_device(serial, &["shell", LOCALE_STATE_COMMAND])?;
+        let mut state = parse_locale_state(&output);
+        state.foreground = self
+            .resumed_activity(serial)?
+            .and_then(|component| component.split_once('/').map(|(p, _)| p.to_string()));
+        Ok(state)
+    }
+
+    pub fn set_locale(&self, serial: &str, tag: &str, scope: &LocaleScope) -> Result<()> {
+        let state = self.locale_state(serial)?;
+        if let Some(error) = state.scope_error(scope) {
+            return Err(eyre!("{}", error));
+        }
+        let command = match scope {
+            LocaleScope::System => system_locale_command(tag, state.api_level),
+            LocaleScope::App(package) => app_locale_command(package, tag),
+        };
+        let output = self.run_for_device(serial, &["shell", &command])?;
+        // Silent on success; unknown packages and bad tags are printed
+        if !output.trim().is_empty() {
+            return Err(eyre!("Setting the locale failed: {}", output.trim()));
+        }
+        Ok(())
+    }
+
+    pub fn set_timezone(&self, serial: &str, zone: &str) -> Result<()> {
+        let state = self.locale_state(serial)?;
+        let output =
+            self.run_for_device(serial, &["shell", &timezone_command(zone, state.api_level)])?;
+        let now = self.locale_state(serial)?.timezone;
+        if now != zone {
+            return Err(eyre!(
+                "The time zone is still {now}: {}",
+                output.lines().next().unwrap_or_default().trim()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn activity_stack(&self, serial: &str) -> Result<Vec<Display>> {
+        let output =
+            self.run_for_device(serial, &["shell", "dumpsys", "activity", "activities"])?;
+        Ok(parse_activity_stack(&output))
+    }
+
+    /// Removes a root task and every task and activity in it.
+    pub fn remove_task(&self, serial: &str, root_id: u32) -> Result<()> {
+        let output = self.run_for_device(
+            serial,
+            &["shell", "am", "stack", "remove", &root_id.to_string()],
+        )?;
+        check_shell_output("am stack remove", &output)
+    }
+
+    pub fn notifications(&self, serial: &str) -> Result<Vec<Notification>> {
+        let output =
+            self.run_for_device(serial, &["shell", "dumpsys", "notification", "--noredact"])?;
+        Ok(parse_notifications(&output))
+    }
+
+    /// Snoozes the notification out of the shade; see `DISMISS_SNOOZE_MS`.
+    pub fn dismiss_notification(&self, serial: &str, key: &str) -> Result<()> {
+        let output = self.run_for_device(
+            serial,
+            &[
+                "shell",
+                "cmd",
+                "notification",
+                "snooze",
+                "--for",
+                &DISMISS_SNOOZE_MS.to_string(),
+                &shell_quote(key),
+            ],
+        )?;
+        // Releases without `snooze` print the usage text and exit 0
+        if !output.contains("snoozing") {
+            return Err(eyre!(
+                "This device cannot dismiss notifications from the shell: {}",
+                output.lines().next().unwrap_or_default().trim()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn ui_hierarchy(&self, serial: &str) -> Result<Vec<UiNode>> {
+        let direct = self
+            .run_for_device(serial, &["exec-out", "uiautomator", "dump", "/dev/tty"])
+            .unwrap_or_default();
+        if direct.contains("</hierarchy>") {
+            return parse_hierarchy(&direct);
+        }
+        // Some releases refuse to write to the tty; go through a file instead
+        let output =
+            self.run_for_device(serial, &["shell", "uiautomator", "dump", UI_DUMP_PATH])?;
+        if !output.contains("dumped to") {
+            return parse_hierarchy(&output);
+        }
+        let xml = self.run_for_device(serial, &["exec-out", "cat", UI_DUMP_PATH])?;
+        let _ = self.remove_file(serial, UI_DUMP_PATH);
+        parse_hierarchy(&xml)
+    }
+
+    pub fn launch_intent(&self, serial: &str, intent: &Intent) -> Result<AmResult> {
+        let args = intent.shell_args();
+        let mut cmd = vec!["shell"];
+        cmd.extend(args.iter().map(String::as_str));
+        parse_am_output(&self.run_for_device(serial, &cmd)?)
+    }
+
+    /// Fails unless ADB Keyboard is the active keyboard, which `ImeText`
+    /// needs.
+    pub fn check_adb_keyboard(&self, serial: &str) -> Result<()> {
+        let input_method = self.run_for_device(
+            serial,
+            &["shell", "settings", "get", "secure", "default_input_method"],
+        )?;
+        if !is_adb_keyboard(&input_method) {
+            return Err(eyre!(
+                "Pasting non-ASCII text needs ADB Keyboard as the active keyboard \
+                 (adb shell ime set {})",
+                ADB_KEYBOARD_IME
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn send_input(&self, serial: &str, event: &InputEvent) -> Result<()> {
+        let args = event.shell_args();
+        let mut cmd = vec!["shell"];
+        cmd.extend(args.iter().map(String::as_str));
+        let output = self.run_for_device(serial, &cmd)?;
+        check_shell_output("input", &output)
+    }
+
+    pub fn open_shell(
+        &self,
+        serial: &str,
+        rows: u16,
+        cols: u16,
+    ) -> Result<(ShellSession, Box<dyn Read + Send>)> {
+        ShellSession::spawn(&self.adb_path, serial, rows, cols)
+    }
+
     pub fn disconnect_device(&self, serial: &str) -> Result<()> {
         let output = Command::new(&self.adb_path)
             .args(["disconnect", serial])
@@ -278,6 +992,20 @@ impl AdbClient {
     }
 }
 
+/// `am`, `input` and the like report failures on stdout and still exit 0, so
+/// an exception or `Error` there fails `what`.
+fn check_shell_output(what: &str, output: &str) -> Result<()> {
+    if output.contains("Exception") || output.starts_with("Error") {
+        return Err(eyre!("{} failed: {}", what, output.trim()));
+    }
+    Ok(())
+}
+
+/// Quotes an argument for the device shell, which re-splits what `adb shell` sends.
+pub(super) fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
 fn resolve_emulator_path() -> String {
     // 1. Try ANDROID_HOME or ANDROID_SDK_ROOT
     let sdk_dir = std::env::var("ANDROID_HOME")
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use color_eyre::{Result, eyre::eyre};
+use directories::BaseDirs;
+
+/// Where the emulator keeps the token its console asks for.
+pub const AUTH_TOKEN_FILE: &str = ".emulator_console_auth_token";
+
+pub const POWER_STATUSES: [&str; 5] =
+    ["charging", "discharging", "not-charging", "full", "unknown"];
+/// `network speed` profiles, slowest first.
+pub const NETWORK_SPEEDS: [&str; 9] = [
+    "gsm", "hscsd", "gprs", "edge", "umts", "hsdpa", "lte", "evdo", "full",
+];
+/// `network delay` profiles; `none` turns latency off.
+pub const NETWORK_DELAYS: [&str; 4] = ["none", "gprs", "edge", "umts"];
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A command for the emulator console.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleCommand {
+    GeoFix { longitude: f64, latitude: f64 },
+    Sms { from: String, text: String },
+    Call(String),
+    CancelCall(String),
+    PowerCapacity(u8),
+    PowerStatus(&'static str),
+    NetworkSpeed(&'static str),
+    NetworkDelay(&'static str),
+    FingerTouch(u32),
+}
+
+impl ConsoleCommand {
+    /// The line sent to the console. The console reads one command per line,
+    /// so line breaks in an SMS become spaces.
+    pub fn line(&self) -> String {
+        match self {
+            Self::GeoFix {
+                longitude,
+                latitude,
+            } => format!("geo fix {longitude} {latitude}"),
+            Self::Sms { from, text } => {
+                format!("sms send {from} {}", text.replace(['\r', '\n'], " "))
+            }
+            Self::Call(number) => format!("gsm call {number}"),
+            Self::CancelCall(number) => format!("gsm cancel {number}"),
+            Self::PowerCapacity(percent) => format!("power capacity {percent}"),
+            Self::PowerStatus(status) => format!("power status {status}"),
+            Self::NetworkSpeed(speed) => format!("network speed {speed}"),
+            Self::NetworkDelay(delay) => format!("network delay {delay}"),
+            Self::FingerTouch(id) => format!("finger touch {id}"),
+        }
+    }
+}
+
+impl fmt::Display for ConsoleCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.line())
+    }
+}
+
+/// `emulator-5554` listens for its console on port 5554.
+pub fn console_port(serial: &str) -> Option<u16> {
+    serial.strip_prefix("emulator-")?.parse().ok()
+}
+
+/// The token in `~/.emulator_console_auth_token`; `None` when the file is
+/// missing or empty, which turns authentication off.
+pub fn read_auth_token() -> Option<String> {
+    let path = BaseDirs::new()?.home_dir().join(AUTH_TOKEN_FILE);
+    let token = std::fs::read_to_string(path).ok()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// `+1 555-0100`: digits with an optional leading `+`, spaces and dashes
+/// dropped.
+pub fn parse_phone_number(value: &str) -> Result<String> {
+    let number: String = value.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    let digits = number.strip_prefix('+').unwrap_or(&number);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(eyre!("'{}' is not a phone number", value.trim()));
+    }
+    Ok(number)
+}
+
+/// `longitude latitude`, in that order like `geo fix`; a comma may separate
+/// them.
+pub fn parse_geo_fix(value: &str) -> Result<ConsoleCommand> {
+    let numbers: Vec<f64> = value
+        .split([' ', ','])
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()
+        .ok_or_else(|| eyre!("Enter the longitude and latitude in degrees"))?;
+    let [longitude, latitude] = numbers.as_slice() else {
+        return Err(eyre!("Enter the longitude and latitude in degrees"));
+    };
+    if !(-180.0..=180.0).contains(longitude) || !(-90.0..=90.0).contains(latitude) {
+        return Err(eyre!(
+            "Longitude runs from -180 to 180, latitude from -90 to 90"
+        ));
+    }
+    Ok(ConsoleCommand::GeoFix {
+        longitude: *longitude,
+        latitude: *latitude,
+    })
+}
+
+/// A connection to an emulator console, authenticated when it asks.
+pub struct Console {
+    reader: BufReader<TcpStream>,
+    stream: TcpStream,
+}
+
+impl Console {
+    pub fn connect(addr: SocketAddr, token: Option<&str>) -> Result<Self> {
+        let stream = TcpStream::connect_timeout(&addr, TIMEOUT)
+            .map_err(|e| eyre!("Can't reach the emulator console on {}: {}", addr, e))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        let mut console = Self {
+            reader: BufReader::new(stream.try_clone()?),
+            stream,
+        };
+        let banner = console.read_reply()?;
+        if banner.contains("Authentication required") {
+            let token = token.ok_or_else(|| {
+                eyre!(
+                    "The emulator console needs the token in ~/{}",
+                    AUTH_TOKEN_FILE
+                )
+            })?;
+            console
+                .send(&format!("auth {token}"))
+                .map_err(|e| eyre!("Emulator console authentication failed: {}", e))?;
+        }
+        Ok(console)
+    }
+
+    /// Runs one command and returns what it printed before `OK`. A `KO` reply
+    /// is an error carrying the console's message.
+    pub fn send(&mut self, line: &str) -> Result<String> {
+        self.stream.write_all(format!("{line}\n").as_bytes())?;
+        self.read_reply()
+    }
+
+    fn read_reply(&mut self) -> Result<String> {
+        let mut output = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(eyre!("The emulator console closed the connection"));
+            }
+            let line = line.trim_end();
+            if line == "OK" {
+                return Ok(output.join("\n"));
+            }
+            if let Some(error) = line.strip_prefix("KO") {
+                return Err(eyre!("{}", error.trim_start_matches(':').trim()));
+            }
+            output.push(line.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+
+    /// Serves one connection like the emulator does, requiring `token`, and
+    /// hands back the lines it was sent.
+    fn fake_console(token: &'static str) -> (SocketAddr, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            stream
+                .write_all(
+                    b"Android Console: Authentication required\r\n\
+                      Android Console: type 'auth <auth_token>' to authenticate\r\n\
+                      Android Console: you can find your <auth_token> in \r\n\
+                      '/home/me/.emulator_console_auth_token'\r\nOK\r\n",
+                )
+                .unwrap();
+            let mut received = Vec::new();
+            let mut authenticated = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let reply = match line.split_once(' ') {
+                    Some(("auth", given)) if given == token => {
+                        authenticated = true;
+                        "Android Console: type 'help' for a list of commands\r\nOK\r\n"
+                    }
+                    Some(("auth", _)) => {
+                        "KO: authentication token does not match ~/.emulator_console_auth_token\r\n"
+                    }
+                    _ if !authenticated => "KO: unknown command, try 'help'\r\n",
+                    Some(("power", "capacity 101")) => "KO: Usage: \"capacity <percentage>\"\r\n",
+                    Some(("avd", "name")) => "Pixel_8_API_34\r\nOK\r\n",
+                    _ => "OK\r\n",
+                };
+                received.push(line);
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+            received
+        });
+        (addr, server)
+    }
+
+    #[test]
+    fn authenticates_and_sends_commands() {
+        let (addr, server) = fake_console("s3cret");
+        let mut console = Console::connect(addr, Some("s3cret")).unwrap();
+        let geo = parse_geo_fix("-122.084, 37.422").unwrap();
+        assert_eq!(console.send(&geo.line()).unwrap(), "");
+        assert_eq!(console.send("avd name").unwrap(), "Pixel_8_API_34");
+        let error = console
+            .send(&ConsoleCommand::PowerCapacity(101).line())
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Usage"));
+        drop(console);
+
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                "auth s3cret",
+                "geo fix -122.084 37.422",
+                "avd name",
+                "power capacity 101"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejected_or_missing_tokens_fail_to_connect() {
+        let (addr, server) = fake_console("s3cret");
+        let error = Console::connect(addr, Some("stale")).err().unwrap();
+        assert!(error.to_string().contains("does not match"));
+        assert_eq!(server.join().unwrap(), ["auth stale"]);
+
+        let (addr, server) = fake_console("s3cret");
+        let error = Console::connect(addr, None).err().unwrap();
+        assert!(error.to_string().contains(AUTH_TOKEN_FILE));
+        assert!(server.join().unwrap().is_empty());
+    }
+
+    #[test]
+    fn command_lines() {
+        let sms = ConsoleCommand::Sms {
+            from: parse_phone_number("+1 555-0100").unwrap(),
+            text: "code 1234\nexpires soon".into(),
+        };
+        assert_eq!(sms.line(), "sms send +15550100 code 1234 expires soon");
+        assert_eq!(
+            ConsoleCommand::CancelCall("5550100".into()).line(),
+            "gsm cancel 5550100"
+        );
+        assert_eq!(
+            ConsoleCommand::NetworkDelay("none").line(),
+            "network delay none"
+        );
+        assert!(parse_phone_number("555; kill").is_err());
+        assert!(parse_geo_fix("37.4").is_err());
+        assert!(parse_geo_fix("200 10").is_err());
+        assert_eq!(console_port("emulator-5556"), Some(5556));
+        assert_eq!(console_port("R58M123"), None);
+    }
+}
@@ -0,0 +1,282 @@
+/// Thermal zones at or above this are flagged as hot.
+pub const HOT_TEMP_C: f64 = 70.0;
+
+/// Sysfs files read alongside `/proc/stat`: per-core frequencies and every
+/// thermal zone's type and temperature.
+const SYSFS_PATHS: [&str; 5] = [
+    "/sys/devices/system/cpu/cpu[0-9]*/cpufreq/scaling_cur_freq",
+    "/sys/devices/system/cpu/cpu[0-9]*/cpufreq/scaling_max_freq",
+    "/sys/devices/system/cpu/cpu[0-9]*/cpufreq/cpuinfo_max_freq",
+    "/sys/class/thermal/thermal_zone*/type",
+    "/sys/class/thermal/thermal_zone*/temp",
+];
+
+/// A device shell command printing each of `SYSFS_PATHS` as `path:value`.
+/// Offline cores and sensors fail to read, which makes grep exit non-zero;
+/// the `true` keeps adb from treating the partial output as a failure.
+pub fn sysfs_command() -> String {
+    format!("grep -H . {} 2>/dev/null; true", SYSFS_PATHS.join(" "))
+}
+
+/// Cumulative jiffies from a `cpu` line of `/proc/stat`; load is the
+/// change between two readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub total: u64,
+    /// Idle plus I/O wait.
+    pub idle: u64,
+}
+
+impl CpuTimes {
+    /// Percentage of the time since `earlier` spent busy.
+    pub fn load_since(&self, earlier: &CpuTimes) -> Option<f64> {
+        let total = self.total.checked_sub(earlier.total)?;
+        let idle = self.idle.checked_sub(earlier.idle)?;
+        if total == 0 {
+            return None;
+        }
+        Some(100.0 * total.saturating_sub(idle) as f64 / total as f64)
+    }
+}
+
+/// The aggregate line of `/proc/stat` and one per online core.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuStat {
+    pub total: CpuTimes,
+    pub cores: Vec<(u32, CpuTimes)>,
+}
+
+/// Busy percentages between two `CpuStat` readings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuLoad {
+    pub total: Option<f64>,
+    /// Cores online in both readings.
+    pub cores: Vec<(u32, f64)>,
+}
+
+impl CpuStat {
+    pub fn load_since(&self, earlier: &CpuStat) -> CpuLoad {
+        let cores = self
+            .cores
+            .iter()
+            .filter_map(|(core, now)| {
+                let (_, before) = earlier.cores.iter().find(|(c, _)| c == core)?;
+                Some((*core, now.load_since(before)?))
+            })
+            .collect();
+        CpuLoad {
+            total: self.total.load_since(&earlier.total),
+            cores,
+        }
+    }
+}
+
+/// Parses `/proc/stat`'s `cpu  user nice system idle iowait irq softirq ...`
+/// line and the `cpuN` lines after it.
+pub fn parse_cpu_stat(output: &str) -> Option<CpuStat> {
+    let mut total = None;
+    let mut cores = Vec::new();
+    for line in output.lines() {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next().and_then(|w| w.strip_prefix("cpu")) else {
+            continue;
+        };
+        let fields: Vec<u64> = words.map_while(|f| f.parse().ok()).collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        // Guest time is already counted in user and nice
+        let times = CpuTimes {
+            total: fields.iter().take(8).sum(),
+            idle: fields[3] + fields.get(4).copied().unwrap_or(0),
+        };
+        if name.is_empty() {
+            total = Some(times);
+        } else if let Ok(core) = name.parse() {
+            cores.push((core, times));
+        }
+    }
+    Some(CpuStat {
+        total: total?,
+        cores,
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoreFreq {
+    pub core: u32,
+    pub cur_khz: Option<u64>,
+    /// `cpuinfo_max_freq`: what the hardware can do.
+    pub max_khz: Option<u64>,
+    /// `scaling_max_freq`: the cap thermal mitigation lowers.
+    pub limit_khz: Option<u64>,
+}
+
+impl CoreFreq {
+    pub fn throttled(&self) -> bool {
+        matches!((self.limit_khz, self.max_khz), (Some(limit), Some(max)) if limit < max)
+    }
+}
+
+/// Picks the cpufreq lines out of `sysfs_command` output.
+pub fn parse_cpufreq(output: &str) -> Vec<CoreFreq> {
+    let mut freqs: Vec<CoreFreq> = Vec::new();
+    for (path, value) in output.lines().filter_map(|l| l.split_once(':')) {
+        let Some(rest) = path.strip_prefix("/sys/devices/system/cpu/cpu") else {
+            continue;
+        };
+        let Some((core, file)) = rest.split_once("/cpufreq/") else {
+            continue;
+        };
+        let (Ok(core), Ok(khz)) = (core.parse::<u32>(), value.trim().parse::<u64>()) else {
+            continue;
+        };
+        let index = match freqs.iter().position(|f| f.core == core) {
+            Some(i) => i,
+            None => {
+                freqs.push(CoreFreq {
+                    core,
+                    ..Default::default()
+                });
+                freqs.len() - 1
+            }
+        };
+        let freq = &mut freqs[index];
+        match file {
+            "scaling_cur_freq" => freq.cur_khz = Some(khz),
+            "scaling_max_freq" => freq.limit_khz = Some(khz),
+            "cpuinfo_max_freq" => freq.max_khz = Some(khz),
+            _ => {}
+        }
+    }
+    freqs.sort_by_key(|f| f.core);
+    freqs
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalZone {
+    pub zone: u32,
+    /// The sensor name, e.g. `cpu-0-0-usr`, `battery` or `skin-therm`.
+    pub kind: String,
+    pub temp_c: f64,
+}
+
+impl ThermalZone {
+    pub fn is_hot(&self) -> bool {
+        self.temp_c >= HOT_TEMP_C
+    }
+}
+
+/// Picks the thermal zone lines out of `sysfs_command` output, hottest first.
+/// Most kernels report millidegrees, a few whole degrees.
+pub fn parse_thermal(output: &str) -> Vec<ThermalZone> {
+    let mut kinds = Vec::new();
+    let mut temps = Vec::new();
+    for (path, value) in output.lines().filter_map(|l| l.split_once(':')) {
+        let Some(rest) = path.strip_prefix("/sys/class/thermal/thermal_zone") else {
+            continue;
+        };
+        let Some((zone, file)) = rest.split_once('/') else {
+            continue;
+        };
+        let Ok(zone) = zone.parse::<u32>() else {
+            continue;
+        };
+        match file {
+            "type" => kinds.push((zone, value.trim().to_string())),
+            "temp" => {
+                if let Ok(raw) = value.trim().parse::<f64>() {
+                    let temp_c = if raw.abs() >= 1000.0 { raw / 1000.0 } else { raw };
+                    temps.push((zone, temp_c));
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut zones: Vec<ThermalZone> = temps
+        .into_iter()
+        // Disabled sensors read as absolute zero or similar
+        .filter(|(_, temp_c)| *temp_c > -40.0)
+        .map(|(zone, temp_c)| ThermalZone {
+            zone,
+            kind: kinds
+                .iter()
+                .find(|(z, _)| *z == zone)
+                .map(|(_, kind)| kind.clone())
+                .unwrap_or_else(|| format!("zone{zone}")),
+            temp_c,
+        })
+        .collect();
+    zones.sort_by(|a, b| b.temp_c.total_cmp(&a.temp_c));
+    zones
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_core_load_comes_from_deltas() {
+        let before = parse_cpu_stat(
+            "cpu  100 0 100 700 100 0 0 0 0 0\n\
+             cpu0 50 0 50 350 50 0 0 0 0 0\n\
+             cpu1 50 0 50 350 50 0 0 0 0 0\n\
+             intr 12345 0 0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            before.total,
+            CpuTimes {
+                total: 1000,
+                idle: 800
+            }
+        );
+        // cpu1 went offline in between
+        let after = parse_cpu_stat(
+            "cpu  400 0 100 1000 100 0 0 0 0 0\n\
+             cpu0 250 0 50 450 50 0 0 0 0 0\n",
+        )
+        .unwrap();
+        let load = after.load_since(&before);
+        assert_eq!(load.total, Some(50.0));
+        assert_eq!(load.cores, [(0, 200.0 * 100.0 / 300.0)]);
+        assert_eq!(before.load_since(&after).total, None);
+    }
+
+    #[test]
+    fn cpufreq_flags_capped_cores() {
+        let output = "\
+/sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq:1804800
+/sys/devices/system/cpu/cpu4/cpufreq/scaling_cur_freq:1209600
+/sys/devices/system/cpu/cpu0/cpufreq/scaling_max_freq:1804800
+/sys/devices/system/cpu/cpu4/cpufreq/scaling_max_freq:1497600
+/sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq:1804800
+/sys/devices/system/cpu/cpu4/cpufreq/cpuinfo_max_freq:2419200
+/sys/class/thermal/thermal_zone0/temp:41000
+";
+        let freqs = parse_cpufreq(output);
+        assert_eq!(freqs.len(), 2);
+        assert_eq!(freqs[0].cur_khz, Some(1_804_800));
+        assert!(!freqs[0].throttled());
+        assert!(freqs[1].throttled());
+    }
+
+    #[test]
+    fn thermal_zones_are_normalised_and_sorted() {
+        let output = "\
+/sys/class/thermal/thermal_zone0/type:battery
+/sys/class/thermal/thermal_zone1/type:cpu-1-0-usr
+/sys/class/thermal/thermal_zone2/type:disabled
+/sys/class/thermal/thermal_zone0/temp:31
+/sys/class/thermal/thermal_zone1/temp:74500
+/sys/class/thermal/thermal_zone2/temp:-273000
+/sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq:1804800
+";
+        let zones = parse_thermal(output);
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].kind, "cpu-1-0-usr");
+        assert_eq!(zones[0].temp_c, 74.5);
+        assert!(zones[0].is_hot());
+        assert_eq!(zones[1].temp_c, 31.0);
+    }
+}
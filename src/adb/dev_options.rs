@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use super::settings::SettingsNamespace;
+
+/// `IBinder.SYSPROPS_TRANSACTION`: makes running apps re-read `debug.*`
+/// properties, so layout bounds and the HWUI overlays show up without a restart.
+pub const SYSPROPS_POKE: &str = "service call activity 1599295570 > /dev/null";
+
+/// Where a developer option keeps its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Store {
+    Setting(SettingsNamespace, &'static str),
+    /// A system property, re-read by apps after `SYSPROPS_POKE`.
+    Prop(&'static str),
+}
+
+impl Store {
+    /// The key the value is reported under by `state_command`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Setting(_, key) | Self::Prop(key) => key,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DevOption {
+    pub label: &'static str,
+    pub store: Store,
+    /// `(value, label)` pairs cycled through in order; two-value options are
+    /// shown as on/off switches, off first.
+    pub values: &'static [(&'static str, &'static str)],
+    /// Assumed when the setting or property is unset.
+    pub default: &'static str,
+}
+
+const ON_OFF: &[(&str, &str)] = &[("0", "off"), ("1", "on")];
+
+const ANIMATION_SCALES: &[(&str, &str)] = &[
+    ("0", "off"),
+    ("0.5", "0.5x"),
+    ("1", "1x"),
+    ("1.5", "1.5x"),
+    ("2", "2x"),
+    ("5", "5x"),
+    ("10", "10x"),
+];
+
+pub const DEV_OPTIONS: [DevOption; 10] = [
+    DevOption {
+        label: "Stay awake while charging",
+        store: Store::Setting(SettingsNamespace::Global, "stay_on_while_plugged_in"),
+        // AC, USB and wireless chargers
+        values: &[("0", "off"), ("7", "on")],
+        default: "0",
+    },
+    DevOption {
+        label: "Show touches",
+        store: Store::Setting(SettingsNamespace::System, "show_touches"),
+        values: ON_OFF,
+        default: "0",
+    },
+    DevOption {
+        label: "Pointer location",
+        store: Store::Setting(SettingsNamespace::System, "pointer_location"),
+        values: ON_OFF,
+        default: "0",
+    },
+    DevOption {
+        label: "Show layout bounds",
+        store: Store::Prop("debug.layout"),
+        values: &[("false", "off"), ("true", "on")],
+        default: "false",
+    },
+    DevOption {
+        label: "Debug GPU overdraw",
+        store: Store::Prop("debug.hwui.overdraw"),
+        values: &[("false", "off"), ("show", "on")],
+        default: "false",
+    },
+    DevOption {
+        label: "Profile GPU rendering",
+        store: Store::Prop("debug.hwui.profile"),
+        values: &[("false", "off"), ("visual_bars", "on")],
+        default: "false",
+    },
+    DevOption {
+        label: "Don't keep activities",
+        store: Store::Setting(SettingsNamespace::Global, "always_finish_activities"),
+        values: ON_OFF,
+        default: "0",
+    },
+    DevOption {
+        label: "Window animation scale",
+        store: Store::Setting(SettingsNamespace::Global, "window_animation_scale"),
+        values: ANIMATION_SCALES,
+        default: "1",
+    },
+    DevOption {
+        label: "Transition animation scale",
+        store: Store::Setting(SettingsNamespace::Global, "transition_animation_scale"),
+        values: ANIMATION_SCALES,
+        default: "1",
+    },
+    DevOption {
+        label: "Animator duration scale",
+        store: Store::Setting(SettingsNamespace::Global, "animator_duration_scale"),
+        values: ANIMATION_SCALES,
+        default: "1",
+    },
+];
+
+impl DevOption {
+    pub fn is_switch(&self) -> bool {
+        self.values.len() == 2
+    }
+
+    /// The option's value in `state`, or its default when unset.
+    pub fn current<'a>(&self, state: &'a BTreeMap<String, String>) -> &'a str {
+        state
+            .get(self.store.key())
+            .map(String::as_str)
+            .unwrap_or(self.default)
+    }
+
+    /// The label of `value`, or the raw value when it is not one of ours.
+    pub fn describe<'a>(&self, value: &'a str) -> &'a str {
+        self.position(value).map_or(value, |i| self.values[i].1)
+    }
+
+    /// The value after `current`; unknown values start over from the first.
+    pub fn next_value(&self, current: &str) -> &'static str {
+        match self.position(current) {
+            Some(i) => self.values[(i + 1) % self.values.len()].0,
+            None => self.values[0].0,
+        }
+    }
+
+    /// Scales are compared numerically so `1.0` matches `1`.
+    fn position(&self, value: &str) -> Option<usize> {
+        let number = value.parse::<f64>().ok();
+        self.values
+            .iter()
+            .position(|(v, _)| *v == value || (number.is_some() && v.parse::<f64>().ok() == number))
+    }
+
+    /// The device shell command setting the option to `value`.
+    pub fn write_command(&self, value: &str) -> String {
+        match self.store {
+            Store::Setting(namespace, key) => format!("settings put {namespace} {key} {value}"),
+            Store::Prop(key) => format!("setprop {key} {value}; {SYSPROPS_POKE}"),
+        }
+    }
+}
+
+/// A device shell command printing `key=value` for every option.
+pub fn state_command() -> String {
+    let parts: Vec<String> = DEV_OPTIONS
+        .iter()
+        .map(|option| match option.store {
+            Store::Setting(namespace, key) => {
+                format!("echo {key}=$(settings get {namespace} {key})")
+            }
+            Store::Prop(key) => format!("echo {key}=$(getprop {key})"),
+        })
+        .collect();
+    format!("{}; true", parts.join("; "))
+}
+
+/// Parses `state_command` output, dropping unset values (`null` from
+/// `settings get`, an empty line from `getprop`).
+pub fn parse_state(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter(|(_, value)| !value.is_empty() && *value != "null")
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(label: &str) -> DevOption {
+        *DEV_OPTIONS.iter().find(|o| o.label == label).unwrap()
+    }
+
+    #[test]
+    fn unset_values_fall_back_to_defaults() {
+        let state = parse_state(
+            "stay_on_while_plugged_in=7\n\
+             show_touches=null\n\
+             debug.layout=\n\
+             window_animation_scale=0.5\n\
+             animator_duration_scale=1.0\n",
+        );
+        let awake = option("Stay awake while charging");
+        assert_eq!(awake.describe(awake.current(&state)), "on");
+        assert_eq!(option("Show touches").current(&state), "0");
+        assert_eq!(option("Show layout bounds").current(&state), "false");
+        let animator = option("Animator duration scale");
+        assert_eq!(animator.describe(animator.current(&state)), "1x");
+        assert_eq!(animator.next_value(animator.current(&state)), "1.5");
+    }
+
+    #[test]
+    fn props_are_poked_after_writing() {
+        let bounds = option("Show layout bounds");
+        assert_eq!(bounds.next_value("false"), "true");
+        assert_eq!(
+            bounds.write_command("true"),
+            format!("setprop debug.layout true; {SYSPROPS_POKE}")
+        );
+        assert_eq!(
+            option("Show touches").write_command("1"),
+            "settings put system show_touches 1"
+        );
+        assert!(state_command().ends_with("; true"));
+    }
+}
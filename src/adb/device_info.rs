@@ -1,9 +1,25 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
+use super::battery::health_name;
+use super::cpu::{CoreFreq, CpuStat, ThermalZone};
+
 #[derive(Debug, Clone)]
 pub struct BatteryInfo {
     pub level: u8,
     pub status: String,
+    /// The raw `BATTERY_STATUS_*` code behind `status`.
+    pub status_code: u32,
+    pub ac_powered: bool,
+    pub usb_powered: bool,
+    pub health: Option<String>,
+    pub temperature_c: Option<f32>,
+    pub voltage_mv: Option<u32>,
+    pub technology: Option<String>,
+    pub charge_counter_uah: Option<u64>,
+    /// Set by `dumpsys battery set`/`unplug`; the values above are then
+    /// simulated until `dumpsys battery reset`.
+    pub simulated: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -40,11 +56,15 @@ pub struct DeviceInfo {
     pub connection_type: String,
     pub abi: String,
     pub locale: String,
+    pub timezone: String,
     pub battery: Option<BatteryInfo>,
     pub storage: Option<StorageInfo>,
     pub ram: Option<RamInfo>,
     pub screen: Option<ScreenInfo>,
     pub wifi: Option<WifiInfo>,
+    pub cpu: Option<CpuStat>,
+    pub cpu_freqs: Vec<CoreFreq>,
+    pub thermal: Vec<ThermalZone>,
 }
 
 impl fmt::Display for BatteryInfo {
@@ -74,16 +94,10 @@ pub fn parse_getprop(output: &str) -> GetpropResult {
                 "ro.build.version.release" => result.android_version = value.to_string(),
                 "ro.build.version.sdk" => result.api_level = value.to_string(),
                 "ro.product.cpu.abi" => result.abi = value.to_string(),
-                "persist.sys.locale" => {
-                    if result.locale.is_empty() {
-                        result.locale = value.to_string();
-                    }
-                }
-                "ro.product.locale" => {
-                    if result.locale.is_empty() {
-                        result.locale = value.to_string();
-                    }
+                "persist.sys.locale" | "ro.product.locale" if result.locale.is_empty() => {
+                    result.locale = value.to_string();
                 }
+                "persist.sys.timezone" => result.timezone = value.to_string(),
                 "ro.product.model" => result.model = value.to_string(),
                 _ => {}
             }
@@ -92,6 +106,15 @@ pub fn parse_getprop(output: &str) -> GetpropResult {
     result
 }
 
+/// Parses every `getprop` line into a key-sorted map.
+pub fn parse_all_props(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| parse_prop_line(line.trim()))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
 fn parse_prop_line(line: &str) -> Option<(&str, &str)> {
     // Format: [key]: [value]
     let line = line.strip_prefix('[')?;
@@ -108,12 +131,21 @@ pub struct GetpropResult {
     pub api_level: String,
     pub abi: String,
     pub locale: String,
+    pub timezone: String,
 }
 
 pub fn parse_battery(output: &str) -> Option<BatteryInfo> {
     let mut level: Option<u8> = None;
     let mut status_code: Option<u32> = None;
     let mut plugged: Option<u32> = None;
+    let mut ac_powered = false;
+    let mut usb_powered = false;
+    let mut health = None;
+    let mut temperature_c = None;
+    let mut voltage_mv = None;
+    let mut technology = None;
+    let mut charge_counter_uah = None;
+    let mut simulated = false;
 
     for line in output.lines() {
         let line = line.trim();
@@ -123,11 +155,29 @@ pub fn parse_battery(output: &str) -> Option<BatteryInfo> {
             status_code = val.trim().parse().ok();
         } else if let Some(val) = line.strip_prefix("plugged:") {
             plugged = val.trim().parse().ok();
+        } else if let Some(val) = line.strip_prefix("AC powered:") {
+            ac_powered = val.trim() == "true";
+        } else if let Some(val) = line.strip_prefix("USB powered:") {
+            usb_powered = val.trim() == "true";
+        } else if let Some(val) = line.strip_prefix("health:") {
+            health = val.trim().parse().ok().map(|c| health_name(c).to_string());
+        } else if let Some(val) = line.strip_prefix("temperature:") {
+            // Tenths of a degree Celsius
+            temperature_c = val.trim().parse::<f32>().ok().map(|t| t / 10.0);
+        } else if let Some(val) = line.strip_prefix("voltage:") {
+            voltage_mv = val.trim().parse().ok();
+        } else if let Some(val) = line.strip_prefix("technology:") {
+            technology = Some(val.trim().to_string()).filter(|t| !t.is_empty());
+        } else if let Some(val) = line.strip_prefix("Charge counter:") {
+            charge_counter_uah = val.trim().parse().ok();
+        } else if line.starts_with("(UPDATES STOPPED") {
+            simulated = true;
         }
     }
 
     let level = level?;
-    let status_str = match status_code.unwrap_or(0) {
+    let status_code = status_code.unwrap_or(0);
+    let status_str = match status_code {
         2 => {
             let plug_type = match plugged.unwrap_or(0) {
                 1 => "AC",
@@ -146,6 +196,15 @@ pub fn parse_battery(output: &str) -> Option<BatteryInfo> {
     Some(BatteryInfo {
         level,
         status: status_str,
+        status_code,
+        ac_powered,
+        usb_powered,
+        health,
+        temperature_c,
+        voltage_mv,
+        technology,
+        charge_counter_uah,
+        simulated,
     })
 }
 
@@ -185,25 +244,59 @@ pub fn parse_ram(output: &str) -> Option<RamInfo> {
     })
 }
 
-pub fn parse_screen_size(output: &str) -> Option<String> {
-    // "Physical size: 1080x2400"
-    for line in output.lines() {
-        if let Some(size) = line.strip_prefix("Physical size:") {
-            let size = size.trim();
-            return Some(size.replace('x', "\u{00d7}"));
+/// A `wm size` or `wm density` reading.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WmValue {
+    pub physical: String,
+    /// Set by `wm size <WxH>` or `wm density <dpi>` until reset.
+    pub overridden: Option<String>,
+}
+
+impl WmValue {
+    /// What the device is currently using.
+    pub fn effective(&self) -> &str {
+        self.overridden.as_deref().unwrap_or(&self.physical)
+    }
+
+    /// The effective value, followed by the physical one when overridden.
+    pub fn describe(&self, format: impl Fn(&str) -> String) -> String {
+        match self.overridden {
+            Some(ref value) => format!("{} (physical {})", format(value), format(&self.physical)),
+            None => format(&self.physical),
         }
     }
-    None
 }
 
-pub fn parse_screen_density(output: &str) -> Option<String> {
-    // "Physical density: 420"
-    for line in output.lines() {
-        if let Some(density) = line.strip_prefix("Physical density:") {
-            return Some(format!("{}dpi", density.trim()));
+/// Parses `wm size` or `wm density`, e.g. "Physical size: 1080x2400" and an
+/// optional "Override size: 720x1280".
+pub fn parse_wm_value(output: &str, what: &str) -> Option<WmValue> {
+    let (physical, overridden) = (format!("Physical {what}:"), format!("Override {what}:"));
+    let mut value: Option<WmValue> = None;
+    let mut override_value = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(v) = line.strip_prefix(&physical) {
+            value = Some(WmValue {
+                physical: v.trim().to_string(),
+                overridden: None,
+            });
+        } else if let Some(v) = line.strip_prefix(&overridden) {
+            override_value = Some(v.trim().to_string());
         }
     }
-    None
+    value.map(|v| WmValue {
+        overridden: override_value,
+        ..v
+    })
+}
+
+pub fn parse_screen_size(output: &str) -> Option<String> {
+    let size = parse_wm_value(output, "size")?;
+    Some(size.describe(|s| s.replace('x', "\u{00d7}")))
+}
+
+pub fn parse_screen_density(output: &str) -> Option<String> {
+    let density = parse_wm_value(output, "density")?;
+    Some(density.describe(|d| format!("{d}dpi")))
 }
 
 pub fn parse_wifi(output: &str) -> Option<WifiInfo> {
@@ -212,23 +305,23 @@ pub fn parse_wifi(output: &str) -> Option<WifiInfo> {
 
     for line in output.lines() {
         let trimmed = line.trim();
-        if ssid.is_none() {
-            if let Some(val) = trimmed.strip_prefix("mWifiInfo") {
-                // Look for SSID in mWifiInfo line: SSID: "MyNetwork", ...
-                if let Some(start) = val.find("SSID: ") {
-                    let rest = &val[start + 6..];
-                    let ssid_val = rest.split(',').next().unwrap_or("").trim();
-                    let ssid_val = ssid_val.trim_matches('"');
-                    if !ssid_val.is_empty() && ssid_val != "<unknown ssid>" {
-                        ssid = Some(ssid_val.to_string());
-                    }
+        if ssid.is_none()
+            && let Some(val) = trimmed.strip_prefix("mWifiInfo")
+        {
+            // Look for SSID in mWifiInfo line: SSID: "MyNetwork", ...
+            if let Some(start) = val.find("SSID: ") {
+                let rest = &val[start + 6..];
+                let ssid_val = rest.split(',').next().unwrap_or("").trim();
+                let ssid_val = ssid_val.trim_matches('"');
+                if !ssid_val.is_empty() && ssid_val != "<unknown ssid>" {
+                    ssid = Some(ssid_val.to_string());
                 }
-                if let Some(start) = val.find("IP: ") {
-                    let rest = &val[start + 4..];
-                    let ip_val = rest.split([',', '/']).next().unwrap_or("").trim();
-                    if !ip_val.is_empty() && ip_val != "0.0.0.0" {
-                        ip = Some(ip_val.to_string());
-                    }
+            }
+            if let Some(start) = val.find("IP: ") {
+                let rest = &val[start + 4..];
+                let ip_val = rest.split([',', '/']).next().unwrap_or("").trim();
+                if !ip_val.is_empty() && ip_val != "0.0.0.0" {
+                    ip = Some(ip_val.to_string());
                 }
             }
         }
@@ -251,6 +344,7 @@ mod tests {
 [ro.build.version.sdk]: [34]
 [ro.product.cpu.abi]: [arm64-v8a]
 [persist.sys.locale]: [en-US]
+[persist.sys.timezone]: [Europe/Berlin]
 [ro.product.model]: [Pixel 7]
 [some.other.prop]: [value]
 ";
@@ -259,9 +353,25 @@ mod tests {
         assert_eq!(result.api_level, "34");
         assert_eq!(result.abi, "arm64-v8a");
         assert_eq!(result.locale, "en-US");
+        assert_eq!(result.timezone, "Europe/Berlin");
         assert_eq!(result.model, "Pixel 7");
     }
 
+    #[test]
+    fn test_parse_all_props() {
+        let output = "\
+[ro.product.model]: [Pixel 7]
+[dalvik.vm.heapsize]: [512m]
+[persist.sys.empty]: []
+not a property line
+";
+        let props = parse_all_props(output);
+        assert_eq!(props.len(), 3);
+        assert_eq!(props["ro.product.model"], "Pixel 7");
+        assert_eq!(props["persist.sys.empty"], "");
+        assert_eq!(props.keys().next().unwrap(), "dalvik.vm.heapsize");
+    }
+
     #[test]
     fn test_parse_battery() {
         let output = "\
@@ -280,6 +390,34 @@ Current Battery Service state:
         let info = parse_battery(output).unwrap();
         assert_eq!(info.level, 72);
         assert_eq!(info.status, "charging (USB)");
+        assert!(info.usb_powered && !info.ac_powered);
+        assert_eq!(info.health.as_deref(), Some("good"));
+        assert_eq!(info.temperature_c, Some(25.0));
+        assert!(!info.simulated);
+    }
+
+    #[test]
+    fn test_parse_battery_simulated() {
+        let output = "\
+Current Battery Service state:
+  (UPDATES STOPPED -- use 'reset' to restart)
+  AC powered: false
+  USB powered: false
+  Charge counter: 2874000
+  status: 3
+  health: 3
+  level: 5
+  voltage: 3612
+  temperature: 412
+  technology: Li-ion
+";
+        let info = parse_battery(output).unwrap();
+        assert!(info.simulated);
+        assert_eq!(info.level, 5);
+        assert_eq!(info.health.as_deref(), Some("overheat"));
+        assert_eq!(info.voltage_mv, Some(3612));
+        assert_eq!(info.technology.as_deref(), Some("Li-ion"));
+        assert_eq!(info.charge_counter_uah, Some(2_874_000));
     }
 
     #[test]
@@ -329,4 +467,16 @@ Buffers:          123000 kB
             Some("420dpi".to_string())
         );
     }
+
+    #[test]
+    fn test_parse_screen_overrides() {
+        assert_eq!(
+            parse_screen_size("Physical size: 1080x2400\nOverride size: 720x1280\n"),
+            Some("720\u{00d7}1280 (physical 1080\u{00d7}2400)".to_string())
+        );
+        assert_eq!(
+            parse_screen_density("Physical density: 420\nOverride density: 320\n"),
+            Some("320dpi (physical 420dpi)".to_string())
+        );
+    }
 }
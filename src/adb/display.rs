@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use super::device_info::{WmValue, parse_wm_value};
+use crate::config::save_toml;
+
+/// Reads everything the display view shows in one shell round trip.
+pub const DISPLAY_STATE_COMMAND: &str = "wm size; wm density; cmd uimode night; \
+    echo font_scale: $(settings get system font_scale); \
+    echo accelerometer_rotation: $(settings get system accelerometer_rotation); \
+    echo user_rotation: $(settings get system user_rotation)";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayState {
+    pub size: WmValue,
+    pub density: WmValue,
+    pub font_scale: f32,
+    /// `None` while night mode follows a schedule (`auto` or `custom`).
+    pub night_mode: Option<bool>,
+    /// Auto-rotate is off, so `user_rotation` decides the orientation.
+    pub rotation_locked: bool,
+    /// Quarter turns from the natural orientation, 0-3.
+    pub user_rotation: u8,
+}
+
+/// Parses `DISPLAY_STATE_COMMAND` output. `settings get` prints `null` for
+/// keys never written, which leaves the Android defaults.
+pub fn parse_display_state(output: &str) -> DisplayState {
+    let value = |key: &str| {
+        output
+            .lines()
+            .find_map(|l| l.trim().strip_prefix(key))
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && *v != "null")
+    };
+    DisplayState {
+        size: parse_wm_value(output, "size").unwrap_or_default(),
+        density: parse_wm_value(output, "density").unwrap_or_default(),
+        font_scale: value("font_scale:")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1.0),
+        night_mode: match value("Night mode:") {
+            Some("yes") => Some(true),
+            Some("no") => Some(false),
+            _ => None,
+        },
+        rotation_locked: value("accelerometer_rotation:") == Some("0"),
+        user_rotation: value("user_rotation:")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayChange {
+    /// `WxH`, or `None` to return to the physical size.
+    Size(Option<String>),
+    /// Dots per inch, or `None` to return to the physical density.
+    Density(Option<u32>),
+    FontScale(f32),
+    NightMode(bool),
+    RotationLock(bool),
+    /// Locks rotation at this many quarter turns.
+    Rotation(u8),
+}
+
+impl DisplayChange {
+    /// The device shell command making the change.
+    pub fn shell_command(&self) -> String {
+        match self {
+            Self::Size(Some(size)) => format!("wm size {size}"),
+            Self::Size(None) => "wm size reset".to_string(),
+            Self::Density(Some(dpi)) => format!("wm density {dpi}"),
+            Self::Density(None) => "wm density reset".to_string(),
+            Self::FontScale(scale) => format!("settings put system font_scale {scale}"),
+            Self::NightMode(on) => format!("cmd uimode night {}", if *on { "yes" } else { "no" }),
+            Self::RotationLock(locked) => format!(
+                "settings put system accelerometer_rotation {}",
+                if *locked { 0 } else { 1 }
+            ),
+            Self::Rotation(turns) => format!(
+                "settings put system accelerometer_rotation 0; \
+                 settings put system user_rotation {turns}"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DisplayChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Size(Some(size)) => write!(f, "Screen size set to {size}"),
+            Self::Size(None) => write!(f, "Screen size reset"),
+            Self::Density(Some(dpi)) => write!(f, "Density set to {dpi}dpi"),
+            Self::Density(None) => write!(f, "Density reset"),
+            Self::FontScale(scale) => write!(f, "Font scale set to {scale}"),
+            Self::NightMode(on) => write!(f, "Night mode {}", if *on { "on" } else { "off" }),
+            Self::RotationLock(true) => write!(f, "Rotation locked"),
+            Self::RotationLock(false) => write!(f, "Auto-rotate on"),
+            Self::Rotation(turns) => write!(f, "Rotation locked at {}\u{b0}", *turns as u32 * 90),
+        }
+    }
+}
+
+/// Checks a `WxH` size such as `1080x1920`.
+pub fn parse_size(input: &str) -> Result<String> {
+    let input = input.trim();
+    let valid = input.split_once('x').is_some_and(|(w, h)| {
+        w.parse::<u32>().is_ok_and(|w| w > 0) && h.parse::<u32>().is_ok_and(|h| h > 0)
+    });
+    if !valid {
+        return Err(eyre!("'{}' is not a size like 1080x1920", input));
+    }
+    Ok(input.to_string())
+}
+
+pub fn parse_density(input: &str) -> Result<u32> {
+    match input.trim().parse::<u32>() {
+        Ok(dpi) if (72..=1000).contains(&dpi) => Ok(dpi),
+        _ => Err(eyre!("Density must be a dpi between 72 and 1000")),
+    }
+}
+
+pub fn parse_font_scale(input: &str) -> Result<f32> {
+    match input.trim().parse::<f32>() {
+        Ok(scale) if (0.5..=3.0).contains(&scale) => Ok(scale),
+        _ => Err(eyre!("Font scale must be between 0.5 and 3.0")),
+    }
+}
+
+/// A named display configuration from the `[display_presets.<name>]` config
+/// tables; unset fields are left alone when applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisplayPreset {
+    /// `WxH`, e.g. `"720x1280"`.
+    #[serde(default)]
+    pub size: Option<String>,
+    #[serde(default)]
+    pub density: Option<u32>,
+    #[serde(default)]
+    pub font_scale: Option<f32>,
+    #[serde(default)]
+    pub night_mode: Option<bool>,
+    /// Locks rotation at this many quarter turns.
+    #[serde(default)]
+    pub rotation: Option<u8>,
+}
+
+impl DisplayPreset {
+    /// Captures what the device is showing now.
+    pub fn from_state(state: &DisplayState) -> Self {
+        let non_empty = |v: &str| (!v.is_empty()).then(|| v.to_string());
+        Self {
+            size: non_empty(state.size.effective()),
+            density: state.density.effective().parse().ok(),
+            font_scale: Some(state.font_scale),
+            night_mode: state.night_mode,
+            rotation: state.rotation_locked.then_some(state.user_rotation),
+        }
+    }
+
+    /// The preset's changes; a malformed value fails the whole preset so
+    /// typos in the config are noticed.
+    pub fn changes(&self) -> Result<Vec<DisplayChange>> {
+        let mut changes = Vec::new();
+        if let Some(ref size) = self.size {
+            changes.push(DisplayChange::Size(Some(parse_size(size)?)));
+        }
+        if let Some(dpi) = self.density {
+            let dpi = parse_density(&dpi.to_string())?;
+            changes.push(DisplayChange::Density(Some(dpi)));
+        }
+        if let Some(scale) = self.font_scale {
+            let scale = parse_font_scale(&scale.to_string())?;
+            changes.push(DisplayChange::FontScale(scale));
+        }
+        if let Some(on) = self.night_mode {
+            changes.push(DisplayChange::NightMode(on));
+        }
+        if let Some(turns) = self.rotation {
+            if turns > 3 {
+                return Err(eyre!("Rotation must be 0-3 quarter turns"));
+            }
+            changes.push(DisplayChange::Rotation(turns));
+        }
+        Ok(changes)
+    }
+}
+
+#[derive(Serialize)]
+struct DisplayPresetsFile<'a> {
+    display_presets: &'a BTreeMap<String, DisplayPreset>,
+}
+
+/// Writes the presets as a config file of `[display_presets.<name>]` tables.
+pub fn save_display_presets(path: &Path, presets: &BTreeMap<String, DisplayPreset>) -> Result<()> {
+    save_toml(
+        path,
+        &DisplayPresetsFile {
+            display_presets: presets,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_display_state() {
+        let output = "\
+Physical size: 1080x2400
+Override size: 720x1280
+Physical density: 420
+Night mode: yes
+font_scale: 1.3
+accelerometer_rotation: 0
+user_rotation: 1
+";
+        let state = parse_display_state(output);
+        assert_eq!(state.size.effective(), "720x1280");
+        assert_eq!(state.density.overridden, None);
+        assert_eq!(state.font_scale, 1.3);
+        assert_eq!(state.night_mode, Some(true));
+        assert!(state.rotation_locked);
+        assert_eq!(state.user_rotation, 1);
+
+        let defaults = parse_display_state("Night mode: auto\nfont_scale: null\n");
+        assert_eq!(defaults.font_scale, 1.0);
+        assert_eq!(defaults.night_mode, None);
+        assert!(!defaults.rotation_locked);
+    }
+
+    #[test]
+    fn input_is_validated() {
+        assert_eq!(parse_size(" 720x1280 ").unwrap(), "720x1280");
+        assert!(parse_size("720 x 1280").is_err());
+        assert!(parse_size("0x1280").is_err());
+        assert!(parse_density("40").is_err());
+        assert!(parse_font_scale("1.15").is_ok());
+        assert!(parse_font_scale("big").is_err());
+    }
+
+    #[test]
+    fn presets_round_trip_through_state() {
+        let state = parse_display_state(
+            "Physical size: 1080x2400\nPhysical density: 420\nNight mode: no\n\
+             accelerometer_rotation: 1\n",
+        );
+        let preset = DisplayPreset::from_state(&state);
+        assert_eq!(
+            preset.changes().unwrap(),
+            [
+                DisplayChange::Size(Some("1080x2400".into())),
+                DisplayChange::Density(Some(420)),
+                DisplayChange::FontScale(1.0),
+                DisplayChange::NightMode(false),
+            ]
+        );
+
+        let presets = BTreeMap::from([("small phone".to_string(), preset)]);
+        let text = toml::to_string(&DisplayPresetsFile {
+            display_presets: &presets,
+        })
+        .unwrap();
+        assert!(text.contains("[display_presets.\"small phone\"]"));
+        assert!(!text.contains("rotation"));
+    }
+
+    #[test]
+    fn rotation_unlocks_auto_rotate_first() {
+        assert_eq!(
+            DisplayChange::Rotation(3).shell_command(),
+            "settings put system accelerometer_rotation 0; settings put system user_rotation 3"
+        );
+        assert_eq!(DisplayChange::Size(None).shell_command(), "wm size reset");
+    }
+}
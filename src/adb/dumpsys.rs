@@ -0,0 +1,72 @@
+/// Parses `dumpsys -l`: a "Currently running services:" header followed by
+/// one indented service name per line.
+pub fn parse_service_list(output: &str) -> Vec<String> {
+    let mut services: Vec<String> = output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .map(|line| line.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    services.sort_by_key(|s| s.to_lowercase());
+    services.dedup();
+    services
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// For each line, the index of the last line of the section it opens: the
+/// run of following lines indented deeper than it. Blank lines inside a
+/// section belong to it; a line that opens nothing ends at itself.
+pub fn section_ends(lines: &[String]) -> Vec<usize> {
+    let mut ends: Vec<usize> = (0..lines.len()).collect();
+    // Lines whose section is still open, shallowest first
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let depth = indent(line);
+        while open.last().is_some_and(|(d, _)| *d >= depth) {
+            open.pop();
+        }
+        for (_, start) in &open {
+            ends[*start] = i;
+        }
+        open.push((depth, i));
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_list_skips_the_header() {
+        let output = "Currently running services:\n  window\n  activity\n  SurfaceFlinger\n";
+        assert_eq!(
+            parse_service_list(output),
+            ["activity", "SurfaceFlinger", "window"]
+        );
+    }
+
+    #[test]
+    fn sections_follow_indentation() {
+        let lines: Vec<String> = [
+            "WINDOW MANAGER",
+            "  Display 0:",
+            "    mBaseDisplayInfo",
+            "",
+            "    mOverrideDisplayInfo",
+            "  Display 1:",
+            "",
+            "ACTIVITY",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(section_ends(&lines), [5, 4, 2, 3, 4, 5, 6, 7]);
+    }
+}
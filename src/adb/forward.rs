@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::config::save_toml;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// A host socket reaching into the device.
+    Forward,
+    /// A device socket reaching back to the host.
+    Reverse,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Reverse => "reverse",
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// One `adb forward` or `adb reverse` rule, with each end named by the side
+/// it lives on rather than by adb's argument order, which flips for reverse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortRule {
+    pub direction: Direction,
+    pub host: String,
+    pub device: String,
+}
+
+impl PortRule {
+    /// Parses the two specs in adb's argument order, e.g. `tcp:8081 tcp:8081`.
+    pub fn parse(direction: Direction, specs: &str) -> Result<Self> {
+        let mut parts = specs.split_whitespace();
+        let (Some(first), Some(second), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(eyre!("Expected two socket specs, e.g. 'tcp:8081 tcp:8081'"));
+        };
+        let (host, device) = match direction {
+            Direction::Forward => (first, second),
+            Direction::Reverse => (second, first),
+        };
+        validate_spec(host, false)?;
+        validate_spec(device, direction == Direction::Forward)?;
+        Ok(Self {
+            direction,
+            host: host.to_string(),
+            device: device.to_string(),
+        })
+    }
+
+    /// The specs in adb's argument order.
+    pub fn specs(&self) -> [&str; 2] {
+        match self.direction {
+            Direction::Forward => [&self.host, &self.device],
+            Direction::Reverse => [&self.device, &self.host],
+        }
+    }
+
+    /// The spec `--remove` takes: the listening end.
+    pub fn listener(&self) -> &str {
+        self.specs()[0]
+    }
+}
+
+impl fmt::Display for PortRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [from, to] = self.specs();
+        write!(f, "{from} {to}")
+    }
+}
+
+/// Accepts the socket specs adb understands; `jdwp:<pid>` is only valid as
+/// the device end of a forward.
+fn validate_spec(spec: &str, allow_jdwp: bool) -> Result<()> {
+    let (kind, value) = spec
+        .split_once(':')
+        .ok_or_else(|| eyre!("'{}' is not a socket spec", spec))?;
+    let valid = match kind {
+        "tcp" => value.parse::<u16>().is_ok(),
+        "jdwp" => allow_jdwp && !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()),
+        "localabstract" | "localreserved" | "localfilesystem" | "dev" => !value.is_empty(),
+        _ => false,
+    };
+    if !valid {
+        return Err(eyre!("'{}' is not a valid socket spec here", spec));
+    }
+    Ok(())
+}
+
+/// Parses `adb forward --list`, which lists every device's forwards as
+/// `<serial> <host> <device>`; only `serial`'s are kept.
+pub fn parse_forward_list(output: &str, serial: &str) -> Vec<PortRule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.split_whitespace();
+            let (owner, host, device) = (cols.next()?, cols.next()?, cols.next()?);
+            (owner == serial).then(|| PortRule {
+                direction: Direction::Forward,
+                host: host.to_string(),
+                device: device.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `adb reverse --list`: `<transport> <device> <host>` per line.
+pub fn parse_reverse_list(output: &str) -> Vec<PortRule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.split_whitespace();
+            let (_, device, host) = (cols.next()?, cols.next()?, cols.next()?);
+            Some(PortRule {
+                direction: Direction::Reverse,
+                host: host.to_string(),
+                device: device.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// A named group of rules from the `[port_rules.<name>]` config tables,
+/// applied whenever a matching device comes online.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleSet {
+    /// Serials the set applies to; every device when empty.
+    #[serde(default)]
+    pub devices: Vec<String>,
+    /// Specs in adb's argument order, e.g. `"tcp:9222 localabstract:chrome_devtools_remote"`.
+    #[serde(default)]
+    pub forward: Vec<String>,
+    #[serde(default)]
+    pub reverse: Vec<String>,
+}
+
+impl RuleSet {
+    pub fn from_rules(rules: &[PortRule]) -> Self {
+        let specs = |direction| {
+            rules
+                .iter()
+                .filter(|r| r.direction == direction)
+                .map(PortRule::to_string)
+                .collect()
+        };
+        Self {
+            devices: Vec::new(),
+            forward: specs(Direction::Forward),
+            reverse: specs(Direction::Reverse),
+        }
+    }
+
+    pub fn applies_to(&self, serial: &str) -> bool {
+        self.devices.is_empty() || self.devices.iter().any(|d| d == serial)
+    }
+
+    /// The set's rules; a malformed entry fails the whole set so typos in the
+    /// config are noticed.
+    pub fn rules(&self) -> Result<Vec<PortRule>> {
+        let forward = self
+            .forward
+            .iter()
+            .map(|s| PortRule::parse(Direction::Forward, s));
+        let reverse = self
+            .reverse
+            .iter()
+            .map(|s| PortRule::parse(Direction::Reverse, s));
+        forward.chain(reverse).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len() + self.reverse.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Serialize)]
+struct RuleSetsFile<'a> {
+    port_rules: &'a BTreeMap<String, RuleSet>,
+}
+
+/// Writes the sets as a config file of `[port_rules.<name>]` tables.
+pub fn save_rule_sets(path: &Path, sets: &BTreeMap<String, RuleSet>) -> Result<()> {
+    save_toml(path, &RuleSetsFile { port_rules: sets })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_specs_are_given_device_first() {
+        let rule = PortRule::parse(Direction::Reverse, "tcp:8081 tcp:3000").unwrap();
+        assert_eq!(rule.device, "tcp:8081");
+        assert_eq!(rule.host, "tcp:3000");
+        assert_eq!(rule.listener(), "tcp:8081");
+        assert_eq!(rule.to_string(), "tcp:8081 tcp:3000");
+    }
+
+    #[test]
+    fn specs_are_validated() {
+        assert!(
+            PortRule::parse(
+                Direction::Forward,
+                "tcp:9222 localabstract:chrome_devtools_remote"
+            )
+            .is_ok()
+        );
+        assert!(PortRule::parse(Direction::Forward, "tcp:8700 jdwp:1234").is_ok());
+        assert!(PortRule::parse(Direction::Reverse, "jdwp:1234 tcp:8700").is_err());
+        assert!(PortRule::parse(Direction::Forward, "tcp:99999 tcp:1").is_err());
+        assert!(PortRule::parse(Direction::Forward, "tcp:1").is_err());
+        assert!(PortRule::parse(Direction::Forward, "udp:1 tcp:1").is_err());
+    }
+
+    #[test]
+    fn forward_list_is_filtered_by_serial() {
+        let output = "\
+emulator-5554 tcp:8000 tcp:8000
+R58M123 tcp:9222 localabstract:chrome_devtools_remote
+";
+        let rules = parse_forward_list(output, "R58M123");
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].device, "localabstract:chrome_devtools_remote");
+    }
+
+    #[test]
+    fn reverse_list_puts_device_spec_first() {
+        let rules = parse_reverse_list("UsbFfs tcp:8081 tcp:8081\nhost-12 tcp:5000 tcp:6000\n");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1].device, "tcp:5000");
+        assert_eq!(rules[1].host, "tcp:6000");
+    }
+
+    #[test]
+    fn rule_sets_round_trip_through_rules() {
+        let rules = vec![
+            PortRule::parse(Direction::Forward, "tcp:8000 tcp:8000").unwrap(),
+            PortRule::parse(Direction::Reverse, "tcp:8081 tcp:8081").unwrap(),
+        ];
+        let set = RuleSet::from_rules(&rules);
+        assert_eq!(set.rules().unwrap(), rules);
+        assert!(set.applies_to("anything"));
+
+        let pinned = RuleSet {
+            devices: vec!["R58M123".into()],
+            ..set
+        };
+        assert!(!pinned.applies_to("emulator-5554"));
+    }
+}
@@ -0,0 +1,221 @@
+use base64::Engine;
+
+use super::client::shell_quote;
+
+/// Characters per `input text` call; longer runs are split, since some
+/// releases drop characters from long arguments.
+const TEXT_CHUNK: usize = 200;
+
+/// Broadcast the ADB Keyboard IME types from, base64 encoded.
+const IME_TEXT_ACTION: &str = "ADB_INPUT_B64";
+/// The ADB Keyboard input method, as `ime set` takes it.
+pub const ADB_KEYBOARD_IME: &str = "com.android.adbkeyboard/.AdbIME";
+
+/// Something to inject with the device's `input` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputEvent {
+    /// A keycode name such as `KEYCODE_DPAD_UP`.
+    Key(String),
+    /// ASCII text.
+    Text(String),
+    /// Text `input` can't type, sent to the ADB Keyboard IME
+    /// (`com.android.adbkeyboard`) when that is the active keyboard.
+    ImeText(String),
+    /// A tap at screen pixels `(x, y)`.
+    Tap(i32, i32),
+}
+
+impl InputEvent {
+    /// Arguments after `adb shell`, quoted for the device shell.
+    pub fn shell_args(&self) -> Vec<String> {
+        match self {
+            Self::Key(code) => vec!["input".into(), "keyevent".into(), code.clone()],
+            // `input text` reads `%s` as a space; a literal space would end the word on older releases
+            Self::Text(text) => text_chunks(text)
+                .iter()
+                .enumerate()
+                .flat_map(|(i, chunk)| {
+                    let separator = (i > 0).then(|| "&&".to_string());
+                    separator.into_iter().chain([
+                        "input".into(),
+                        "text".into(),
+                        shell_quote(&chunk.replace(' ', "%s")),
+                    ])
+                })
+                .collect(),
+            Self::ImeText(text) => vec![
+                "am".into(),
+                "broadcast".into(),
+                "-a".into(),
+                IME_TEXT_ACTION.into(),
+                "--es".into(),
+                "msg".into(),
+                base64::engine::general_purpose::STANDARD.encode(text),
+            ],
+            Self::Tap(x, y) => vec!["input".into(), "tap".into(), x.to_string(), y.to_string()],
+        }
+    }
+}
+
+/// Splits text into the arguments of successive `input text` calls, each at
+/// most `TEXT_CHUNK` characters and none holding a literal `%s`.
+fn text_chunks(text: &str) -> Vec<String> {
+    let mut chunks = vec![String::new()];
+    let mut len = 0;
+    for c in text.chars() {
+        let chunk = chunks.last_mut().expect("never empty");
+        if len == TEXT_CHUNK || (c == 's' && chunk.ends_with('%')) {
+            chunks.push(String::new());
+            len = 0;
+        }
+        chunks.last_mut().expect("never empty").push(c);
+        len += 1;
+    }
+    chunks
+}
+
+/// The events that type a terminal paste: ASCII as text, line breaks and
+/// tabs as keys, anything else through the IME. A trailing line break, which
+/// copying a line tends to bring along, is dropped.
+pub fn paste_events(text: &str) -> Vec<InputEvent> {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut events: Vec<InputEvent> = Vec::new();
+    for c in text.trim_end_matches('\n').chars() {
+        let key = match c {
+            '\n' => Some("KEYCODE_ENTER"),
+            '\t' => Some("KEYCODE_TAB"),
+            _ => None,
+        };
+        match (key, events.last_mut()) {
+            (Some(code), _) => events.push(InputEvent::Key(code.into())),
+            (None, _) if c.is_ascii_control() => {}
+            (None, Some(InputEvent::Text(run))) if c.is_ascii() => run.push(c),
+            (None, Some(InputEvent::ImeText(run))) if !c.is_ascii() => run.push(c),
+            (None, _) if c.is_ascii() => events.push(InputEvent::Text(c.to_string())),
+            (None, _) => events.push(InputEvent::ImeText(c.to_string())),
+        }
+    }
+    events
+}
+
+/// Whether `default_input_method` names ADB Keyboard; any other keyboard
+/// ignores `ImeText` broadcasts without a word.
+pub fn is_adb_keyboard(input_method: &str) -> bool {
+    let package = ADB_KEYBOARD_IME.split('/').next().unwrap_or_default();
+    input_method.trim().split('/').next() == Some(package)
+}
+
+/// Whether `name` looks like an Android keycode, and so is safe to pass unquoted.
+pub fn is_keycode(name: &str) -> bool {
+    name.strip_prefix("KEYCODE_").is_some_and(|rest| {
+        !rest.is_empty()
+            && rest
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
+/// Merges runs of text queued for the same device into one `input text`
+/// call, which takes about as long as a single keystroke.
+pub fn coalesce(events: Vec<(String, InputEvent)>) -> Vec<(String, InputEvent)> {
+    let mut merged: Vec<(String, InputEvent)> = Vec::with_capacity(events.len());
+    for (serial, event) in events {
+        if let (Some((last_serial, InputEvent::Text(last))), InputEvent::Text(text)) =
+            (merged.last_mut(), &event)
+            && *last_serial == serial
+        {
+            last.push_str(text);
+            continue;
+        }
+        merged.push((serial, event));
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_escaped_for_input_and_the_shell() {
+        let args = InputEvent::Text("it's a test".into()).shell_args();
+        assert_eq!(args, ["input", "text", r"'it'\''s%sa%stest'"]);
+
+        let args = InputEvent::Text("50%sale".into()).shell_args();
+        assert_eq!(
+            args,
+            ["input", "text", "'50%'", "&&", "input", "text", "'sale'"]
+        );
+
+        let long = "x".repeat(TEXT_CHUNK + 1);
+        let args = InputEvent::Text(long).shell_args();
+        assert_eq!(args.len(), 7);
+        assert_eq!(args[6], "'x'");
+
+        let args = InputEvent::Key("KEYCODE_HOME".into()).shell_args();
+        assert_eq!(args, ["input", "keyevent", "KEYCODE_HOME"]);
+
+        let args = InputEvent::Tap(540, 1200).shell_args();
+        assert_eq!(args, ["input", "tap", "540", "1200"]);
+    }
+
+    #[test]
+    fn pastes_split_into_text_keys_and_ime_text() {
+        let events = paste_events("user@example.com\tpässwörd \"x\"\r\n");
+        assert_eq!(
+            events,
+            [
+                InputEvent::Text("user@example.com".into()),
+                InputEvent::Key("KEYCODE_TAB".into()),
+                InputEvent::Text("p".into()),
+                InputEvent::ImeText("ä".into()),
+                InputEvent::Text("ssw".into()),
+                InputEvent::ImeText("ö".into()),
+                InputEvent::Text("rd \"x\"".into()),
+            ]
+        );
+        let args = InputEvent::ImeText("ä".into()).shell_args();
+        assert_eq!(args.last().map(String::as_str), Some("w6Q="));
+    }
+
+    #[test]
+    fn keycode_names() {
+        assert!(is_keycode("KEYCODE_DPAD_CENTER"));
+        assert!(is_keycode("KEYCODE_F12"));
+        assert!(!is_keycode("KEYCODE_"));
+        assert!(!is_keycode("HOME"));
+        assert!(!is_keycode("KEYCODE_HOME; reboot"));
+    }
+
+    #[test]
+    fn adb_keyboard_is_recognised() {
+        assert!(is_adb_keyboard("com.android.adbkeyboard/.AdbIME\n"));
+        assert!(!is_adb_keyboard(
+            "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME"
+        ));
+        assert!(!is_adb_keyboard("null"));
+    }
+
+    #[test]
+    fn consecutive_text_for_one_device_is_merged() {
+        let text = |s: &str| InputEvent::Text(s.into());
+        let key = |s: &str| InputEvent::Key(s.into());
+        let events = vec![
+            ("A".to_string(), text("h")),
+            ("A".to_string(), text("i")),
+            ("B".to_string(), text("x")),
+            ("A".to_string(), key("KEYCODE_ENTER")),
+            ("A".to_string(), text("o")),
+            ("A".to_string(), text("k")),
+        ];
+        assert_eq!(
+            coalesce(events),
+            vec![
+                ("A".to_string(), text("hi")),
+                ("B".to_string(), text("x")),
+                ("A".to_string(), key("KEYCODE_ENTER")),
+                ("A".to_string(), text("ok")),
+            ]
+        );
+    }
+}
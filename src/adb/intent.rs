@@ -0,0 +1,384 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use super::client::shell_quote;
+use crate::config::save_toml;
+
+/// Entries kept per package; the oldest are dropped first.
+const HISTORY_PER_PACKAGE: usize = 20;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntentKind {
+    #[default]
+    Activity,
+    Service,
+    Broadcast,
+}
+
+impl IntentKind {
+    pub const ALL: [IntentKind; 3] = [Self::Activity, Self::Service, Self::Broadcast];
+
+    /// The `am` subcommand that delivers this kind of intent.
+    pub fn am_command(&self) -> &'static str {
+        match self {
+            Self::Activity => "start",
+            Self::Service => "start-service",
+            Self::Broadcast => "broadcast",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|k| *k == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for IntentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "am {}", self.am_command())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtraType {
+    #[default]
+    String,
+    Int,
+    Long,
+    Float,
+    Bool,
+    Uri,
+}
+
+impl ExtraType {
+    pub const ALL: [ExtraType; 6] = [
+        Self::String,
+        Self::Int,
+        Self::Long,
+        Self::Float,
+        Self::Bool,
+        Self::Uri,
+    ];
+
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Self::String => "--es",
+            Self::Int => "--ei",
+            Self::Long => "--el",
+            Self::Float => "--ef",
+            Self::Bool => "--ez",
+            Self::Uri => "--eu",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Extra {
+    pub kind: ExtraType,
+    pub key: String,
+    pub value: String,
+}
+
+/// Everything `am` needs to build an intent; empty fields are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Intent {
+    pub kind: IntentKind,
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub data: String,
+    /// `package/.Class` or `package/full.Class`.
+    #[serde(default)]
+    pub component: String,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Passed to `-f`, e.g. `0x10000000`.
+    #[serde(default)]
+    pub flags: String,
+    #[serde(default)]
+    pub extras: Vec<Extra>,
+}
+
+impl Intent {
+    pub fn is_empty(&self) -> bool {
+        self.action.is_empty() && self.data.is_empty() && self.component.is_empty()
+    }
+
+    /// Arguments after `adb shell`, quoted for the device shell.
+    pub fn shell_args(&self) -> Vec<String> {
+        let mut args = vec!["am".to_string(), self.kind.am_command().to_string()];
+        if self.kind == IntentKind::Activity {
+            // Waits for the launch so the result includes status and timings
+            args.push("-W".into());
+        }
+        let mut push = |flag: &str, value: &str| {
+            if !value.is_empty() {
+                args.extend([flag.to_string(), shell_quote(value)]);
+            }
+        };
+        push("-a", &self.action);
+        push("-d", &self.data);
+        for category in &self.categories {
+            push("-c", category);
+        }
+        push("-n", &self.component);
+        push("-f", &self.flags);
+        for extra in self.extras.iter().filter(|e| !e.key.is_empty()) {
+            args.extend([
+                extra.kind.flag().to_string(),
+                shell_quote(&extra.key),
+                shell_quote(&extra.value),
+            ]);
+        }
+        args
+    }
+
+    /// The package the intent is aimed at, used to group history: the
+    /// component's package, else the data URI's scheme and host.
+    pub fn target(&self) -> String {
+        if let Some((package, _)) = self.component.split_once('/') {
+            return package.to_string();
+        }
+        if let Some((scheme, rest)) = self.data.split_once("://") {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+            return format!("{scheme}://{host}");
+        }
+        if !self.data.is_empty() {
+            return self.data.clone();
+        }
+        "(implicit)".to_string()
+    }
+
+    /// One-line description for lists.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        for value in [&self.action, &self.data, &self.component] {
+            if !value.is_empty() {
+                parts.push(value.clone());
+            }
+        }
+        if !self.extras.is_empty() {
+            parts.push(format!("+{} extras", self.extras.len()));
+        }
+        parts.join("  ")
+    }
+}
+
+/// What `am` reported back.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AmResult {
+    /// `Key: value` lines such as `Status`, `Activity` and `TotalTime`.
+    pub fields: Vec<(String, String)>,
+    /// Everything else, e.g. the `Starting: Intent { ... }` echo.
+    pub lines: Vec<String>,
+}
+
+/// Parses `am start -W`, `am start-service` and `am broadcast` output. `am`
+/// exits 0 on most failures, so `Error` lines become the error here.
+pub fn parse_am_output(output: &str) -> Result<AmResult> {
+    let mut result = AmResult::default();
+    let mut errors = Vec::new();
+    for line in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if line.starts_with("Error") || line.starts_with("Exception") {
+            let message = line.strip_prefix("Error:").unwrap_or(line).trim();
+            errors.push(message.to_string());
+        } else if let Some((key, value)) = line.split_once(": ")
+            && !key.contains(' ')
+            && !value.starts_with("Intent {")
+        {
+            result.fields.push((key.to_string(), value.to_string()));
+        } else {
+            result.lines.push(line.to_string());
+        }
+    }
+    if !errors.is_empty() {
+        return Err(eyre!("{}", errors.join("; ")));
+    }
+    Ok(result)
+}
+
+/// Launched intents grouped by target, newest first, saved as TOML in the
+/// data dir.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntentHistory {
+    #[serde(default)]
+    packages: BTreeMap<String, Vec<Intent>>,
+}
+
+impl IntentHistory {
+    /// Reads the history file; a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| eyre!("Invalid intent history {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_toml(path, self)
+    }
+
+    /// Moves `intent` to the front of its target's list.
+    pub fn record(&mut self, intent: &Intent) {
+        let entries = self.packages.entry(intent.target()).or_default();
+        entries.retain(|e| e != intent);
+        entries.insert(0, intent.clone());
+        entries.truncate(HISTORY_PER_PACKAGE);
+    }
+
+    pub fn remove(&mut self, target: &str, index: usize) {
+        if let Some(entries) = self.packages.get_mut(target) {
+            if index < entries.len() {
+                entries.remove(index);
+            }
+            if entries.is_empty() {
+                self.packages.remove(target);
+            }
+        }
+    }
+
+    /// Every entry as `(target, index, intent)`, grouped by target.
+    pub fn entries(&self) -> Vec<(&str, usize, &Intent)> {
+        self.packages
+            .iter()
+            .flat_map(|(target, entries)| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, intent)| (target.as_str(), i, intent))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deep_link() -> Intent {
+        Intent {
+            action: "android.intent.action.VIEW".into(),
+            data: "myapp://orders/42?ref=it's".into(),
+            categories: vec!["android.intent.category.BROWSABLE".into()],
+            flags: "0x10000000".into(),
+            extras: vec![
+                Extra {
+                    kind: ExtraType::Bool,
+                    key: "debug".into(),
+                    value: "true".into(),
+                },
+                Extra {
+                    kind: ExtraType::String,
+                    key: String::new(),
+                    value: "ignored".into(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn args_quote_values_and_skip_empty_fields() {
+        assert_eq!(
+            deep_link().shell_args(),
+            [
+                "am",
+                "start",
+                "-W",
+                "-a",
+                "'android.intent.action.VIEW'",
+                "-d",
+                r"'myapp://orders/42?ref=it'\''s'",
+                "-c",
+                "'android.intent.category.BROWSABLE'",
+                "-f",
+                "'0x10000000'",
+                "--ez",
+                "'debug'",
+                "'true'",
+            ]
+        );
+
+        let broadcast = Intent {
+            kind: IntentKind::Broadcast,
+            action: "com.example.PING".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            broadcast.shell_args(),
+            ["am", "broadcast", "-a", "'com.example.PING'"]
+        );
+    }
+
+    #[test]
+    fn target_prefers_component_then_uri_host() {
+        assert_eq!(deep_link().target(), "myapp://orders");
+        let explicit = Intent {
+            component: "com.example/.MainActivity".into(),
+            ..deep_link()
+        };
+        assert_eq!(explicit.target(), "com.example");
+    }
+
+    #[test]
+    fn parses_am_start_result() {
+        let output = "\
+Starting: Intent { act=android.intent.action.VIEW dat=myapp://orders/42 }
+Status: ok
+LaunchState: COLD
+Activity: com.example/.OrderActivity
+TotalTime: 412
+WaitTime: 415
+Complete
+";
+        let result = parse_am_output(output).unwrap();
+        assert_eq!(result.fields[0], ("Status".into(), "ok".into()));
+        assert_eq!(result.fields.len(), 5);
+        assert_eq!(result.lines.len(), 2);
+    }
+
+    #[test]
+    fn am_errors_are_reported() {
+        let output = "\
+Starting: Intent { act=android.intent.action.VIEW dat=nope:// }
+Error: Activity not started, unable to resolve Intent { act=android.intent.action.VIEW dat=nope:// }
+";
+        let err = parse_am_output(output).unwrap_err().to_string();
+        assert!(err.starts_with("Activity not started"));
+    }
+
+    #[test]
+    fn history_keeps_newest_first_without_duplicates() {
+        let mut history = IntentHistory::default();
+        let other = Intent {
+            data: "myapp://orders/7".into(),
+            ..deep_link()
+        };
+        history.record(&deep_link());
+        history.record(&other);
+        history.record(&deep_link());
+
+        let entries = history.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].2, &deep_link());
+
+        let text = toml::to_string(&history).unwrap();
+        assert_eq!(toml::from_str::<IntentHistory>(&text).unwrap(), history);
+
+        history.remove("myapp://orders", 0);
+        history.remove("myapp://orders", 0);
+        assert!(history.entries().is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use super::client::shell_quote;
+
+/// Locales offered by the picker. `en-XA` and `ar-XB` are the pseudo-locales:
+/// accented, longer strings and a mirrored right-to-left layout.
+pub const LOCALES: [(&str, &str); 22] = [
+    ("en-US", "English (United States)"),
+    ("en-GB", "English (United Kingdom)"),
+    ("de-DE", "German"),
+    ("fr-FR", "French"),
+    ("es-ES", "Spanish"),
+    ("es-419", "Spanish (Latin America)"),
+    ("it-IT", "Italian"),
+    ("pt-BR", "Portuguese (Brazil)"),
+    ("nl-NL", "Dutch"),
+    ("pl-PL", "Polish"),
+    ("ru-RU", "Russian"),
+    ("uk-UA", "Ukrainian"),
+    ("tr-TR", "Turkish"),
+    ("ar-EG", "Arabic"),
+    ("he-IL", "Hebrew"),
+    ("hi-IN", "Hindi"),
+    ("ja-JP", "Japanese"),
+    ("ko-KR", "Korean"),
+    ("zh-CN", "Chinese (Simplified)"),
+    ("zh-TW", "Chinese (Traditional)"),
+    ("en-XA", "Pseudo: accented, longer text"),
+    ("ar-XB", "Pseudo: right-to-left"),
+];
+
+/// Time zones offered by the picker, west to east.
+pub const TIME_ZONES: [&str; 27] = [
+    "Pacific/Honolulu",
+    "America/Anchorage",
+    "America/Los_Angeles",
+    "America/Denver",
+    "America/Chicago",
+    "America/New_York",
+    "America/Sao_Paulo",
+    "Atlantic/Azores",
+    "Etc/UTC",
+    "Europe/London",
+    "Europe/Paris",
+    "Europe/Berlin",
+    "Europe/Athens",
+    "Africa/Cairo",
+    "Africa/Johannesburg",
+    "Europe/Moscow",
+    "Asia/Dubai",
+    "Asia/Kolkata",
+    "Asia/Kathmandu",
+    "Asia/Bangkok",
+    "Asia/Shanghai",
+    "Asia/Singapore",
+    "Asia/Tokyo",
+    "Australia/Adelaide",
+    "Australia/Sydney",
+    "Pacific/Auckland",
+    "Pacific/Chatham",
+];
+
+/// Prints, one per line, what `parse_locale_state` reads.
+pub const LOCALE_STATE_COMMAND: &str = "getprop persist.sys.locale; getprop ro.product.locale; \
+     getprop persist.sys.timezone; getprop ro.build.version.sdk; id -u";
+
+/// Android 13 added per-app locales, which the shell may set without root.
+const APP_LOCALES_API: u32 = 33;
+/// Android 9 added `cmd alarm set-timezone`.
+const ALARM_TIMEZONE_API: u32 = 28;
+/// Android 5 replaced `persist.sys.language` and `persist.sys.country`.
+const LOCALE_PROP_API: u32 = 21;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LocaleState {
+    pub locale: String,
+    pub timezone: String,
+    pub api_level: u32,
+    /// Whether adbd runs as root, which the system locale needs.
+    pub root: bool,
+    /// Package of the resumed activity.
+    pub foreground: Option<String>,
+}
+
+/// What a new locale applies to; the user picks one explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocaleScope {
+    System,
+    App(String),
+}
+
+impl fmt::Display for LocaleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => write!(f, "the system"),
+            Self::App(package) => write!(f, "{package} only"),
+        }
+    }
+}
+
+impl LocaleState {
+    /// Why the locale of `scope` can't be changed on this device, if it can't:
+    /// the system locale needs root, per-app locales need Android 13.
+    pub fn scope_error(&self, scope: &LocaleScope) -> Option<&'static str> {
+        match scope {
+            LocaleScope::System if !self.root => {
+                Some("Changing the system locale needs root (adb root)")
+            }
+            LocaleScope::App(_) if self.api_level < APP_LOCALES_API => {
+                Some("Per-app locales need Android 13 or later")
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_locale_state(output: &str) -> LocaleState {
+    let mut lines = output.lines().map(str::trim);
+    let mut next = || lines.next().unwrap_or_default().to_string();
+    let (persisted, product, timezone, api_level, uid) = (next(), next(), next(), next(), next());
+    LocaleState {
+        locale: if persisted.is_empty() {
+            product
+        } else {
+            persisted
+        },
+        timezone,
+        api_level: api_level.parse().unwrap_or(0),
+        root: uid == "0",
+        foreground: None,
+    }
+}
+
+/// `en-US`, `es-419`, `zh-Hant-TW`: letters, digits and dashes, starting with
+/// a two or three letter language.
+pub fn is_locale_tag(tag: &str) -> bool {
+    let mut parts = tag.split('-');
+    let language = parts.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// An IANA zone id such as `America/Sao_Paulo` or `Etc/GMT+3`.
+pub fn is_zone_id(zone: &str) -> bool {
+    !zone.is_empty()
+        && !zone.starts_with('/')
+        && !zone.contains("..")
+        && zone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '+' | '-'))
+}
+
+/// Sets the system locale as root. The framework only reads it when it
+/// starts, so this restarts zygote.
+pub fn system_locale_command(tag: &str, api_level: u32) -> String {
+    let set = if api_level >= LOCALE_PROP_API {
+        format!("setprop persist.sys.locale {}", shell_quote(tag))
+    } else {
+        let (language, country) = tag.split_once('-').unwrap_or((tag, ""));
+        format!(
+            "setprop persist.sys.language {} && setprop persist.sys.country {}",
+            shell_quote(language),
+            shell_quote(country)
+        )
+    };
+    format!("{set} && setprop ctl.restart zygote")
+}
+
+pub fn app_locale_command(package: &str, tag: &str) -> String {
+    format!(
+        "cmd locale set-app-locales {} --locales {}",
+        shell_quote(package),
+        shell_quote(tag)
+    )
+}
+
+/// Turns off network time zone detection, which would undo the change, then
+/// sets the zone through the alarm service.
+pub fn timezone_command(zone: &str, api_level: u32) -> String {
+    let set = if api_level >= ALARM_TIMEZONE_API {
+        format!("cmd alarm set-timezone {}", shell_quote(zone))
+    } else {
+        // IAlarmManager.setTimeZone
+        format!("service call alarm 3 s16 {}", shell_quote(zone))
+    };
+    format!("settings put global auto_time_zone 0 && {set}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_falls_back_to_the_product_locale() {
+        let state = parse_locale_state("\nen-US\nEurope/Berlin\n34\n2000\n");
+        assert_eq!(state.locale, "en-US");
+        assert_eq!(state.timezone, "Europe/Berlin");
+        assert_eq!(state.api_level, 34);
+        assert!(!state.root);
+    }
+
+    #[test]
+    fn scopes_need_root_or_android_13() {
+        let app = LocaleScope::App("com.example.app".into());
+        let state = parse_locale_state("\nen-US\nEurope/Berlin\n34\n2000\n");
+        assert!(state.scope_error(&LocaleScope::System).is_some());
+        assert_eq!(state.scope_error(&app), None);
+
+        let old = LocaleState {
+            api_level: 30,
+            ..state
+        };
+        assert!(old.scope_error(&app).is_some());
+        let root = LocaleState { root: true, ..old };
+        assert_eq!(root.scope_error(&LocaleScope::System), None);
+    }
+
+    #[test]
+    fn commands_follow_the_api_level() {
+        assert_eq!(
+            system_locale_command("ar-XB", 34),
+            "setprop persist.sys.locale 'ar-XB' && setprop ctl.restart zygote"
+        );
+        assert_eq!(
+            system_locale_command("de-DE", 19),
+            "setprop persist.sys.language 'de' && setprop persist.sys.country 'DE' \
+             && setprop ctl.restart zygote"
+        );
+        assert_eq!(
+            timezone_command("Asia/Tokyo", 34),
+            "settings put global auto_time_zone 0 && cmd alarm set-timezone 'Asia/Tokyo'"
+        );
+        assert!(
+            timezone_command("Asia/Tokyo", 26).ends_with("service call alarm 3 s16 'Asia/Tokyo'")
+        );
+    }
+
+    #[test]
+    fn typed_values_are_validated() {
+        assert!(LOCALES.iter().all(|(tag, _)| is_locale_tag(tag)));
+        assert!(is_locale_tag("zh-Hant-TW"));
+        assert!(!is_locale_tag("english"));
+        assert!(!is_locale_tag("en-US; reboot"));
+        assert!(TIME_ZONES.iter().all(|zone| is_zone_id(zone)));
+        assert!(is_zone_id("Etc/GMT+3"));
+        assert!(!is_zone_id("../../etc"));
+        assert!(!is_zone_id("UTC; reboot"));
+    }
+}
@@ -1,4 +1,26 @@
+pub mod activities;
+pub mod battery;
+pub mod bugreport;
+pub mod capture;
 pub mod client;
+pub mod console;
+pub mod cpu;
+pub mod dev_options;
 pub mod device;
 pub mod device_info;
+pub mod display;
+pub mod dumpsys;
 pub mod emulator;
+pub mod forward;
+pub mod input;
+pub mod intent;
+pub mod locale;
+pub mod network;
+pub mod notifications;
+pub mod permissions;
+pub mod processes;
+pub mod properties;
+pub mod screenrecord;
+pub mod settings;
+pub mod shell;
+pub mod ui_hierarchy;
@@ -0,0 +1,279 @@
+use super::forward::{Direction, PortRule};
+
+/// One interface from `ip addr`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Interface {
+    pub name: String,
+    /// The operational state, e.g. `UP`, `DOWN` or `UNKNOWN`.
+    pub state: String,
+    pub mac: Option<String>,
+    /// Addresses in CIDR notation, IPv4 before IPv6 as `ip` prints them.
+    pub addresses: Vec<String>,
+}
+
+/// One line of `ip route show table all`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+    pub destination: String,
+    pub gateway: Option<String>,
+    pub device: Option<String>,
+    /// Android keeps a routing table per network, named after its interface.
+    pub table: Option<String>,
+}
+
+/// The default network according to `dumpsys connectivity`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActiveNetwork {
+    pub id: String,
+    /// The transport, e.g. `WIFI`, `CELLULAR` or `VPN`.
+    pub transport: Option<String>,
+    pub interface: Option<String>,
+    pub dns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkInfo {
+    pub interfaces: Vec<Interface>,
+    pub routes: Vec<Route>,
+    pub active: Option<ActiveNetwork>,
+    /// The global `http_proxy` setting; `None` when unset or cleared.
+    pub http_proxy: Option<String>,
+}
+
+impl NetworkInfo {
+    /// Whether the device's proxy points at the host's `port` through `adb reverse`.
+    pub fn proxies_to_host(&self, port: u16) -> bool {
+        self.http_proxy.as_deref() == Some(host_proxy(port).as_str())
+    }
+}
+
+/// The `http_proxy` value that sends device traffic to `port` on the host,
+/// once `host_proxy_rule` has reversed that port.
+pub fn host_proxy(port: u16) -> String {
+    format!("127.0.0.1:{port}")
+}
+
+pub fn host_proxy_rule(port: u16) -> PortRule {
+    PortRule {
+        direction: Direction::Reverse,
+        host: format!("tcp:{port}"),
+        device: format!("tcp:{port}"),
+    }
+}
+
+/// Parses `settings get global http_proxy`, which prints `null` when the key
+/// was never set and `:0` once it has been cleared.
+pub fn parse_http_proxy(output: &str) -> Option<String> {
+    let value = output.trim();
+    (!value.is_empty() && value != "null" && value != ":0").then(|| value.to_string())
+}
+
+/// Parses `ip addr`: a `<index>: <name>: <FLAGS> ... state <STATE>` header per
+/// interface followed by indented `link/` and `inet` lines.
+pub fn parse_ip_addr(output: &str) -> Vec<Interface> {
+    let mut interfaces: Vec<Interface> = Vec::new();
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let mut parts = line.splitn(3, ": ");
+            let (Some(index), Some(name)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if index.parse::<u32>().is_err() {
+                continue;
+            }
+            // Virtual pairs are named `eth0@if12`
+            let name = name.split('@').next().unwrap_or(name);
+            let rest = parts.next().unwrap_or_default();
+            let state = value_after(rest, "state").unwrap_or("UNKNOWN");
+            interfaces.push(Interface {
+                name: name.to_string(),
+                state: state.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(interface) = interfaces.last_mut() else {
+            continue;
+        };
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some(kind), Some(mac)) if kind.starts_with("link/") && kind != "link/none" => {
+                interface.mac = Some(mac.to_string());
+            }
+            (Some("inet" | "inet6"), Some(address)) => {
+                interface.addresses.push(address.to_string());
+            }
+            _ => {}
+        }
+    }
+    interfaces
+}
+
+/// Parses `ip route show table all`, leaving out the kernel's `local` and
+/// `broadcast` entries.
+pub fn parse_routes(output: &str) -> Vec<Route> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let destination = line.split_whitespace().next()?;
+            if matches!(
+                destination,
+                "local" | "broadcast" | "anycast" | "multicast" | "unreachable"
+            ) {
+                return None;
+            }
+            Some(Route {
+                destination: destination.to_string(),
+                gateway: value_after(line, "via").map(str::to_string),
+                device: value_after(line, "dev").map(str::to_string),
+                table: value_after(line, "table").map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Finds the default network in `dumpsys connectivity`. Its
+/// `NetworkAgentInfo` line is `NetworkAgentInfo{network{100} ...}` up to
+/// Android 12 and `NetworkAgentInfo [WIFI () - 100] ...` after.
+pub fn parse_active_network(output: &str) -> Option<ActiveNetwork> {
+    let id = output.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Active default network:")
+            .map(str::trim)
+    })?;
+    if id.is_empty() || id == "none" {
+        return None;
+    }
+    let mut network = ActiveNetwork {
+        id: id.to_string(),
+        ..Default::default()
+    };
+    let (legacy, current) = (format!("network{{{id}}}"), format!(" - {id}]"));
+    let Some(agent) = output.lines().find(|line| {
+        line.contains("NetworkAgentInfo") && (line.contains(&legacy) || line.contains(&current))
+    }) else {
+        return Some(network);
+    };
+
+    network.transport = value_after(agent, "Transports:")
+        .or_else(|| {
+            let rest = agent.split("NetworkAgentInfo [").nth(1)?;
+            rest.split([' ', '(']).next()
+        })
+        .map(str::to_string);
+    // Glued to the `lp{{` that opens the link properties
+    network.interface = agent
+        .split("InterfaceName: ")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(str::to_string);
+    if let Some(rest) = agent.split("DnsAddresses: [").nth(1) {
+        let list = rest.split(']').next().unwrap_or_default();
+        network.dns = list
+            .split(',')
+            .map(|a| a.trim().trim_start_matches('/'))
+            .filter(|a| !a.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    Some(network)
+}
+
+/// The whitespace-separated word following `key` in `line`.
+fn value_after<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let mut words = line.split_whitespace();
+    words.find(|w| *w == key)?;
+    words.next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ip_addr() {
+        let output = "\
+1: lo: <LOOPBACK,UP,LOWER_UP> mtu 65536 qdisc noqueue state UNKNOWN group default qlen 1000
+    link/loopback 00:00:00:00:00:00 brd 00:00:00:00:00:00
+    inet 127.0.0.1/8 scope host lo
+       valid_lft forever preferred_lft forever
+    inet6 ::1/128 scope host
+       valid_lft forever preferred_lft forever
+2: dummy0: <BROADCAST,NOARP> mtu 1500 qdisc noop state DOWN group default qlen 1000
+    link/ether 6e:2c:91:0a:bd:3f brd ff:ff:ff:ff:ff:ff
+12: wlan0@if11: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 qdisc noqueue state UP group default qlen 1000
+    link/ether 02:15:b2:00:00:00 brd ff:ff:ff:ff:ff:ff link-netns router
+    inet 10.0.2.16/24 brd 10.0.2.255 scope global wlan0
+       valid_lft forever preferred_lft forever
+    inet6 fe80::15:b2ff:fe00:0/64 scope link
+       valid_lft forever preferred_lft forever
+";
+        let interfaces = parse_ip_addr(output);
+        assert_eq!(interfaces.len(), 3);
+        assert_eq!(interfaces[0].addresses, ["127.0.0.1/8", "::1/128"]);
+        assert_eq!(interfaces[1].state, "DOWN");
+        assert!(interfaces[1].addresses.is_empty());
+        assert_eq!(interfaces[2].name, "wlan0");
+        assert_eq!(interfaces[2].mac.as_deref(), Some("02:15:b2:00:00:00"));
+        assert_eq!(interfaces[2].addresses[0], "10.0.2.16/24");
+    }
+
+    #[test]
+    fn parses_routes_without_local_entries() {
+        let output = "\
+default via 10.0.2.2 dev wlan0 table wlan0 proto static
+10.0.2.0/24 dev wlan0 table wlan0 proto static scope link
+local 10.0.2.16 dev wlan0 table local proto kernel scope host src 10.0.2.16
+broadcast 10.0.2.255 dev wlan0 table local proto kernel scope link src 10.0.2.16
+fe80::/64 dev wlan0 table wlan0 proto kernel metric 256 pref medium
+";
+        let routes = parse_routes(output);
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].destination, "default");
+        assert_eq!(routes[0].gateway.as_deref(), Some("10.0.2.2"));
+        assert_eq!(routes[1].gateway, None);
+        assert_eq!(routes[2].table.as_deref(), Some("wlan0"));
+    }
+
+    #[test]
+    fn finds_active_network_in_current_format() {
+        let output = "\
+NetworkProvider info:
+Active default network: 101
+Current Networks:
+  NetworkAgentInfo [CELLULAR (LTE) - 100] ...
+  NetworkAgentInfo [WIFI () - 101] network{101} lp{{InterfaceName: wlan0 LinkAddresses: [ 10.0.2.16/24 ] DnsAddresses: [ /10.0.2.3,/fec0::3 ] Domains: null MTU: 0}} nc{[ Transports: WIFI Capabilities: INTERNET&VALIDATED]}
+";
+        let active = parse_active_network(output).unwrap();
+        assert_eq!(active.id, "101");
+        assert_eq!(active.transport.as_deref(), Some("WIFI"));
+        assert_eq!(active.interface.as_deref(), Some("wlan0"));
+        assert_eq!(active.dns, ["10.0.2.3", "fec0::3"]);
+    }
+
+    #[test]
+    fn finds_active_network_in_legacy_format() {
+        let output = "\
+Active default network: 100
+  NetworkAgentInfo{ ni{[type: MOBILE[LTE]...]} network{100} lp{{InterfaceName: rmnet0 DnsAddresses: [ /8.8.8.8, ]}} nc{[ Transports: CELLULAR Capabilities: INTERNET]}}
+";
+        let active = parse_active_network(output).unwrap();
+        assert_eq!(active.transport.as_deref(), Some("CELLULAR"));
+        assert_eq!(active.interface.as_deref(), Some("rmnet0"));
+        assert_eq!(active.dns, ["8.8.8.8"]);
+
+        assert_eq!(parse_active_network("Active default network: none\n"), None);
+    }
+
+    #[test]
+    fn host_proxy_is_recognised() {
+        let mut info = NetworkInfo {
+            http_proxy: parse_http_proxy(":0\n"),
+            ..Default::default()
+        };
+        assert!(!info.proxies_to_host(8888));
+        info.http_proxy = parse_http_proxy("127.0.0.1:8888\n");
+        assert!(info.proxies_to_host(8888));
+        assert_eq!(host_proxy_rule(8888).to_string(), "tcp:8888 tcp:8888");
+    }
+}
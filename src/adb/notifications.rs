@@ -0,0 +1,242 @@
+use chrono::{DateTime, Local};
+
+/// Shell has no command to cancel another app's notification, so dismissing
+/// snoozes it for a day instead, which takes it out of the shade.
+pub const DISMISS_SNOOZE_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// `Notification.flags` bits, in the order they are listed.
+const FLAGS: [(u32, &str); 14] = [
+    (0x1, "SHOW_LIGHTS"),
+    (0x2, "ONGOING_EVENT"),
+    (0x4, "INSISTENT"),
+    (0x8, "ONLY_ALERT_ONCE"),
+    (0x10, "AUTO_CANCEL"),
+    (0x20, "NO_CLEAR"),
+    (0x40, "FOREGROUND_SERVICE"),
+    (0x80, "HIGH_PRIORITY"),
+    (0x100, "LOCAL_ONLY"),
+    (0x200, "GROUP_SUMMARY"),
+    (0x400, "AUTOGROUP_SUMMARY"),
+    (0x1000, "BUBBLE"),
+    (0x2000, "NO_DISMISS"),
+    (0x8000, "USER_INITIATED_JOB"),
+];
+
+const FLAG_ONGOING_EVENT: u32 = 0x2;
+const FLAG_NO_CLEAR: u32 = 0x20;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Notification {
+    /// `user|package|id|tag|uid`, what `cmd notification snooze` takes.
+    pub key: String,
+    pub package: String,
+    pub id: String,
+    pub tag: Option<String>,
+    pub channel: Option<String>,
+    /// `NotificationManager.IMPORTANCE_*`, 0-5.
+    pub importance: Option<u8>,
+    pub flags: u32,
+    /// `Notification.when`, in milliseconds since the epoch.
+    pub posted: Option<i64>,
+    /// `(name, value)` of each extra, values stripped of their type.
+    pub extras: Vec<(String, String)>,
+}
+
+impl Notification {
+    pub fn extra(&self, name: &str) -> Option<&str> {
+        self.extras
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.extra("android.title")
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.extra("android.text")
+            .or_else(|| self.extra("android.bigText"))
+    }
+
+    pub fn importance_label(&self) -> &'static str {
+        match self.importance {
+            Some(0) => "none",
+            Some(1) => "min",
+            Some(2) => "low",
+            Some(3) => "default",
+            Some(4) => "high",
+            Some(5) => "max",
+            _ => "?",
+        }
+    }
+
+    pub fn flag_names(&self) -> Vec<&'static str> {
+        FLAGS
+            .iter()
+            .filter(|(bit, _)| self.flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Ongoing and no-clear notifications stay until their app removes them.
+    pub fn is_clearable(&self) -> bool {
+        self.flags & (FLAG_ONGOING_EVENT | FLAG_NO_CLEAR) == 0
+    }
+
+    pub fn posted_at(&self, format: &str) -> Option<String> {
+        let posted = DateTime::from_timestamp_millis(self.posted?)?;
+        Some(posted.with_timezone(&Local).format(format).to_string())
+    }
+}
+
+/// The value of a `key=value` token in a dump line.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .find_map(|token| token.strip_prefix(key))
+        .filter(|v| !v.is_empty())
+}
+
+/// `String (Hello)` -> `Hello`. Redacted values (`String [length=5]`) and
+/// values without a type are kept whole.
+fn extra_value(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((_, rest)) if rest.starts_with('(') && rest.ends_with(')') => {
+            rest[1..rest.len() - 1].to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Parses the "Notification List" of `dumpsys notification --noredact`,
+/// skipping the enqueued, snoozed and archived sections.
+pub fn parse_notifications(output: &str) -> Vec<Notification> {
+    let mut notifications: Vec<Notification> = Vec::new();
+    // Indent of the "Notification List:" header while inside it
+    let mut list_indent = None;
+    let mut in_extras = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed == "Notification List:" {
+            list_indent = Some(indent(line));
+            continue;
+        }
+        let Some(header_indent) = list_indent else {
+            continue;
+        };
+        if indent(line) <= header_indent {
+            list_indent = None;
+            in_extras = false;
+            continue;
+        }
+
+        if trimmed.starts_with("NotificationRecord(") {
+            in_extras = false;
+            let flags = field(trimmed, "flags=0x")
+                .and_then(|f| u32::from_str_radix(f, 16).ok())
+                .unwrap_or(0);
+            notifications.push(Notification {
+                key: field(trimmed, "key=")
+                    .map(|k| k.trim_end_matches(':').to_string())
+                    .unwrap_or_default(),
+                package: field(trimmed, "pkg=").unwrap_or_default().to_string(),
+                id: field(trimmed, "id=").unwrap_or_default().to_string(),
+                tag: field(trimmed, "tag=")
+                    .filter(|t| *t != "null")
+                    .map(str::to_string),
+                channel: field(trimmed, "channel=")
+                    .filter(|c| *c != "null")
+                    .map(str::to_string),
+                importance: field(trimmed, "importance=").and_then(|i| i.parse().ok()),
+                flags,
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(notification) = notifications.last_mut() else {
+            continue;
+        };
+        if in_extras {
+            if trimmed == "}" {
+                in_extras = false;
+            } else if let Some((name, value)) = trimmed.split_once('=') {
+                notification
+                    .extras
+                    .push((name.to_string(), extra_value(value)));
+            }
+        } else if trimmed == "extras={" {
+            in_extras = true;
+        } else if let Some(when) = trimmed.strip_prefix("when=") {
+            notification.posted = when.parse().ok();
+        } else if let Some(created) = trimmed.strip_prefix("mCreationTimeMs=")
+            && notification.posted.is_none_or(|when| when <= 0)
+        {
+            notification.posted = created.parse().ok();
+        }
+    }
+    notifications
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "\
+Current Notification Manager state:
+  Notification List:
+    NotificationRecord(0x0a1b2c3d: pkg=com.example.app user=UserHandle{0} id=7 tag=null importance=4 key=0|com.example.app|7|null|10123: Notification(channel=orders shortcut=null contentView=null vibrate=null sound=null defaults=0x0 flags=0x10 color=0x00000000 vis=PRIVATE))
+      uid=10123 userId=0
+      when=1700000000000
+      extras={
+        android.title=String (Order shipped)
+        android.text=SpannableString (Your order #42 is on its way)
+        android.showWhen=Boolean (true)
+      }
+      mCreationTimeMs=1700000000123
+    NotificationRecord(0x0e0f: pkg=com.example.player user=UserHandle{0} id=1 tag=media importance=2 key=0|com.example.player|1|media|10124: Notification(channel=playback flags=0x62))
+      when=0
+      mCreationTimeMs=1700000005000
+  Enqueued Notification List:
+    NotificationRecord(0x99: pkg=com.example.pending user=UserHandle{0} id=3 tag=null importance=3 key=0|com.example.pending|3|null|10125: Notification(channel=x flags=0x0))
+";
+
+    #[test]
+    fn parses_the_active_list_only() {
+        let notifications = parse_notifications(DUMP);
+        assert_eq!(notifications.len(), 2);
+        let order = &notifications[0];
+        assert_eq!(order.package, "com.example.app");
+        assert_eq!(order.key, "0|com.example.app|7|null|10123");
+        assert_eq!(order.channel.as_deref(), Some("orders"));
+        assert_eq!(order.importance_label(), "high");
+        assert_eq!(order.title(), Some("Order shipped"));
+        assert_eq!(order.text(), Some("Your order #42 is on its way"));
+        assert_eq!(order.extra("android.showWhen"), Some("true"));
+        assert_eq!(order.posted, Some(1700000000000));
+        assert!(order.is_clearable());
+
+        let player = &notifications[1];
+        assert_eq!(player.tag.as_deref(), Some("media"));
+        assert_eq!(player.posted, Some(1700000005000));
+        assert_eq!(
+            player.flag_names(),
+            ["ONGOING_EVENT", "NO_CLEAR", "FOREGROUND_SERVICE"]
+        );
+        assert!(!player.is_clearable());
+    }
+
+    #[test]
+    fn redacted_extras_are_kept_whole() {
+        assert_eq!(extra_value("String [length=5]"), "String [length=5]");
+        assert_eq!(extra_value("Integer (3)"), "3");
+        assert_eq!(extra_value("null"), "null");
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionKind {
+    Requested,
+    Install,
+    Runtime,
+}
+
+impl fmt::Display for PermissionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Requested => write!(f, "requested"),
+            Self::Install => write!(f, "install"),
+            Self::Runtime => write!(f, "runtime"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permission {
+    pub name: String,
+    pub kind: PermissionKind,
+    /// `None` for permissions that are only requested and have no grant state.
+    pub granted: Option<bool>,
+    pub flags: Vec<String>,
+}
+
+impl Permission {
+    pub fn short_name(&self) -> &str {
+        self.name
+            .strip_prefix("android.permission.")
+            .unwrap_or(&self.name)
+    }
+
+    /// Runtime permissions fixed by policy or the system cannot be toggled with `pm`.
+    pub fn is_changeable(&self) -> bool {
+        self.kind == PermissionKind::Runtime
+            && !self
+                .flags
+                .iter()
+                .any(|f| f == "SYSTEM_FIXED" || f == "POLICY_FIXED")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppOpMode {
+    Allow,
+    Ignore,
+    Deny,
+    Default,
+    Foreground,
+}
+
+impl AppOpMode {
+    pub const ALL: [AppOpMode; 5] = [
+        Self::Allow,
+        Self::Ignore,
+        Self::Deny,
+        Self::Default,
+        Self::Foreground,
+    ];
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(Self::Allow),
+            "ignore" => Some(Self::Ignore),
+            "deny" | "error" => Some(Self::Deny),
+            "default" => Some(Self::Default),
+            "foreground" => Some(Self::Foreground),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Ignore => "ignore",
+            Self::Deny => "deny",
+            Self::Default => "default",
+            Self::Foreground => "foreground",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for AppOpMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppOp {
+    pub name: String,
+    pub mode: AppOpMode,
+    /// Set when the mode comes from the `Uid mode:` line rather than the package.
+    pub uid_mode: bool,
+    /// Trailing `time=...; duration=...` details, if any.
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PackagePermissions {
+    pub serial: String,
+    pub package: String,
+    pub permissions: Vec<Permission>,
+    pub app_ops: Vec<AppOp>,
+}
+
+pub fn parse_package_list(output: &str) -> Vec<String> {
+    let mut packages: Vec<String> = output
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("package:"))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    packages.sort();
+    packages
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Requested,
+    Install,
+    Runtime,
+}
+
+/// Parses the permission sections of `dumpsys package <pkg>`.
+///
+/// Only the first `Package [...]` block is read; later blocks describe hidden
+/// system copies of the same package.
+pub fn parse_package_permissions(output: &str) -> Vec<Permission> {
+    let mut permissions: Vec<Permission> = Vec::new();
+    let mut section = Section::None;
+    let mut section_indent = 0;
+    let mut seen_package = false;
+
+    for line in output.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed.starts_with("Package [") {
+            if seen_package {
+                break;
+            }
+            seen_package = true;
+            section = Section::None;
+            continue;
+        }
+        if trimmed.starts_with("Hidden system packages:") {
+            break;
+        }
+
+        let header = match trimmed {
+            "requested permissions:" => Some(Section::Requested),
+            "install permissions:" => Some(Section::Install),
+            "runtime permissions:" => Some(Section::Runtime),
+            _ => None,
+        };
+        if let Some(header) = header {
+            section = header;
+            section_indent = indent;
+            continue;
+        }
+
+        if section == Section::None {
+            continue;
+        }
+        if indent <= section_indent {
+            section = Section::None;
+            continue;
+        }
+
+        match section {
+            Section::Requested => {
+                // "android.permission.X" or "android.permission.X: restricted=true"
+                let name = trimmed.split([':', ',']).next().unwrap_or(trimmed).trim();
+                if !permissions.iter().any(|p| p.name == name) {
+                    permissions.push(Permission {
+                        name: name.to_string(),
+                        kind: PermissionKind::Requested,
+                        granted: None,
+                        flags: Vec::new(),
+                    });
+                }
+            }
+            Section::Install | Section::Runtime => {
+                let Some(parsed) = parse_permission_state(trimmed) else {
+                    continue;
+                };
+                let kind = if section == Section::Install {
+                    PermissionKind::Install
+                } else {
+                    PermissionKind::Runtime
+                };
+                let entry = Permission { kind, ..parsed };
+                match permissions.iter_mut().find(|p| p.name == entry.name) {
+                    Some(existing) => *existing = entry,
+                    None => permissions.push(entry),
+                }
+            }
+            Section::None => {}
+        }
+    }
+
+    permissions
+}
+
+fn parse_permission_state(line: &str) -> Option<Permission> {
+    // "android.permission.CAMERA: granted=false, flags=[ USER_SET|USER_FIXED ]"
+    let (name, rest) = line.split_once(": ")?;
+    let granted = rest
+        .split(',')
+        .find_map(|part| part.trim().strip_prefix("granted="))
+        .map(|v| v.trim() == "true");
+    let flags = rest
+        .split_once("flags=[")
+        .and_then(|(_, f)| f.split_once(']'))
+        .map(|(f, _)| {
+            f.split('|')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Permission {
+        name: name.trim().to_string(),
+        kind: PermissionKind::Requested,
+        granted,
+        flags,
+    })
+}
+
+/// Parses `appops get <pkg>`.
+///
+/// Package-level modes win over `Uid mode:` entries for the same op.
+pub fn parse_appops(output: &str) -> Vec<AppOp> {
+    let mut ops: Vec<AppOp> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        let (uid_mode, rest) = match trimmed.strip_prefix("Uid mode:") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, trimmed),
+        };
+
+        let Some((name, value)) = rest.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+            continue;
+        }
+
+        let mut parts = value.split(';');
+        let Some(mode) = parts.next().and_then(|m| AppOpMode::from_str(m.trim())) else {
+            continue;
+        };
+        let detail = parts
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let op = AppOp {
+            name: name.to_string(),
+            mode,
+            uid_mode,
+            detail,
+        };
+        match ops.iter_mut().find(|o| o.name == op.name) {
+            Some(existing) if existing.uid_mode && !uid_mode => *existing = op,
+            Some(_) => {}
+            None => ops.push(op),
+        }
+    }
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMPSYS_PACKAGE: &str = "\
+Packages:
+  Package [com.example.app] (2f1a3b4):
+    userId=10123
+    requested permissions:
+      android.permission.INTERNET
+      android.permission.CAMERA
+      android.permission.READ_EXTERNAL_STORAGE: restricted=true
+      android.permission.POST_NOTIFICATIONS
+    install permissions:
+      android.permission.INTERNET: granted=true
+    User 0: ceDataInode=12345 installed=true hidden=false
+      gids=[3003]
+      runtime permissions:
+        android.permission.CAMERA: granted=false, flags=[ USER_SET|USER_FIXED ]
+        android.permission.READ_EXTERNAL_STORAGE: granted=true, flags=[ RESTRICTION_INSTALLER_EXEMPT ]
+        android.permission.POST_NOTIFICATIONS: granted=true, flags=[ SYSTEM_FIXED ]
+      disabledComponents:
+        com.example.app.Foo
+Hidden system packages:
+  Package [com.example.app] (99):
+    install permissions:
+      android.permission.WAKE_LOCK: granted=true
+";
+
+    #[test]
+    fn parse_package_list_sorts_and_strips_prefix() {
+        let output = "package:com.b\npackage:com.a\n\nnoise\n";
+        assert_eq!(parse_package_list(output), vec!["com.a", "com.b"]);
+    }
+
+    #[test]
+    fn parse_permissions_merges_sections() {
+        let perms = parse_package_permissions(DUMPSYS_PACKAGE);
+        assert_eq!(perms.len(), 4);
+
+        let internet = &perms[0];
+        assert_eq!(internet.name, "android.permission.INTERNET");
+        assert_eq!(internet.kind, PermissionKind::Install);
+        assert_eq!(internet.granted, Some(true));
+
+        let camera = &perms[1];
+        assert_eq!(camera.kind, PermissionKind::Runtime);
+        assert_eq!(camera.granted, Some(false));
+        assert_eq!(camera.flags, vec!["USER_SET", "USER_FIXED"]);
+        assert!(camera.is_changeable());
+
+        let storage = &perms[2];
+        assert_eq!(storage.name, "android.permission.READ_EXTERNAL_STORAGE");
+        assert_eq!(storage.granted, Some(true));
+
+        let notifications = &perms[3];
+        assert!(!notifications.is_changeable());
+    }
+
+    #[test]
+    fn parse_permissions_ignores_hidden_system_packages() {
+        let perms = parse_package_permissions(DUMPSYS_PACKAGE);
+        assert!(!perms.iter().any(|p| p.name.ends_with("WAKE_LOCK")));
+    }
+
+    #[test]
+    fn parse_permissions_keeps_requested_only_entries() {
+        let output = "\
+  Package [com.x] (1):
+    requested permissions:
+      android.permission.VIBRATE
+";
+        let perms = parse_package_permissions(output);
+        assert_eq!(perms.len(), 1);
+        assert_eq!(perms[0].kind, PermissionKind::Requested);
+        assert_eq!(perms[0].granted, None);
+        assert_eq!(perms[0].short_name(), "VIBRATE");
+    }
+
+    #[test]
+    fn parse_appops_prefers_package_mode() {
+        let output = "\
+Uid mode: COARSE_LOCATION: foreground
+Uid mode: CAMERA: ignore
+CAMERA: allow; time=+1d2h ago; duration=+1s
+COARSE_LOCATION: ignore; rejectTime=+5m ago
+WAKE_LOCK: allow
+No operations.
+";
+        let ops = parse_appops(output);
+        assert_eq!(ops.len(), 3);
+
+        assert_eq!(ops[0].name, "COARSE_LOCATION");
+        assert_eq!(ops[0].mode, AppOpMode::Ignore);
+        assert!(!ops[0].uid_mode);
+        assert_eq!(ops[0].detail, "rejectTime=+5m ago");
+
+        assert_eq!(ops[1].name, "CAMERA");
+        assert_eq!(ops[1].mode, AppOpMode::Allow);
+        assert_eq!(ops[1].detail, "time=+1d2h ago; duration=+1s");
+
+        assert_eq!(ops[2].mode, AppOpMode::Allow);
+    }
+
+    #[test]
+    fn appop_mode_cycles() {
+        assert_eq!(AppOpMode::Allow.next(), AppOpMode::Ignore);
+        assert_eq!(AppOpMode::Foreground.next(), AppOpMode::Allow);
+        assert_eq!(AppOpMode::from_str("error"), Some(AppOpMode::Deny));
+        assert_eq!(AppOpMode::from_str("bogus"), None);
+    }
+}
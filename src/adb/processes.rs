@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub user: String,
+    pub rss_kb: u64,
+    pub vsz_kb: u64,
+    pub state: String,
+    pub name: String,
+    /// Filled in from `top`; zero when the process was not in its snapshot.
+    pub cpu: f32,
+}
+
+impl ProcessInfo {
+    /// App processes are named after their package, with an optional `:suffix`
+    /// for secondary processes.
+    pub fn package(&self) -> Option<&str> {
+        let base = self.name.split(':').next()?;
+        let looks_like_package = base.contains('.')
+            && !base.starts_with('/')
+            && base
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_');
+        looks_like_package.then_some(base)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Term,
+    Kill,
+    Int,
+    Hup,
+    Stop,
+    Cont,
+}
+
+impl Signal {
+    pub const ALL: [Signal; 6] = [
+        Self::Term,
+        Self::Kill,
+        Self::Int,
+        Self::Hup,
+        Self::Stop,
+        Self::Cont,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Term => "TERM",
+            Self::Kill => "KILL",
+            Self::Int => "INT",
+            Self::Hup => "HUP",
+            Self::Stop => "STOP",
+            Self::Cont => "CONT",
+        }
+    }
+}
+
+/// Parses `ps -A -o PID,PPID,USER,RSS,VSZ,S,NAME`.
+pub fn parse_ps(output: &str) -> Vec<ProcessInfo> {
+    let mut processes = Vec::new();
+
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            continue;
+        }
+        let Ok(pid) = parts[0].parse() else {
+            // Header line
+            continue;
+        };
+        processes.push(ProcessInfo {
+            pid,
+            ppid: parts[1].parse().unwrap_or(0),
+            user: parts[2].to_string(),
+            rss_kb: parts[3].parse().unwrap_or(0),
+            vsz_kb: parts[4].parse().unwrap_or(0),
+            state: parts[5].to_string(),
+            name: parts[6..].join(" "),
+            cpu: 0.0,
+        });
+    }
+
+    processes
+}
+
+/// Parses the per-process `%CPU` column out of `top -b -n 1`.
+pub fn parse_top_cpu(output: &str) -> HashMap<u32, f32> {
+    let mut cpu = HashMap::new();
+    let mut columns: Option<(usize, usize)> = None;
+
+    for line in output.lines() {
+        // toybox prints "S[%CPU]" with the state and CPU columns glued together
+        let normalized = line.replace(['[', ']'], " ");
+        let parts: Vec<&str> = normalized.split_whitespace().collect();
+
+        let Some((pid_col, cpu_col)) = columns else {
+            let pid_col = parts.iter().position(|p| *p == "PID");
+            let cpu_col = parts.iter().position(|p| *p == "%CPU");
+            if let (Some(pid_col), Some(cpu_col)) = (pid_col, cpu_col) {
+                columns = Some((pid_col, cpu_col));
+            }
+            continue;
+        };
+
+        let (Some(pid), Some(value)) = (parts.get(pid_col), parts.get(cpu_col)) else {
+            continue;
+        };
+        if let (Ok(pid), Ok(value)) = (pid.parse(), value.trim_end_matches('%').parse()) {
+            cpu.insert(pid, value);
+        }
+    }
+
+    cpu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ps() {
+        let output = "\
+  PID  PPID USER            RSS    VSZ S NAME
+    1     0 root          10220 1234 S init
+  812     1 system        98000 4567 S system_server
+ 4321   700 u0_a123      150000 9999 R com.example.app:remote
+";
+        let procs = parse_ps(output);
+        assert_eq!(procs.len(), 3);
+        assert_eq!(procs[0].pid, 1);
+        assert_eq!(procs[0].name, "init");
+        assert_eq!(procs[2].ppid, 700);
+        assert_eq!(procs[2].user, "u0_a123");
+        assert_eq!(procs[2].rss_kb, 150000);
+        assert_eq!(procs[2].state, "R");
+        assert_eq!(procs[2].package(), Some("com.example.app"));
+        assert_eq!(procs[1].package(), None);
+    }
+
+    #[test]
+    fn test_parse_top_cpu() {
+        let output = "\
+Tasks: 720 total,   1 running, 719 sleeping,   0 stopped,   0 zombie
+  Mem:  5791296K total,  5615168K used,   176128K free,    62260K buffers
+800%cpu  12%user   0%nice  12%sys 776%idle   0%iow   0%irq   0%sirq   0%host
+  PID USER         PR  NI VIRT  RES  SHR S[%CPU] %MEM     TIME+ ARGS
+ 4970 shell        20   0  10G 4.0M 3.0M R 10.3   0.0   0:00.03 top -b -n 1
+  812 system       18  -2  14G 320M 200M S  3.4   5.5  12:01.10 system_server
+";
+        let cpu = parse_top_cpu(output);
+        assert_eq!(cpu.len(), 2);
+        assert!((cpu[&4970] - 10.3).abs() < f32::EPSILON);
+        assert!((cpu[&812] - 3.4).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn package_ignores_native_paths() {
+        let proc = ProcessInfo {
+            pid: 1,
+            ppid: 0,
+            user: "root".into(),
+            rss_kb: 0,
+            vsz_kb: 0,
+            state: "S".into(),
+            name: "/system/bin/surfaceflinger".into(),
+            cpu: 0.0,
+        };
+        assert_eq!(proc.package(), None);
+    }
+}
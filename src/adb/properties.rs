@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+/// Prefix groups the properties view can narrow to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropGroup {
+    All,
+    ReadOnly,
+    Persist,
+    Sys,
+    Other,
+}
+
+impl PropGroup {
+    pub const ALL: [PropGroup; 5] = [
+        Self::All,
+        Self::ReadOnly,
+        Self::Persist,
+        Self::Sys,
+        Self::Other,
+    ];
+
+    pub fn of(key: &str) -> Self {
+        if key.starts_with("ro.") {
+            Self::ReadOnly
+        } else if key.starts_with("persist.") {
+            Self::Persist
+        } else if key.starts_with("sys.") {
+            Self::Sys
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn contains(self, key: &str) -> bool {
+        self == Self::All || Self::of(key) == self
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::ReadOnly => "ro.",
+            Self::Persist => "persist.",
+            Self::Sys => "sys.",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|g| *g == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// `ro.*` properties can only be set once, at boot; `ctl.*` are commands to init.
+pub fn is_writable(key: &str) -> bool {
+    !key.starts_with("ro.") && !key.starts_with("ctl.")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropDiff {
+    pub key: String,
+    /// `None` when the property is missing on that device.
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+/// Keys whose values differ between the two property sets, in key order.
+pub fn diff_props(
+    left: &BTreeMap<String, String>,
+    right: &BTreeMap<String, String>,
+) -> Vec<PropDiff> {
+    let mut keys: Vec<&String> = left.keys().chain(right.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let (l, r) = (left.get(key), right.get(key));
+            (l != r).then(|| PropDiff {
+                key: key.clone(),
+                left: l.cloned(),
+                right: r.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn groups_by_prefix() {
+        assert_eq!(PropGroup::of("ro.build.type"), PropGroup::ReadOnly);
+        assert_eq!(PropGroup::of("persist.sys.locale"), PropGroup::Persist);
+        assert_eq!(PropGroup::of("sys.boot_completed"), PropGroup::Sys);
+        assert_eq!(PropGroup::of("dalvik.vm.heapsize"), PropGroup::Other);
+        assert!(PropGroup::All.contains("anything"));
+        assert_eq!(PropGroup::Other.next(), PropGroup::All);
+    }
+
+    #[test]
+    fn read_only_keys_are_not_writable() {
+        assert!(!is_writable("ro.debuggable"));
+        assert!(!is_writable("ctl.start"));
+        assert!(is_writable("debug.hwui.profile"));
+    }
+
+    #[test]
+    fn diff_reports_changed_and_missing_keys() {
+        let left = props(&[("a", "1"), ("b", "2"), ("c", "3")]);
+        let right = props(&[("b", "2"), ("c", "4"), ("d", "5")]);
+        let diff = diff_props(&left, &right);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff[0].key, "a");
+        assert_eq!(diff[0].right, None);
+        assert_eq!(diff[1].key, "c");
+        assert_eq!(diff[1].left.as_deref(), Some("3"));
+        assert_eq!(diff[1].right.as_deref(), Some("4"));
+        assert_eq!(diff[2].key, "d");
+        assert_eq!(diff[2].left, None);
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::client::shell_quote;
+
+/// `screenrecord` refuses to run longer than this in one go.
+pub const MAX_SEGMENT_SECS: u64 = 180;
+/// How long `screenrecord` gets to finish the file after SIGINT before it is
+/// killed.
+pub const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The `[screenrecord]` config table.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RecordOptions {
+    /// Bits per second, e.g. `8000000`.
+    #[serde(default)]
+    pub bit_rate: Option<u32>,
+    /// `WIDTHxHEIGHT`, e.g. `1280x720`; the display size when unset.
+    #[serde(default)]
+    pub size: Option<String>,
+    /// Seconds to record in total, chaining segments as needed; records until
+    /// stopped when unset.
+    #[serde(default)]
+    pub time_limit: Option<u64>,
+}
+
+impl RecordOptions {
+    /// How long the next segment may run once `recorded` seconds are on disk,
+    /// or `None` when the total time limit is used up.
+    pub fn next_segment(&self, recorded: u64) -> Option<u64> {
+        match self.time_limit {
+            Some(limit) if recorded >= limit => None,
+            Some(limit) => Some((limit - recorded).min(MAX_SEGMENT_SECS)),
+            None => Some(MAX_SEGMENT_SECS),
+        }
+    }
+
+    /// Arguments after `adb shell` for one segment written to `remote_path`.
+    pub fn args(&self, segment_secs: u64, remote_path: &str) -> Vec<String> {
+        let mut args = vec!["screenrecord".to_string()];
+        if let Some(bit_rate) = self.bit_rate {
+            args.extend(["--bit-rate".into(), bit_rate.to_string()]);
+        }
+        if let Some(ref size) = self.size {
+            args.extend(["--size".into(), size.clone()]);
+        }
+        args.extend([
+            "--time-limit".into(),
+            segment_secs.to_string(),
+            remote_path.into(),
+        ]);
+        args
+    }
+}
+
+/// Where segment `index` of a recording is written on the device.
+pub fn remote_segment_path(stamp: &str, index: usize) -> String {
+    format!("/sdcard/lazyadb-{stamp}-{index}.mp4")
+}
+
+/// Arguments after `adb shell` that SIGINT the `screenrecord` writing
+/// `remote_path` and no other. The bracket keeps the pattern from matching the
+/// shell that runs `pkill`, whose command line holds the pattern itself.
+pub fn interrupt_args(remote_path: &str) -> [String; 4] {
+    [
+        "pkill".into(),
+        "-INT".into(),
+        "-f".into(),
+        shell_quote(&format!("[s]creenrecord .*{remote_path}")),
+    ]
+}
+
+/// Local name for segment `index` of `count`: `file_name` itself for a single
+/// segment, otherwise numbered from 1 before the extension.
+pub fn segment_file_name(file_name: &str, index: usize, count: usize) -> String {
+    if count <= 1 {
+        return file_name.to_string();
+    }
+    let path = Path::new(file_name);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(file_name);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem}_{}.{ext}", index + 1),
+        None => format!("{stem}_{}", index + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_are_chained_up_to_the_time_limit() {
+        let unlimited = RecordOptions::default();
+        assert_eq!(unlimited.next_segment(0), Some(180));
+        assert_eq!(unlimited.next_segment(3600), Some(180));
+
+        let limited = RecordOptions {
+            time_limit: Some(400),
+            ..Default::default()
+        };
+        assert_eq!(limited.next_segment(0), Some(180));
+        assert_eq!(limited.next_segment(360), Some(40));
+        assert_eq!(limited.next_segment(400), None);
+    }
+
+    #[test]
+    fn args_include_only_configured_options() {
+        let options = RecordOptions {
+            bit_rate: Some(4_000_000),
+            size: Some("720x1280".into()),
+            time_limit: None,
+        };
+        assert_eq!(
+            options.args(180, "/sdcard/a.mp4"),
+            [
+                "screenrecord",
+                "--bit-rate",
+                "4000000",
+                "--size",
+                "720x1280",
+                "--time-limit",
+                "180",
+                "/sdcard/a.mp4"
+            ]
+        );
+        assert_eq!(
+            RecordOptions::default().args(60, "/sdcard/b.mp4"),
+            ["screenrecord", "--time-limit", "60", "/sdcard/b.mp4"]
+        );
+    }
+
+    #[test]
+    fn interrupt_targets_one_recording() {
+        assert_eq!(
+            interrupt_args("/sdcard/lazyadb-20240101-120000-0.mp4"),
+            [
+                "pkill",
+                "-INT",
+                "-f",
+                "'[s]creenrecord .*/sdcard/lazyadb-20240101-120000-0.mp4'"
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_names_are_numbered_when_chained() {
+        assert_eq!(segment_file_name("Pixel_7.mp4", 0, 1), "Pixel_7.mp4");
+        assert_eq!(segment_file_name("Pixel_7.mp4", 0, 3), "Pixel_7_1.mp4");
+        assert_eq!(segment_file_name("Pixel_7.mp4", 2, 3), "Pixel_7_3.mp4");
+    }
+}
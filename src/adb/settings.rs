@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsNamespace {
+    System,
+    Secure,
+    Global,
+}
+
+impl SettingsNamespace {
+    pub const ALL: [SettingsNamespace; 3] = [Self::System, Self::Secure, Self::Global];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Secure => "secure",
+            Self::Global => "global",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|n| *n == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for SettingsNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Parses `settings list <namespace>`; values may themselves contain `=`.
+pub fn parse_settings_list(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter(|(key, _)| !key.is_empty() && !key.contains(char::is_whitespace))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// A setting as it was before we first touched it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingChange {
+    pub namespace: SettingsNamespace,
+    pub key: String,
+    /// `None` when the key did not exist, so reverting deletes it.
+    pub original: Option<String>,
+}
+
+/// Original values of every setting changed on one device.
+#[derive(Debug, Clone, Default)]
+pub struct RevertLog {
+    changes: Vec<SettingChange>,
+}
+
+impl RevertLog {
+    /// Remembers `original` unless the key was already changed earlier, in
+    /// which case the earlier original is the one to go back to.
+    pub fn record(&mut self, namespace: SettingsNamespace, key: &str, original: Option<&str>) {
+        if self
+            .changes
+            .iter()
+            .any(|c| c.namespace == namespace && c.key == key)
+        {
+            return;
+        }
+        self.changes.push(SettingChange {
+            namespace,
+            key: key.to_string(),
+            original: original.map(str::to_string),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The changes to undo, newest first.
+    pub fn changes(&self) -> Vec<SettingChange> {
+        self.changes.iter().rev().cloned().collect()
+    }
+
+    /// Drops changes that have been undone.
+    pub fn forget(&mut self, reverted: &[SettingChange]) {
+        self.changes.retain(|c| !reverted.contains(c));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings_list() {
+        let output = "\
+screen_brightness=102
+screen_off_timeout=60000
+enabled_accessibility_services=
+sysui_qs_tiles=wifi,bt,custom(com.example/.Tile=1)
+";
+        let settings = parse_settings_list(output);
+        assert_eq!(settings.len(), 4);
+        assert_eq!(settings["screen_off_timeout"], "60000");
+        assert_eq!(settings["enabled_accessibility_services"], "");
+        assert_eq!(
+            settings["sysui_qs_tiles"],
+            "wifi,bt,custom(com.example/.Tile=1)"
+        );
+    }
+
+    #[test]
+    fn revert_log_keeps_first_original() {
+        let mut log = RevertLog::default();
+        log.record(
+            SettingsNamespace::System,
+            "screen_off_timeout",
+            Some("30000"),
+        );
+        log.record(
+            SettingsNamespace::System,
+            "screen_off_timeout",
+            Some("60000"),
+        );
+        log.record(SettingsNamespace::Global, "new_key", None);
+        assert_eq!(log.len(), 2);
+
+        let changes = log.changes();
+        assert_eq!(changes[0].key, "new_key");
+        assert_eq!(changes[0].original, None);
+        assert_eq!(changes[1].original.as_deref(), Some("30000"));
+
+        log.forget(&changes[..1]);
+        assert_eq!(log.changes(), &changes[1..]);
+    }
+}
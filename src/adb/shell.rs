@@ -0,0 +1,84 @@
+use std::io::{Read, Write};
+
+use color_eyre::{Result, eyre::eyre};
+use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
+
+/// An `adb shell` running on a local PTY, so adb allocates a remote one and
+/// forwards window size changes.
+pub struct ShellSession {
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl ShellSession {
+    /// Spawns the shell and returns it with a reader for its output, which the
+    /// caller drains on a thread of its own.
+    pub fn spawn(
+        adb_path: &str,
+        serial: &str,
+        rows: u16,
+        cols: u16,
+    ) -> Result<(Self, Box<dyn Read + Send>)> {
+        let pair = native_pty_system()
+            .openpty(pty_size(rows, cols))
+            .map_err(|e| eyre!("Failed to open PTY: {}", e))?;
+
+        let mut cmd = CommandBuilder::new(adb_path);
+        cmd.args(["-s", serial, "shell"]);
+        cmd.env("TERM", "xterm-256color");
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| eyre!("Failed to run 'adb shell': {}", e))?;
+        // Only the child keeps the slave end open, so EOF arrives when it exits
+        drop(pair.slave);
+
+        let reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| eyre!("Failed to read from PTY: {}", e))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| eyre!("Failed to write to PTY: {}", e))?;
+
+        Ok((
+            Self {
+                master: pair.master,
+                writer,
+                child,
+            },
+            reader,
+        ))
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> Result<()> {
+        self.writer.write_all(data)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn resize(&self, rows: u16, cols: u16) -> Result<()> {
+        self.master
+            .resize(pty_size(rows, cols))
+            .map_err(|e| eyre!("Failed to resize PTY: {}", e))
+    }
+}
+
+impl Drop for ShellSession {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+        }
+    }
+}
+
+fn pty_size(rows: u16, cols: u16) -> PtySize {
+    PtySize {
+        rows,
+        cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
@@ -0,0 +1,227 @@
+use color_eyre::{Result, eyre::eyre};
+
+/// Where the hierarchy is dumped when the device can't write it to the tty.
+pub const UI_DUMP_PATH: &str = "/data/local/tmp/lazyadb_window_dump.xml";
+
+/// `[left,top][right,bottom]` in screen pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bounds {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Bounds {
+    pub fn center(&self) -> (i32, i32) {
+        ((self.left + self.right) / 2, (self.top + self.bottom) / 2)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+}
+
+/// One `<node>` of a `uiautomator dump`; empty attributes are `None`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UiNode {
+    /// Nesting level, 0 for the top of each window.
+    pub depth: usize,
+    pub class: String,
+    pub resource_id: Option<String>,
+    pub text: Option<String>,
+    pub content_desc: Option<String>,
+    pub package: Option<String>,
+    pub bounds: Option<Bounds>,
+    pub clickable: bool,
+}
+
+impl UiNode {
+    /// The class without its package, `Button` for `android.widget.Button`.
+    pub fn short_class(&self) -> &str {
+        self.class.rsplit('.').next().unwrap_or(&self.class)
+    }
+
+    /// The id without its package, `login` for `com.example:id/login`.
+    pub fn short_id(&self) -> Option<&str> {
+        let id = self.resource_id.as_deref()?;
+        Some(id.split_once(":id/").map_or(id, |(_, name)| name))
+    }
+
+    /// Whether `needle`, already lowercased, is in any of the shown attributes.
+    pub fn matches(&self, needle: &str) -> bool {
+        [
+            Some(self.class.as_str()),
+            self.resource_id.as_deref(),
+            self.text.as_deref(),
+            self.content_desc.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| value.to_lowercase().contains(needle))
+    }
+}
+
+/// `[0,63][1080,2337]` -> bounds.
+pub fn parse_bounds(value: &str) -> Option<Bounds> {
+    let numbers: Vec<i32> = value
+        .split(['[', ']', ','])
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()?;
+    let [left, top, right, bottom] = numbers.as_slice() else {
+        return None;
+    };
+    Some(Bounds {
+        left: *left,
+        top: *top,
+        right: *right,
+        bottom: *bottom,
+    })
+}
+
+/// Parses the XML of `uiautomator dump` into its nodes, depth first. Text
+/// around the document, like the "UI hierchary dumped to" line that follows
+/// it on the tty, is ignored.
+pub fn parse_hierarchy(output: &str) -> Result<Vec<UiNode>> {
+    let start = output.find("<?xml").or_else(|| output.find("<hierarchy"));
+    let end = output
+        .rfind("</hierarchy>")
+        .map(|i| i + "</hierarchy>".len());
+    let (Some(start), Some(end)) = (start, end) else {
+        let first = output.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(eyre!("uiautomator dump failed: {}", first.trim()));
+    };
+    let doc = roxmltree::Document::parse(&output[start..end])
+        .map_err(|e| eyre!("Invalid hierarchy XML: {}", e))?;
+
+    let mut nodes = Vec::new();
+    for window in doc
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("node"))
+    {
+        collect(window, 0, &mut nodes);
+    }
+    Ok(nodes)
+}
+
+fn collect(node: roxmltree::Node, depth: usize, nodes: &mut Vec<UiNode>) {
+    let attr = |name: &str| {
+        node.attribute(name)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    nodes.push(UiNode {
+        depth,
+        class: attr("class").unwrap_or_default(),
+        resource_id: attr("resource-id"),
+        text: attr("text"),
+        content_desc: attr("content-desc"),
+        package: attr("package"),
+        bounds: node.attribute("bounds").and_then(parse_bounds),
+        clickable: node.attribute("clickable") == Some("true"),
+    });
+    for child in node.children().filter(|n| n.has_tag_name("node")) {
+        collect(child, depth + 1, nodes);
+    }
+}
+
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A `UiSelector` that finds `nodes[index]`: the first of its resource id,
+/// text and content description that no other node shares, or else its id
+/// or class narrowed down with `instance()`.
+pub fn selector(nodes: &[UiNode], index: usize) -> String {
+    let node = &nodes[index];
+    let unique = |value: &Option<String>, get: fn(&UiNode) -> &Option<String>| {
+        value.is_some() && nodes.iter().filter(|n| get(n) == value).count() == 1
+    };
+    if unique(&node.resource_id, |n| &n.resource_id) {
+        let id = node.resource_id.as_deref().unwrap_or_default();
+        return format!("new UiSelector().resourceId({})", quoted(id));
+    }
+    if unique(&node.text, |n| &n.text) {
+        let text = node.text.as_deref().unwrap_or_default();
+        return format!("new UiSelector().text({})", quoted(text));
+    }
+    if unique(&node.content_desc, |n| &n.content_desc) {
+        let desc = node.content_desc.as_deref().unwrap_or_default();
+        return format!("new UiSelector().description({})", quoted(desc));
+    }
+    let (base, instance) = match node.resource_id {
+        Some(ref id) => (
+            format!("resourceId({})", quoted(id)),
+            nodes[..index]
+                .iter()
+                .filter(|n| n.resource_id == node.resource_id)
+                .count(),
+        ),
+        None => (
+            format!("className({})", quoted(&node.class)),
+            nodes[..index]
+                .iter()
+                .filter(|n| n.class == node.class)
+                .count(),
+        ),
+    };
+    format!("new UiSelector().{base}.instance({instance})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"<?xml version='1.0' encoding='UTF-8' standalone='yes' ?><hierarchy rotation="0"><node index="0" text="" resource-id="" class="android.widget.FrameLayout" package="com.example.app" content-desc="" clickable="false" bounds="[0,0][1080,2400]"><node index="0" text="Sign in" resource-id="com.example.app:id/title" class="android.widget.TextView" package="com.example.app" content-desc="" clickable="false" bounds="[48,200][1032,280]" /><node index="1" text="" resource-id="com.example.app:id/field" class="android.widget.EditText" package="com.example.app" content-desc="Email" clickable="true" bounds="[48,320][1032,440]" /><node index="2" text="" resource-id="com.example.app:id/field" class="android.widget.EditText" package="com.example.app" content-desc="" clickable="true" bounds="[48,480][1032,600]" /><node index="3" text="Sign in" resource-id="" class="android.widget.Button" package="com.example.app" content-desc="" clickable="true" bounds="[48,700][1032,820]" /></node></hierarchy>UI hierchary dumped to: /dev/tty"#;
+
+    #[test]
+    fn parses_the_tree_depth_first() {
+        let nodes = parse_hierarchy(DUMP).unwrap();
+        assert_eq!(nodes.len(), 5);
+        let depths: Vec<usize> = nodes.iter().map(|n| n.depth).collect();
+        assert_eq!(depths, [0, 1, 1, 1, 1]);
+        let title = &nodes[1];
+        assert_eq!(title.short_class(), "TextView");
+        assert_eq!(title.short_id(), Some("title"));
+        assert_eq!(title.text.as_deref(), Some("Sign in"));
+        assert_eq!(title.content_desc, None);
+        assert_eq!(nodes[4].bounds.unwrap().center(), (540, 760));
+        assert!(nodes[4].clickable);
+        assert!(nodes[2].matches("email"));
+
+        let error = parse_hierarchy("ERROR: could not get idle state.\n").unwrap_err();
+        assert!(error.to_string().contains("could not get idle state"));
+    }
+
+    #[test]
+    fn selectors_use_the_first_unique_attribute() {
+        let nodes = parse_hierarchy(DUMP).unwrap();
+        assert_eq!(
+            selector(&nodes, 1),
+            r#"new UiSelector().resourceId("com.example.app:id/title")"#
+        );
+        assert_eq!(
+            selector(&nodes, 2),
+            r#"new UiSelector().description("Email")"#
+        );
+        assert_eq!(
+            selector(&nodes, 3),
+            r#"new UiSelector().resourceId("com.example.app:id/field").instance(1)"#
+        );
+        // Text shared with the title
+        assert_eq!(
+            selector(&nodes, 4),
+            r#"new UiSelector().className("android.widget.Button").instance(0)"#
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = parse_bounds("[0,63][1080,2337]").unwrap();
+        assert_eq!(bounds.center(), (540, 1200));
+        assert!(parse_bounds("[0,0][10]").is_none());
+        assert!(parse_bounds("[5,5][5,9]").unwrap().is_empty());
+    }
+}
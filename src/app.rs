@@ -1,10 +1,17 @@
-use std::time::Instant;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
+use chrono::Local;
 use color_eyre::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
@@ -13,35 +20,153 @@ use tokio::sync::mpsc;
 use tracing::debug;
 
 use crate::{
-    adb::client::AdbClient,
+    adb::{
+        battery::BatteryChange,
+        bugreport::{list_bugreports, read_contents},
+        capture::{Screenshot, capture_file_name, save_capture},
+        client::AdbClient,
+        device::{Device, DeviceState},
+        display::save_display_presets,
+        forward::{PortRule, save_rule_sets},
+        input::{InputEvent, coalesce},
+        intent::IntentHistory,
+        network::{host_proxy, host_proxy_rule},
+        properties::diff_props,
+        screenrecord::segment_file_name,
+        settings::SettingChange,
+        shell::ShellSession,
+    },
+    clipboard::osc52_sequence,
     command::Command,
     components::{
         Component, DrawContext,
-        modals::{Modal, emulators::EmulatorsModal, help::HelpModal},
-        panes::{Pane, content::ContentPane, devices::DevicesPane},
+        modals::{Modal, emulators::EmulatorsModal, help::HelpModal, locale::LocaleModal},
+        panes::{
+            Pane, apps::AppsPane, bugreports::BugreportsPane, content::ContentPane,
+            dev_options::DevOptionsPane, devices::DevicesPane, display::DisplayPane,
+            dumpsys::DumpsysPane, emulator_controls::EmulatorControlsPane,
+            hierarchy::HierarchyPane, intents::IntentsPane, network::NetworkPane,
+            notifications::NotificationsPane, permissions::PermissionsPane, ports::PortsPane,
+            processes::ProcessesPane, properties::PropertiesPane, remote::RemotePane,
+            screenshot::ScreenshotPane, settings::SettingsPane, shell::ShellPane, tasks::TasksPane,
+        },
     },
     config::Config,
-    msg::Msg,
+    graphics::{
+        DEFAULT_CELL_SIZE, GraphicsProtocol, KITTY_DELETE_ALL, RgbaImage, fit_cells,
+        kitty_sequence, sixel_sequence,
+    },
+    metrics::MetricsHistory,
+    msg::{Affects, Msg},
     tui::{Event, Tui},
 };
 
+const STATUS_TIMEOUT: Duration = Duration::from_secs(4);
+const FOREGROUND_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEVICE_INFO_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Longest gap between two clicks on the same cell that makes a double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Lines one notch of the mouse wheel moves.
+const SCROLL_LINES: usize = 3;
+/// How long quitting waits for a recording to stop and be pulled.
+const RECORDING_QUIT_TIMEOUT: Duration = Duration::from_secs(15);
+/// The errno a PTY read returns once the shell on the other end has exited.
+const EIO: i32 = 5;
+
 pub struct App {
     running: bool,
 
     focus: Pane,
+    /// Pane shown to the right of the device list.
+    main_pane: Pane,
     config: Config,
     adb: AdbClient,
     last_refresh: Instant,
 
     devices: DevicesPane,
     content: ContentPane,
+    apps: AppsPane,
+    permissions: PermissionsPane,
+    processes: ProcessesPane,
+    properties: PropertiesPane,
+    settings: SettingsPane,
+    screenshot: ScreenshotPane,
+    remote: RemotePane,
+    intents: IntentsPane,
+    ports: PortsPane,
+    network: NetworkPane,
+    display: DisplayPane,
+    dev_options: DevOptionsPane,
+    dumpsys: DumpsysPane,
+    bugreports: BugreportsPane,
+    tasks: TasksPane,
+    notifications: NotificationsPane,
+    hierarchy: HierarchyPane,
+    emulator_controls: EmulatorControlsPane,
+    /// Devices online at the last refresh, to spot reconnects.
+    online_serials: HashSet<String>,
+    graphics: GraphicsProtocol,
+    /// Area and file of the image last painted with terminal graphics.
+    shown_graphic: Option<(Rect, PathBuf)>,
+    shell: ShellPane,
+    /// Live `adb shell` sessions, keyed by the id the shell pane gave them.
+    shells: HashMap<usize, ShellSession>,
+
+    recording: Option<Recording>,
+    /// Set on quit to kill bugreport captures still running.
+    bugreport_cancel: Arc<AtomicBool>,
+    /// The selected device's serial and resumed activity, for the title bar.
+    foreground: Option<(String, Option<String>)>,
+    /// When the last foreground poll went out; `None` polls on the next tick.
+    foreground_polled: Option<Instant>,
+    /// Whether a foreground poll is still waiting for adb.
+    foreground_pending: bool,
+    /// Readings per serial for the content pane's charts, kept while other
+    /// panes or devices are shown.
+    metrics: HashMap<String, MetricsHistory>,
+    /// The device the last device info poll was for, and when it went out.
+    device_info_polled: Option<(String, Instant)>,
+    /// Whether a device info poll is still waiting for adb.
+    device_info_pending: bool,
+    /// Text to hand the terminal with OSC 52 after the next frame.
+    clipboard: Option<String>,
+    /// Feeds the thread that injects remote-control input, in order.
+    input_tx: Option<std::sync::mpsc::Sender<(String, Vec<InputEvent>)>>,
 
     modal: Option<Modal>,
+    status: Option<StatusLine>,
+    /// Where the last frame put each pane, for hit-testing the mouse.
+    areas: Cell<ScreenAreas>,
+    /// Time and cell of the last left click, to spot double-clicks.
+    last_click: Option<(Instant, Position)>,
 
     msg_tx: mpsc::UnboundedSender<Msg>,
     msg_rx: mpsc::UnboundedReceiver<Msg>,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+struct ScreenAreas {
+    /// The whole frame, which modals center themselves in.
+    screen: Rect,
+    devices: Rect,
+    main: Rect,
+}
+
+struct StatusLine {
+    text: String,
+    is_error: bool,
+    created: Instant,
+}
+
+/// The screen recording in progress; one runs at a time.
+struct Recording {
+    serial: String,
+    device_name: String,
+    started: Instant,
+    /// Set to make the recorder interrupt `screenrecord` and pull what it has.
+    stop: Arc<AtomicBool>,
+}
+
 enum GlobalAction {
     Quit,
     CycleFocus,
@@ -71,6 +196,49 @@ impl App {
 
         let device_keymap = config.keybindings.section_keymap("DeviceList");
         let devices_pane = DevicesPane::new(devices, device_keymap);
+        let apps = AppsPane::new(config.keybindings.section_keymap("Apps"));
+        let permissions = PermissionsPane::new(config.keybindings.section_keymap("Permissions"));
+        let processes = ProcessesPane::new(config.keybindings.section_keymap("Processes"));
+        let properties = PropertiesPane::new(config.keybindings.section_keymap("Properties"));
+        let settings = SettingsPane::new(config.keybindings.section_keymap("Settings"));
+        let graphics = GraphicsProtocol::detect(config.config.preview_protocol.as_deref());
+        let screenshot =
+            ScreenshotPane::new(config.keybindings.section_keymap("Screenshot"), graphics);
+        let remote = RemotePane::new(
+            config.keybindings.section_keymap("Remote"),
+            config.keybindings.section_keymap("RemoteControl"),
+        );
+        let history = IntentHistory::load(&config.intent_history_path()).unwrap_or_else(|e| {
+            tracing::warn!("{e}");
+            IntentHistory::default()
+        });
+        let intents = IntentsPane::new(config.keybindings.section_keymap("Intents"), history);
+        let ports = PortsPane::new(
+            config.keybindings.section_keymap("Ports"),
+            config.config.port_rules.clone(),
+        );
+        let content = ContentPane::new(config.keybindings.section_keymap("Content"));
+        let network = NetworkPane::new(
+            config.keybindings.section_keymap("Network"),
+            config.proxy_port(),
+        );
+        let display = DisplayPane::new(
+            config.keybindings.section_keymap("Display"),
+            config.config.display_presets.clone(),
+        );
+        let dev_options = DevOptionsPane::new(config.keybindings.section_keymap("DevOptions"));
+        let dumpsys = DumpsysPane::new(config.keybindings.section_keymap("Dumpsys"));
+        let bugreports = BugreportsPane::new(config.keybindings.section_keymap("Bugreports"));
+        let tasks = TasksPane::new(config.keybindings.section_keymap("Tasks"));
+        let notifications =
+            NotificationsPane::new(config.keybindings.section_keymap("Notifications"));
+        let hierarchy = HierarchyPane::new(config.keybindings.section_keymap("Hierarchy"));
+        let emulator_controls =
+            EmulatorControlsPane::new(config.keybindings.section_keymap("EmulatorControls"));
+        let shell = ShellPane::new(
+            config.keybindings.section_keymap("Shell"),
+            config.keybindings.section_keymap("ShellSession"),
+        );
 
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
 
@@ -78,14 +246,52 @@ impl App {
             running: true,
 
             focus: Pane::DeviceList,
-            config: config,
-            adb: adb,
+            main_pane: Pane::Content,
+            config,
+            adb,
             last_refresh: Instant::now(),
 
             devices: devices_pane,
-            content: ContentPane::new(),
+            content,
+            apps,
+            permissions,
+            processes,
+            properties,
+            settings,
+            screenshot,
+            remote,
+            intents,
+            ports,
+            network,
+            display,
+            dev_options,
+            dumpsys,
+            bugreports,
+            tasks,
+            notifications,
+            hierarchy,
+            emulator_controls,
+            online_serials: HashSet::new(),
+            graphics,
+            shown_graphic: None,
+            shell,
+            shells: HashMap::new(),
+
+            recording: None,
+            bugreport_cancel: Arc::new(AtomicBool::new(false)),
+            foreground: None,
+            foreground_polled: Some(Instant::now()),
+            foreground_pending: false,
+            metrics: HashMap::new(),
+            device_info_polled: None,
+            device_info_pending: false,
+            clipboard: None,
+            input_tx: None,
 
             modal: None,
+            status: None,
+            areas: Cell::new(ScreenAreas::default()),
+            last_click: None,
 
             msg_tx,
             msg_rx,
@@ -93,7 +299,7 @@ impl App {
     }
 
     pub async fn run(&mut self) -> Result<()> {
-        let mut tui = Tui::new()?;
+        let mut tui = Tui::new()?.paste(true).mouse(self.config.mouse());
         tui.enter()?;
 
         loop {
@@ -101,6 +307,8 @@ impl App {
             self.handle_actions()?;
 
             if !self.running {
+                self.bugreport_cancel.store(true, Ordering::Relaxed);
+                self.finish_recording(&mut tui).await?;
                 tui.stop()?;
                 break;
             }
@@ -123,12 +331,103 @@ impl App {
             Event::Error => {}
             Event::FocusGained => {}
             Event::FocusLost => {}
-            Event::Paste(_) => {}
-            Event::Mouse(_) => {}
+            Event::Paste(text) => self.handle_paste(text),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
         }
         Ok(())
     }
 
+    /// Remote control and shell sessions take a paste whole. Text fields get
+    /// its first line as keystrokes, which is how pastes arrived before
+    /// bracketed paste; elsewhere it is dropped rather than run as shortcuts.
+    fn handle_paste(&mut self, text: String) {
+        let captures = match self.modal {
+            Some(Modal::Locale(ref locale)) => locale.captures_input(),
+            Some(_) => false,
+            None => self.focused_pane().captures_input(),
+        };
+        if !captures {
+            return;
+        }
+        if self.modal.is_none() && matches!(self.focus, Pane::Remote | Pane::Shell) {
+            let commands = self.focused_pane().update(&Msg::Paste(text));
+            self.execute_commands(commands).ok();
+            return;
+        }
+        let line = text.lines().next().unwrap_or_default();
+        for c in line.chars().filter(|c| !c.is_control()) {
+            self.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+    }
+
+    /// A left click focuses the pane under the pointer and selects in the
+    /// device list and emulators popup; a double-click activates. The wheel
+    /// focuses the pane under it and moves it like the arrow keys, except
+    /// while it is editing text.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let areas = self.areas.get();
+        let position = Position::new(mouse.column, mouse.row);
+        let hovered = if areas.devices.contains(position) {
+            Some((Pane::DeviceList, areas.devices))
+        } else if areas.main.contains(position) {
+            Some((self.main_pane, areas.main))
+        } else {
+            None
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double = self.last_click.is_some_and(|(at, cell)| {
+                    cell == position && at.elapsed() < DOUBLE_CLICK_INTERVAL
+                });
+                // A third click starts over rather than double-clicking again
+                self.last_click = (!double).then(|| (Instant::now(), position));
+                let click = |area| Msg::Click {
+                    area,
+                    row: mouse.row,
+                    double,
+                };
+
+                let commands = match (&mut self.modal, hovered) {
+                    (Some(Modal::Emulators(emulators)), _) => {
+                        emulators.update(&click(areas.screen))
+                    }
+                    (Some(_), _) | (None, None) => Vec::new(),
+                    (None, Some((pane, area))) => {
+                        self.focus = pane;
+                        self.focused_pane().update(&click(area))
+                    }
+                };
+                self.execute_commands(commands).ok();
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let captures = match self.modal {
+                    Some(Modal::Locale(ref locale)) => locale.captures_input(),
+                    Some(_) => false,
+                    None => {
+                        let Some((pane, _)) = hovered else {
+                            return;
+                        };
+                        self.focus = pane;
+                        self.focused_pane().captures_input()
+                    }
+                };
+                if captures {
+                    return;
+                }
+                let code = if mouse.kind == MouseEventKind::ScrollUp {
+                    KeyCode::Up
+                } else {
+                    KeyCode::Down
+                };
+                for _ in 0..SCROLL_LINES {
+                    self.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn handle_key(&mut self, key: KeyEvent) {
         debug!("Handle key: {}", key.code);
 
@@ -180,9 +479,41 @@ impl App {
                     self.execute_commands(commands).ok();
                     return;
                 }
+                Modal::Locale(locale) => {
+                    // Esc cancels the filter before it closes the picker
+                    if !locale.captures_input()
+                        && let Some(action) = global_action
+                    {
+                        match action {
+                            GlobalAction::CloseModal => {
+                                self.modal = None;
+                                return;
+                            }
+                            GlobalAction::ToggleHelp => {
+                                self.modal = Some(Modal::Help(HelpModal::new()));
+                                return;
+                            }
+                            GlobalAction::Quit => {
+                                self.running = false;
+                                return;
+                            }
+                            _ => {}
+                        }
+                    }
+                    let commands = locale.update(&Msg::KeyPress(key));
+                    self.execute_commands(commands).ok();
+                    return;
+                }
             }
         }
 
+        // A pane editing text gets every key, so typing `q` doesn't quit
+        if self.focused_pane().captures_input() {
+            let commands = self.focused_pane().update(&Msg::KeyPress(key));
+            self.execute_commands(commands).ok();
+            return;
+        }
+
         // Check global keybindings
         if let Some(action) = global_action {
             self.handle_global_action(action);
@@ -200,17 +531,39 @@ impl App {
             GlobalAction::Quit => {
                 self.running = false;
             }
-            GlobalAction::CycleFocus => {
-                self.focus = self.focus.next();
-            }
-            GlobalAction::CycleFocusBackwards => {
-                self.focus = self.focus.prev();
-            }
+            GlobalAction::CycleFocus => self.cycle_focus(true),
+            GlobalAction::CycleFocusBackwards => self.cycle_focus(false),
             GlobalAction::ToggleHelp => {
                 self.modal = Some(Modal::Help(HelpModal::new()));
             }
             GlobalAction::CloseModal => {
-                self.modal = None;
+                if self.modal.is_some() {
+                    self.modal = None;
+                } else if self.focused_pane().back() {
+                    // The pane closed one of its own sub-views
+                } else if self.main_pane != Pane::Content {
+                    self.show_pane(Pane::Content).ok();
+                }
+            }
+        }
+    }
+
+    /// Steps through the views in `Pane` order, the device list included.
+    /// Leaving the device list forwards lands on the view already shown.
+    fn cycle_focus(&mut self, forward: bool) {
+        if self.focus == Pane::DeviceList && forward {
+            self.focus = self.main_pane;
+            return;
+        }
+        let from = if self.focus == Pane::DeviceList {
+            Pane::DeviceList
+        } else {
+            self.main_pane
+        };
+        match if forward { from.next() } else { from.prev() } {
+            Pane::DeviceList => self.focus = Pane::DeviceList,
+            pane => {
+                self.show_pane(pane).ok();
             }
         }
     }
@@ -228,6 +581,155 @@ impl App {
                 debug!("Handling action: {action:?}");
             }
 
+            if matches!(action, Msg::Tick) {
+                self.poll_foreground();
+                self.poll_device_info()?;
+            }
+            if matches!(action, Msg::DeviceInfoUpdated { .. }) {
+                self.device_info_pending = false;
+            }
+            if let Msg::DeviceInfoUpdated {
+                result: Ok(ref info),
+                ..
+            } = action
+            {
+                let window = self.config.history_window();
+                self.metrics
+                    .entry(info.serial.clone())
+                    .or_insert_with(|| MetricsHistory::new(window))
+                    .record(info, Instant::now());
+            }
+            if let Msg::ForegroundActivity {
+                ref serial,
+                ref activity,
+            } = action
+            {
+                self.foreground = Some((serial.clone(), activity.clone()));
+                self.foreground_pending = false;
+            }
+            if let Msg::ShellExited { id, .. } = action {
+                self.shells.remove(&id);
+            }
+            if let Msg::DevicesUpdated(ref devices) = action {
+                self.reapply_port_rules(devices);
+            }
+            if let Msg::PortRulesApplied {
+                ref serial,
+                ref result,
+            } = action
+            {
+                match result {
+                    Ok(0) => {}
+                    Ok(n) => self.notify(format!("Applied {n} port rules on {serial}")),
+                    Err(e) => self.notify_error(e.clone()),
+                }
+            }
+            if let Msg::ChangeFinished {
+                ref serial,
+                ref affects,
+                ref result,
+            } = action
+            {
+                match result {
+                    Ok(text) => self.notify(text.clone()),
+                    Err(e) => self.notify_error(e.clone()),
+                }
+                let serial = serial.clone();
+                let refresh = match affects {
+                    Affects::Devices => Command::RefreshDevices,
+                    Affects::DeviceInfo => Command::RefreshDeviceInfo(serial),
+                    Affects::DevOptions => Command::RefreshDevOptions(serial),
+                    Affects::Display => Command::RefreshDisplay(serial),
+                    Affects::PortRules => Command::RefreshPorts(serial),
+                    Affects::Network => Command::RefreshNetwork(serial),
+                    Affects::Tasks => {
+                        self.foreground_polled = None;
+                        Command::RefreshActivityStack(serial)
+                    }
+                    Affects::Notifications => Command::RefreshNotifications(serial),
+                    Affects::Processes => Command::RefreshProcesses(serial),
+                    Affects::Properties => Command::RefreshProperties(serial),
+                    Affects::Permissions(package) => Command::RefreshPermissions {
+                        serial,
+                        package: package.clone(),
+                    },
+                };
+                self.execute_commands(vec![refresh])?;
+            }
+            if let Msg::SettingChanged {
+                ref change,
+                ref value,
+                ref result,
+                ..
+            } = action
+            {
+                let SettingChange { namespace, key, .. } = change;
+                match (result, value) {
+                    (Ok(()), Some(value)) => self.notify(format!("{namespace} {key} = {value}")),
+                    (Ok(()), None) => self.notify(format!("Deleted {namespace} {key}")),
+                    (Err(e), _) => self.notify_error(e.clone()),
+                }
+            }
+            if let Msg::SettingsReverted {
+                ref changes,
+                ref failed,
+                ..
+            } = action
+            {
+                if failed.is_empty() {
+                    self.notify(format!("Reverted {} settings", changes.len()));
+                } else {
+                    let keys: Vec<_> = failed
+                        .iter()
+                        .map(|c| format!("{}/{}", c.namespace, c.key))
+                        .collect();
+                    self.notify_error(format!("Failed to revert {}", keys.join(", ")));
+                }
+            }
+            // The revert log lives in the settings pane, which must hear about
+            // changes that finish after it was hidden
+            if matches!(
+                action,
+                Msg::SettingChanged { .. } | Msg::SettingsReverted { .. }
+            ) && self.main_pane != Pane::Settings
+            {
+                let commands = self.settings.update(&action);
+                self.execute_commands(commands)?;
+            }
+            if let Msg::InputFailed { ref error, .. } = action {
+                self.notify_error(error.clone());
+            }
+            if let Msg::RecordingFinished {
+                ref serial,
+                ref files,
+                ref error,
+            } = action
+                && self.recording.as_ref().is_some_and(|r| &r.serial == serial)
+            {
+                self.recording = None;
+                let dir = self.config.capture_dir();
+                let what = match files.len() {
+                    1 => "recording".to_string(),
+                    n => format!("{n} recording segments"),
+                };
+                match error {
+                    None => self.notify(format!("Saved {what} to {}", dir.display())),
+                    Some(e) if files.is_empty() => {
+                        self.notify_error(format!("Recording failed: {e}"))
+                    }
+                    Some(e) => self.notify_error(format!(
+                        "Recording failed: {e}; saved {what} to {}",
+                        dir.display()
+                    )),
+                }
+            }
+            if let Msg::BugreportCaptured { ref result, .. } = action {
+                match result {
+                    Ok(path) => self.notify(format!("Saved bugreport to {}", path.display())),
+                    Err(e) => self.notify_error(format!("Bugreport failed: {e}")),
+                }
+            }
+
             // Delegate to component update methods and collect commands
             let mut commands = Vec::new();
             for component in self.components() {
@@ -239,13 +741,41 @@ impl App {
         Ok(())
     }
 
+    /// Components that receive messages: the device list, the visible main pane
+    /// and the open modal. Hidden panes are re-synced in `show_pane`, except the
+    /// shell pane, which always listens so sessions keep their output, and the
+    /// bugreports pane, which follows captures running in the background.
     fn components(&mut self) -> Vec<&mut dyn Component> {
-        let mut components: Vec<&mut dyn Component> = vec![&mut self.devices, &mut self.content];
+        let mut components: Vec<&mut dyn Component> =
+            vec![&mut self.devices, &mut self.shell, &mut self.bugreports];
+        match self.main_pane {
+            Pane::DeviceList | Pane::Content => components.push(&mut self.content),
+            Pane::Apps => components.push(&mut self.apps),
+            Pane::Permissions => components.push(&mut self.permissions),
+            Pane::Processes => components.push(&mut self.processes),
+            Pane::Properties => components.push(&mut self.properties),
+            Pane::Settings => components.push(&mut self.settings),
+            Pane::Screenshot => components.push(&mut self.screenshot),
+            Pane::Remote => components.push(&mut self.remote),
+            Pane::Intents => components.push(&mut self.intents),
+            Pane::Ports => components.push(&mut self.ports),
+            Pane::Network => components.push(&mut self.network),
+            Pane::Display => components.push(&mut self.display),
+            Pane::DevOptions => components.push(&mut self.dev_options),
+            Pane::Dumpsys => components.push(&mut self.dumpsys),
+            Pane::Bugreports => {}
+            Pane::Tasks => components.push(&mut self.tasks),
+            Pane::Notifications => components.push(&mut self.notifications),
+            Pane::Hierarchy => components.push(&mut self.hierarchy),
+            Pane::EmulatorControls => components.push(&mut self.emulator_controls),
+            Pane::Shell => {}
+        }
 
         if let Some(ref mut modal) = self.modal {
             match modal {
                 Modal::Help(help) => components.push(help),
                 Modal::Emulators(emulators) => components.push(emulators),
+                Modal::Locale(locale) => components.push(locale),
             }
         }
 
@@ -256,9 +786,368 @@ impl App {
         match self.focus {
             Pane::DeviceList => &mut self.devices,
             Pane::Content => &mut self.content,
+            Pane::Apps => &mut self.apps,
+            Pane::Permissions => &mut self.permissions,
+            Pane::Processes => &mut self.processes,
+            Pane::Properties => &mut self.properties,
+            Pane::Settings => &mut self.settings,
+            Pane::Screenshot => &mut self.screenshot,
+            Pane::Remote => &mut self.remote,
+            Pane::Intents => &mut self.intents,
+            Pane::Ports => &mut self.ports,
+            Pane::Network => &mut self.network,
+            Pane::Display => &mut self.display,
+            Pane::DevOptions => &mut self.dev_options,
+            Pane::Dumpsys => &mut self.dumpsys,
+            Pane::Bugreports => &mut self.bugreports,
+            Pane::Tasks => &mut self.tasks,
+            Pane::Notifications => &mut self.notifications,
+            Pane::Hierarchy => &mut self.hierarchy,
+            Pane::EmulatorControls => &mut self.emulator_controls,
+            Pane::Shell => &mut self.shell,
+        }
+    }
+
+    fn main_component(&self) -> &dyn Component {
+        match self.main_pane {
+            Pane::DeviceList | Pane::Content => &self.content,
+            Pane::Apps => &self.apps,
+            Pane::Permissions => &self.permissions,
+            Pane::Processes => &self.processes,
+            Pane::Properties => &self.properties,
+            Pane::Settings => &self.settings,
+            Pane::Screenshot => &self.screenshot,
+            Pane::Remote => &self.remote,
+            Pane::Intents => &self.intents,
+            Pane::Ports => &self.ports,
+            Pane::Network => &self.network,
+            Pane::Display => &self.display,
+            Pane::DevOptions => &self.dev_options,
+            Pane::Dumpsys => &self.dumpsys,
+            Pane::Bugreports => &self.bugreports,
+            Pane::Tasks => &self.tasks,
+            Pane::Notifications => &self.notifications,
+            Pane::Hierarchy => &self.hierarchy,
+            Pane::EmulatorControls => &self.emulator_controls,
+            Pane::Shell => &self.shell,
+        }
+    }
+
+    /// Runs a slow adb call on the blocking pool and posts its result as a message.
+    fn spawn_adb<F>(&self, f: F)
+    where
+        F: FnOnce(&AdbClient) -> Msg + Send + 'static,
+    {
+        let adb = self.adb.clone();
+        let msg_tx = self.msg_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let _ = msg_tx.send(f(&adb));
+        });
+    }
+
+    /// Runs a change to a device in the background like `spawn_adb`. The
+    /// status line reports it and the view it `affects` is refreshed.
+    fn spawn_change<F>(&self, serial: String, affects: Affects, f: F)
+    where
+        F: FnOnce(&AdbClient, &str) -> Result<String> + Send + 'static,
+    {
+        self.spawn_adb(move |adb| {
+            let result = f(adb, &serial).map_err(|e| e.to_string());
+            Msg::ChangeFinished {
+                serial,
+                affects,
+                result,
+            }
+        });
+    }
+
+    fn send_input(&mut self, serial: String, events: Vec<InputEvent>) -> Result<()> {
+        let adb = &self.adb;
+        let msg_tx = &self.msg_tx;
+        let input_tx = self
+            .input_tx
+            .get_or_insert_with(|| spawn_input_sender(adb.clone(), msg_tx.clone()));
+        input_tx.send((serial, events))?;
+        Ok(())
+    }
+
+    fn show_pane(&mut self, pane: Pane) -> Result<()> {
+        self.main_pane = pane;
+        self.focus = pane;
+        // The pane missed selection and device list changes while hidden
+        let devices = self.devices.devices().to_vec();
+        self.msg_tx.send(Msg::DevicesUpdated(devices))?;
+        let device = self.devices.selected_device().cloned();
+        self.msg_tx.send(Msg::DeviceSelected(device))?;
+        Ok(())
+    }
+
+    fn notify(&mut self, text: impl Into<String>) {
+        self.status = Some(StatusLine {
+            text: text.into(),
+            is_error: false,
+            created: Instant::now(),
+        });
+    }
+
+    fn notify_error(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        tracing::warn!("{text}");
+        self.status = Some(StatusLine {
+            text,
+            is_error: true,
+            created: Instant::now(),
+        });
+    }
+
+    fn report(&mut self, result: Result<()>, success: String) {
+        match result {
+            Ok(()) => self.notify(success),
+            Err(e) => self.notify_error(e.to_string()),
+        }
+    }
+
+    fn open_shell(&mut self, id: usize, serial: &str, rows: u16, cols: u16) -> Result<()> {
+        match self.adb.open_shell(serial, rows, cols) {
+            Ok((session, reader)) => {
+                self.shells.insert(id, session);
+                spawn_shell_reader(id, reader, self.msg_tx.clone());
+            }
+            Err(e) => {
+                let error = Some(e.to_string());
+                self.msg_tx.send(Msg::ShellExited { id, error })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the configured rule sets to each device that just came online,
+    /// since adb drops forwards and reverses when the transport goes away.
+    fn reapply_port_rules(&mut self, devices: &[Device]) {
+        let online: HashSet<String> = devices
+            .iter()
+            .filter(|d| d.state == DeviceState::Online)
+            .map(|d| d.serial.clone())
+            .collect();
+        let proxy_port = self.config.proxy_port();
+        for serial in online.difference(&self.online_serials) {
+            let mut rules = Vec::new();
+            for (name, set) in &self.config.config.port_rules {
+                if set.is_empty() || !set.applies_to(serial) {
+                    continue;
+                }
+                match set.rules() {
+                    Ok(set_rules) => rules.extend(set_rules),
+                    Err(e) => tracing::warn!("Skipping port rule set '{name}': {e}"),
+                }
+            }
+            let serial = serial.clone();
+            self.spawn_adb(move |adb| {
+                // The global proxy survives a USB drop but its reverse does not
+                let proxy = adb.http_proxy(&serial).ok().flatten();
+                if proxy == Some(host_proxy(proxy_port)) {
+                    rules.push(host_proxy_rule(proxy_port));
+                }
+                let result = adb
+                    .apply_port_rules(&serial, &rules)
+                    .map_err(|e| e.to_string());
+                Msg::PortRulesApplied { serial, result }
+            });
+        }
+        self.online_serials = online;
+    }
+
+    fn apply_port_rules(&self, serial: String, rules: Vec<PortRule>) {
+        self.spawn_adb(move |adb| {
+            let result = adb
+                .apply_port_rules(&serial, &rules)
+                .map_err(|e| e.to_string());
+            Msg::PortRulesApplied { serial, result }
+        });
+    }
+
+    fn toggle_recording(&mut self, serial: String) {
+        if let Some(ref recording) = self.recording {
+            if recording.serial != serial {
+                let name = recording.device_name.clone();
+                self.notify_error(format!("Already recording {name}"));
+            } else if !recording.stop.swap(true, Ordering::Relaxed) {
+                self.notify("Stopping recording...");
+            }
+            return;
+        }
+
+        let device_name = self
+            .devices
+            .devices()
+            .iter()
+            .find(|d| d.serial == serial)
+            .map_or_else(|| serial.clone(), |d| d.display_name());
+        let stop = Arc::new(AtomicBool::new(false));
+        self.recording = Some(Recording {
+            serial: serial.clone(),
+            device_name: device_name.clone(),
+            started: Instant::now(),
+            stop: stop.clone(),
+        });
+        self.notify(format!("Recording {device_name}"));
+
+        let options = self.config.config.screenrecord.clone();
+        let dir = self.config.capture_dir();
+        let adb = self.adb.clone();
+        let msg_tx = self.msg_tx.clone();
+        // A plain thread, unlike spawn_adb, so one stuck in `adb pull` can't
+        // hold the process open after the bounded wait on quit
+        std::thread::spawn(move || {
+            let now = Local::now();
+            let stamp = now.format("%Y%m%d-%H%M%S").to_string();
+            let file_name = capture_file_name(&device_name, now, "mp4");
+            let (segments, recorded) = adb.screenrecord(&serial, &options, &stamp, &stop);
+            let mut error = recorded.err();
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                error.get_or_insert(e.into());
+            }
+            let mut files = Vec::new();
+            for (i, remote) in segments.iter().enumerate() {
+                let local = dir.join(segment_file_name(&file_name, i, segments.len()));
+                match adb.pull(&serial, remote, &local) {
+                    Ok(()) => files.push(local),
+                    Err(e) => {
+                        error.get_or_insert(e);
+                    }
+                }
+                if let Err(e) = adb.remove_file(&serial, remote) {
+                    error.get_or_insert(e);
+                }
+            }
+            let error = error.map(|e| e.to_string());
+            let _ = msg_tx.send(Msg::RecordingFinished {
+                serial,
+                files,
+                error,
+            });
+        });
+    }
+
+    /// Stops a running recording on quit and gives it `RECORDING_QUIT_TIMEOUT`
+    /// to save its segments.
+    async fn finish_recording(&mut self, tui: &mut Tui) -> Result<()> {
+        let Some(recording) = self.recording.take() else {
+            return Ok(());
+        };
+        recording.stop.store(true, Ordering::Relaxed);
+        self.notify(format!("Saving recording of {}...", recording.device_name));
+        self.render(tui)?;
+
+        let deadline = tokio::time::Instant::now() + RECORDING_QUIT_TIMEOUT;
+        loop {
+            match tokio::time::timeout_at(deadline, self.msg_rx.recv()).await {
+                Ok(Some(Msg::RecordingFinished { files, error, .. })) => {
+                    match error {
+                        Some(e) => tracing::warn!("Recording failed: {e}"),
+                        None => tracing::info!("Saved {} recording files", files.len()),
+                    }
+                    return Ok(());
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => return Ok(()),
+                Err(_) => {
+                    tracing::warn!(
+                        "Recording of {} did not finish saving; segments may remain in /sdcard",
+                        recording.device_name
+                    );
+                    return Ok(());
+                }
+            }
         }
     }
 
+    /// Looks up the selected device's resumed activity every couple of seconds.
+    fn poll_foreground(&mut self) {
+        if self.foreground_pending
+            || self
+                .foreground_polled
+                .is_some_and(|t| t.elapsed() < FOREGROUND_POLL_INTERVAL)
+        {
+            return;
+        }
+        let Some(device) = self
+            .devices
+            .selected_device()
+            .filter(|d| d.state == DeviceState::Online)
+        else {
+            return;
+        };
+        self.foreground_polled = Some(Instant::now());
+        self.foreground_pending = true;
+        let serial = device.serial.clone();
+        self.spawn_adb(move |adb| {
+            let activity = adb.resumed_activity(&serial).ok().flatten();
+            Msg::ForegroundActivity { serial, activity }
+        });
+    }
+
+    /// Reads the selected device's info every couple of seconds, right away
+    /// after the selection changes, so the charts cover time spent elsewhere.
+    fn poll_device_info(&mut self) -> Result<()> {
+        let Some(device) = self
+            .devices
+            .selected_device()
+            .filter(|d| d.state == DeviceState::Online)
+        else {
+            return Ok(());
+        };
+        if self.device_info_pending
+            || self
+                .device_info_polled
+            .as_ref()
+            .is_some_and(|(serial, t)| {
+                *serial == device.serial && t.elapsed() < DEVICE_INFO_POLL_INTERVAL
+            })
+        {
+            return Ok(());
+        }
+        let serial = device.serial.clone();
+        self.device_info_polled = Some((serial.clone(), Instant::now()));
+        self.device_info_pending = true;
+        self.execute_commands(vec![Command::RefreshDeviceInfo(serial)])
+    }
+
+    /// Runs `bugreportz` in the background, forwarding its progress, then
+    /// pulls the zip into the bugreport folder and removes it from the device.
+    fn capture_bugreport(&mut self, serial: String) {
+        let device_name = self
+            .devices
+            .devices()
+            .iter()
+            .find(|d| d.serial == serial)
+            .map_or_else(|| serial.clone(), |d| d.display_name());
+        self.notify(format!("Capturing a bugreport from {device_name}"));
+
+        let dir = self.config.bugreport_dir();
+        let msg_tx = self.msg_tx.clone();
+        let cancel = self.bugreport_cancel.clone();
+        self.spawn_adb(move |adb| {
+            let result = adb
+                .bugreport(&serial, &cancel, |done, total| {
+                    let _ = msg_tx.send(Msg::BugreportProgress {
+                        serial: serial.clone(),
+                        done,
+                        total,
+                    });
+                })
+                .and_then(|remote| {
+                    std::fs::create_dir_all(&dir)?;
+                    let local = dir.join(capture_file_name(&device_name, Local::now(), "zip"));
+                    adb.pull(&serial, &remote, &local)?;
+                    adb.remove_file(&serial, &remote)?;
+                    Ok(local)
+                })
+                .map_err(|e| e.to_string());
+            Msg::BugreportCaptured { serial, result }
+        });
+    }
+
     fn execute_commands(&mut self, commands: Vec<Command>) -> Result<()> {
         for cmd in commands {
             match cmd {
@@ -266,7 +1155,10 @@ impl App {
                     let _ = self.adb.start_emulator(&name);
                 }
                 Command::KillEmulator(serial) => {
-                    let _ = self.adb.kill_emulator(&serial);
+                    self.spawn_change(serial, Affects::Devices, |adb, serial| {
+                        adb.kill_emulator(serial)?;
+                        Ok(format!("Stopped {serial}"))
+                    });
                 }
                 Command::OpenEmulatorsModal => {
                     let emulators = self.adb.avds_with_status(self.devices.devices());
@@ -274,8 +1166,44 @@ impl App {
                     self.modal = Some(Modal::Emulators(EmulatorsModal::new(emulators, keymap)))
                 }
                 Command::CloseEmulatorsModal => self.modal = None,
+                Command::OpenLocaleModal(serial) => {
+                    let keymap = self.config.keybindings.section_keymap("LocaleModal");
+                    self.modal = Some(Modal::Locale(LocaleModal::new(serial.clone(), keymap)));
+                    self.spawn_adb(move |adb| {
+                        let result = adb.locale_state(&serial).map_err(|e| e.to_string());
+                        Msg::LocaleStateUpdated { serial, result }
+                    });
+                }
+                Command::CloseLocaleModal => self.modal = None,
+                Command::SetLocale { serial, tag, scope } => {
+                    self.spawn_change(serial, Affects::DeviceInfo, move |adb, serial| {
+                        adb.set_locale(serial, &tag, &scope)?;
+                        Ok(format!("Set the locale of {scope} to {tag}"))
+                    });
+                }
+                Command::SetTimezone { serial, zone } => {
+                    self.spawn_change(serial, Affects::DeviceInfo, move |adb, serial| {
+                        adb.set_timezone(serial, &zone)?;
+                        Ok(format!("Set the time zone to {zone}"))
+                    });
+                }
+                Command::EmulatorConsole { serial, command } => {
+                    self.spawn_adb(move |adb| {
+                        let result = adb
+                            .emulator_console(&serial, &command)
+                            .map_err(|e| e.to_string());
+                        Msg::EmulatorConsoleReplied {
+                            serial,
+                            command,
+                            result,
+                        }
+                    });
+                }
                 Command::DisconnectDevice(serial) => {
-                    let _ = self.adb.disconnect_device(&serial);
+                    self.spawn_change(serial, Affects::Devices, |adb, serial| {
+                        adb.disconnect_device(serial)?;
+                        Ok(format!("Disconnected {serial}"))
+                    });
                 }
                 Command::Focus(panel) => {
                     self.focus = panel;
@@ -287,15 +1215,396 @@ impl App {
                     }
                 }
                 Command::RefreshDeviceInfo(serial) => {
-                    if let Some(device) = self.devices.devices().iter().find(|d| d.serial == serial) {
-                        if let Ok(info) = self.adb.fetch_device_info(device) {
-                            self.msg_tx.send(Msg::DeviceInfoUpdated(info))?;
-                        }
+                    if let Some(device) = self.devices.devices().iter().find(|d| d.serial == serial)
+                    {
+                        let device = device.clone();
+                        self.spawn_adb(move |adb| {
+                            let result = adb
+                                .fetch_device_info(&device)
+                                .map(Box::new)
+                                .map_err(|e| e.to_string());
+                            Msg::DeviceInfoUpdated { serial, result }
+                        });
                     }
                 }
+                Command::SimulateBattery { serial, change } => {
+                    self.spawn_change(serial, Affects::DeviceInfo, move |adb, serial| {
+                        adb.simulate_battery(serial, change)?;
+                        Ok(if change == BatteryChange::Reset {
+                            format!("Restored the real battery state on {serial}")
+                        } else {
+                            format!("Simulating {change} on {serial}")
+                        })
+                    });
+                }
                 Command::DeviceSelected(device) => {
                     self.msg_tx.send(Msg::DeviceSelected(device))?;
                 }
+                Command::ShowPane(pane) => self.show_pane(pane)?,
+                Command::ShowPackage(package) => {
+                    self.show_pane(Pane::Apps)?;
+                    self.msg_tx.send(Msg::FocusPackage(package))?;
+                }
+                Command::RefreshProcesses(serial) => self.spawn_adb(move |adb| {
+                    let processes = adb.processes(&serial).map_err(|e| e.to_string());
+                    Msg::ProcessesUpdated { serial, processes }
+                }),
+                Command::KillProcess {
+                    serial,
+                    pid,
+                    signal,
+                } => {
+                    self.spawn_change(serial, Affects::Processes, move |adb, serial| {
+                        adb.kill_process(serial, pid, signal)?;
+                        Ok(format!("Sent SIG{} to {pid}", signal.as_str()))
+                    });
+                }
+                Command::LoadProcessMemInfo { serial, pid } => self.spawn_adb(move |adb| {
+                    let text = adb.process_meminfo(&serial, pid).map_err(|e| e.to_string());
+                    Msg::ProcessMemInfo { serial, pid, text }
+                }),
+                Command::RefreshProperties(serial) => self.spawn_adb(move |adb| {
+                    let props = adb.properties(&serial).map_err(|e| e.to_string());
+                    Msg::PropertiesUpdated { serial, props }
+                }),
+                Command::SetProperty { serial, key, value } => {
+                    self.spawn_change(serial, Affects::Properties, move |adb, serial| {
+                        adb.set_property(serial, &key, &value)?;
+                        Ok(format!("{key} = {value}"))
+                    });
+                }
+                Command::DiffProperties { left, right } => self.spawn_adb(move |adb| {
+                    let diff = adb
+                        .properties(&left)
+                        .and_then(|l| Ok(diff_props(&l, &adb.properties(&right)?)))
+                        .map_err(|e| e.to_string());
+                    Msg::PropertyDiff { left, right, diff }
+                }),
+                Command::RefreshSettings { serial, namespace } => self.spawn_adb(move |adb| {
+                    let settings = adb.settings(&serial, namespace).map_err(|e| e.to_string());
+                    Msg::SettingsUpdated {
+                        serial,
+                        namespace,
+                        settings,
+                    }
+                }),
+                Command::PutSetting {
+                    serial,
+                    change,
+                    value,
+                } => self.spawn_adb(move |adb| {
+                    let result = adb
+                        .put_setting(&serial, change.namespace, &change.key, &value)
+                        .map_err(|e| e.to_string());
+                    Msg::SettingChanged {
+                        serial,
+                        change,
+                        value: Some(value),
+                        result,
+                    }
+                }),
+                Command::DeleteSetting { serial, change } => self.spawn_adb(move |adb| {
+                    let result = adb
+                        .delete_setting(&serial, change.namespace, &change.key)
+                        .map_err(|e| e.to_string());
+                    Msg::SettingChanged {
+                        serial,
+                        change,
+                        value: None,
+                        result,
+                    }
+                }),
+                Command::RevertSettings { serial, changes } => self.spawn_adb(move |adb| {
+                    let failed = adb.revert_settings(&serial, &changes);
+                    Msg::SettingsReverted {
+                        serial,
+                        changes,
+                        failed,
+                    }
+                }),
+                Command::TakeScreenshot(serial) => {
+                    let name = self
+                        .devices
+                        .devices()
+                        .iter()
+                        .find(|d| d.serial == serial)
+                        .map_or_else(|| serial.clone(), |d| d.display_name());
+                    self.notify(format!("Capturing {name}..."));
+                    self.show_pane(Pane::Screenshot)?;
+                    let dir = self.config.capture_dir();
+                    self.spawn_adb(move |adb| {
+                        let result = adb
+                            .screenshot(&serial)
+                            .and_then(|png| {
+                                let file_name = capture_file_name(&name, Local::now(), "png");
+                                let path = save_capture(&dir, &file_name, &png)?;
+                                let image = RgbaImage::decode_png(&png)?;
+                                Ok(Screenshot { path, image })
+                            })
+                            .map_err(|e| e.to_string());
+                        Msg::ScreenshotTaken { serial, result }
+                    });
+                }
+                Command::ToggleRecording(serial) => self.toggle_recording(serial),
+                Command::RefreshPorts(serial) => self.spawn_adb(move |adb| {
+                    let rules = adb.port_rules(&serial).map_err(|e| e.to_string());
+                    Msg::PortsUpdated { serial, rules }
+                }),
+                Command::AddPortRule { serial, rule } => {
+                    self.spawn_change(serial, Affects::PortRules, move |adb, serial| {
+                        adb.add_port_rule(serial, &rule)?;
+                        Ok(format!("{} {rule}", rule.direction))
+                    });
+                }
+                Command::RemovePortRule { serial, rule } => {
+                    self.spawn_change(serial, Affects::PortRules, move |adb, serial| {
+                        adb.remove_port_rule(serial, &rule)?;
+                        Ok(format!("Removed {} {rule}", rule.direction))
+                    });
+                }
+                Command::ApplyPortRules { serial, rules } => self.apply_port_rules(serial, rules),
+                Command::SavePortRuleSets(sets) => {
+                    let own: BTreeMap<_, _> = sets
+                        .iter()
+                        .filter(|(name, _)| !self.config.config.config_rule_sets.contains(*name))
+                        .map(|(name, set)| (name.clone(), set.clone()))
+                        .collect();
+                    let result = save_rule_sets(&self.config.port_rules_path(), &own);
+                    if let Err(e) = result {
+                        self.notify_error(format!("Failed to save port rules: {e}"));
+                    }
+                    self.config.config.port_rules = sets;
+                }
+                Command::RefreshNetwork(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.network_info(&serial).map_err(|e| e.to_string());
+                    Msg::NetworkUpdated { serial, result }
+                }),
+                Command::SetHostProxy {
+                    serial,
+                    port,
+                    enable,
+                } => {
+                    self.spawn_change(serial, Affects::Network, move |adb, serial| {
+                        adb.set_host_proxy(serial, port, enable)?;
+                        Ok(if enable {
+                            format!("Proxying {serial} through host port {port}")
+                        } else {
+                            format!("Cleared the HTTP proxy on {serial}")
+                        })
+                    });
+                }
+                Command::RefreshDisplay(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.display_state(&serial).map_err(|e| e.to_string());
+                    Msg::DisplayUpdated { serial, result }
+                }),
+                Command::ChangeDisplay { serial, changes } => {
+                    self.spawn_change(serial, Affects::Display, move |adb, serial| {
+                        adb.change_display(serial, &changes)?;
+                        Ok(match changes.as_slice() {
+                            [change] => change.to_string(),
+                            _ => format!("Applied {} display changes", changes.len()),
+                        })
+                    });
+                }
+                Command::SaveDisplayPresets(presets) => {
+                    let own: BTreeMap<_, _> = presets
+                        .iter()
+                        .filter(|(name, _)| {
+                            !self.config.config.config_display_presets.contains(*name)
+                        })
+                        .map(|(name, preset)| (name.clone(), preset.clone()))
+                        .collect();
+                    let result = save_display_presets(&self.config.display_presets_path(), &own);
+                    if let Err(e) = result {
+                        self.notify_error(format!("Failed to save display presets: {e}"));
+                    }
+                    self.config.config.display_presets = presets;
+                }
+                Command::RefreshDevOptions(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.dev_options(&serial).map_err(|e| e.to_string());
+                    Msg::DevOptionsUpdated { serial, result }
+                }),
+                Command::SetDevOption {
+                    serial,
+                    option,
+                    value,
+                } => {
+                    self.spawn_change(serial, Affects::DevOptions, move |adb, serial| {
+                        adb.set_dev_option(serial, &option, value)?;
+                        Ok(format!("{}: {}", option.label, option.describe(value)))
+                    });
+                }
+                Command::RefreshDumpsysServices(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.dumpsys_services(&serial).map_err(|e| e.to_string());
+                    Msg::DumpsysServices { serial, result }
+                }),
+                Command::RunDumpsys { serial, service } => self.spawn_adb(move |adb| {
+                    let result = adb.dumpsys(&serial, &service).map_err(|e| e.to_string());
+                    Msg::DumpsysOutput {
+                        serial,
+                        service,
+                        result,
+                    }
+                }),
+                Command::SaveDumpsys {
+                    serial,
+                    service,
+                    text,
+                } => {
+                    let name = self
+                        .devices
+                        .devices()
+                        .iter()
+                        .find(|d| d.serial == serial)
+                        .map_or_else(|| serial.clone(), |d| d.display_name());
+                    let file_name =
+                        capture_file_name(&format!("{name}_{service}"), Local::now(), "txt");
+                    match save_capture(&self.config.capture_dir(), &file_name, text.as_bytes()) {
+                        Ok(path) => self.notify(format!("Saved {}", path.display())),
+                        Err(e) => self.notify_error(e.to_string()),
+                    }
+                }
+                Command::RefreshActivityStack(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.activity_stack(&serial).map_err(|e| e.to_string());
+                    Msg::ActivityStackUpdated { serial, result }
+                }),
+                Command::RemoveTask { serial, root_id } => {
+                    self.spawn_change(serial, Affects::Tasks, move |adb, serial| {
+                        adb.remove_task(serial, root_id)?;
+                        Ok(format!("Finished task #{root_id}"))
+                    });
+                }
+                Command::RefreshNotifications(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.notifications(&serial).map_err(|e| e.to_string());
+                    Msg::NotificationsUpdated { serial, result }
+                }),
+                Command::DismissNotification { serial, key } => {
+                    self.spawn_change(serial, Affects::Notifications, move |adb, serial| {
+                        adb.dismiss_notification(serial, &key)?;
+                        Ok("Dismissed notification (snoozed for a day)".to_string())
+                    });
+                }
+                Command::RefreshUiHierarchy(serial) => self.spawn_adb(move |adb| {
+                    let result = adb.ui_hierarchy(&serial).map_err(|e| e.to_string());
+                    Msg::UiHierarchyUpdated { serial, result }
+                }),
+                Command::CopyToClipboard(text) => {
+                    self.notify(format!("Copied {text}"));
+                    self.clipboard = Some(text);
+                }
+                Command::CaptureBugreport(serial) => self.capture_bugreport(serial),
+                Command::RefreshBugreports => {
+                    let dir = self.config.bugreport_dir();
+                    let msg_tx = self.msg_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = list_bugreports(&dir).map_err(|e| e.to_string());
+                        let _ = msg_tx.send(Msg::BugreportsListed(result));
+                    });
+                }
+                Command::OpenBugreport(path) => {
+                    let msg_tx = self.msg_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = read_contents(&path)
+                            .map(Arc::new)
+                            .map_err(|e| e.to_string());
+                        let _ = msg_tx.send(Msg::BugreportOpened { path, result });
+                    });
+                }
+                Command::DeleteBugreport(path) => {
+                    let result = std::fs::remove_file(&path);
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    self.report(result.map_err(Into::into), format!("Deleted {name}"));
+                    self.execute_commands(vec![Command::RefreshBugreports])?;
+                }
+                Command::LaunchIntent { serial, intent } => self.spawn_adb(move |adb| {
+                    let result = adb
+                        .launch_intent(&serial, &intent)
+                        .map_err(|e| e.to_string());
+                    Msg::IntentLaunched {
+                        serial,
+                        intent,
+                        result,
+                    }
+                }),
+                Command::SaveIntentHistory(history) => {
+                    if let Err(e) = history.save(&self.config.intent_history_path()) {
+                        self.notify_error(format!("Failed to save intent history: {e}"));
+                    }
+                }
+                Command::SendInput { serial, event } => self.send_input(serial, vec![event])?,
+                Command::PasteInput { serial, events } => self.send_input(serial, events)?,
+                Command::OpenShell {
+                    id,
+                    serial,
+                    rows,
+                    cols,
+                } => self.open_shell(id, &serial, rows, cols)?,
+                Command::ShellInput { id, data } => {
+                    if let Some(session) = self.shells.get_mut(&id)
+                        && let Err(e) = session.write(&data)
+                    {
+                        self.notify_error(e.to_string());
+                    }
+                }
+                Command::ResizeShell { id, rows, cols } => {
+                    if let Some(session) = self.shells.get(&id) {
+                        session.resize(rows, cols).ok();
+                    }
+                }
+                Command::CloseShell(id) => {
+                    self.shells.remove(&id);
+                }
+                Command::RefreshPackages(serial) => self.spawn_adb(move |adb| {
+                    let packages = adb.packages(&serial).map_err(|e| e.to_string());
+                    Msg::PackagesUpdated { serial, packages }
+                }),
+                Command::OpenPermissions { serial, package } => {
+                    self.show_pane(Pane::Permissions)?;
+                    self.execute_commands(vec![Command::RefreshPermissions { serial, package }])?;
+                }
+                Command::RefreshPermissions { serial, package } => self.spawn_adb(move |adb| {
+                    let result = adb
+                        .package_permissions(&serial, &package)
+                        .map(Box::new)
+                        .map_err(|e| e.to_string());
+                    Msg::PermissionsUpdated {
+                        serial,
+                        package,
+                        result,
+                    }
+                }),
+                Command::SetPermission {
+                    serial,
+                    package,
+                    permission,
+                    grant,
+                } => {
+                    let affects = Affects::Permissions(package.clone());
+                    self.spawn_change(serial, affects, move |adb, serial| {
+                        adb.set_permission(serial, &package, &permission, grant)?;
+                        let verb = if grant { "Granted" } else { "Revoked" };
+                        Ok(format!("{verb} {permission}"))
+                    });
+                }
+                Command::ResetPermissions { serial, package } => {
+                    let affects = Affects::Permissions(package.clone());
+                    self.spawn_change(serial, affects, move |adb, serial| {
+                        adb.reset_runtime_permissions(serial, &package)?;
+                        Ok(format!("Reset runtime permissions of {package}"))
+                    });
+                }
+                Command::SetAppOp {
+                    serial,
+                    package,
+                    op,
+                    mode,
+                } => {
+                    let affects = Affects::Permissions(package.clone());
+                    self.spawn_change(serial, affects, move |adb, serial| {
+                        adb.set_app_op(serial, &package, &op, mode)?;
+                        Ok(format!("{op} set to {mode}"))
+                    });
+                }
             }
         }
         Ok(())
@@ -311,6 +1620,63 @@ impl App {
 
     fn render(&mut self, tui: &mut Tui) -> Result<()> {
         tui.draw(|frame| self.draw(frame))?;
+        self.draw_graphics(tui)?;
+        if let Some(text) = self.clipboard.take() {
+            tui.backend_mut()
+                .write_all(osc52_sequence(&text).as_bytes())?;
+            Write::flush(tui.backend_mut())?;
+        }
+        Ok(())
+    }
+
+    /// Paints the screenshot preview with kitty or sixel graphics once the frame
+    /// is on screen, since ratatui's buffer only holds text.
+    fn draw_graphics(&mut self, tui: &mut Tui) -> Result<()> {
+        let visible = self.main_pane == Pane::Screenshot && self.modal.is_none();
+        let wanted = visible
+            .then(|| self.screenshot.graphic())
+            .flatten()
+            .map(|(area, shot)| (area, shot.path.clone()));
+        if wanted == self.shown_graphic {
+            return Ok(());
+        }
+
+        if self.shown_graphic.take().is_some() {
+            match self.graphics {
+                GraphicsProtocol::Kitty => {
+                    tui.backend_mut().write_all(KITTY_DELETE_ALL.as_bytes())?;
+                }
+                // Sixels are plain cells to the terminal; repaint everything over them
+                GraphicsProtocol::Sixel => {
+                    tui.clear()?;
+                    tui.draw(|frame| self.draw(frame))?;
+                }
+                GraphicsProtocol::HalfBlocks => {}
+            }
+        }
+
+        let Some((area, shot)) = visible.then(|| self.screenshot.graphic()).flatten() else {
+            Write::flush(tui.backend_mut())?;
+            return Ok(());
+        };
+        let cell = crossterm::terminal::window_size()
+            .ok()
+            .filter(|size| size.width > 0 && size.columns > 0 && size.rows > 0)
+            .map(|size| (size.width / size.columns, size.height / size.rows))
+            .unwrap_or(DEFAULT_CELL_SIZE);
+        let (width, height, cols, rows) = fit_cells(&shot.image, area.width, area.height, cell);
+        let scaled = shot.image.resize(width, height);
+        let sequence = match self.graphics {
+            GraphicsProtocol::Kitty => kitty_sequence(&scaled, cols, rows),
+            GraphicsProtocol::Sixel => sixel_sequence(&scaled),
+            GraphicsProtocol::HalfBlocks => return Ok(()),
+        };
+
+        let backend = tui.backend_mut();
+        crossterm::queue!(backend, crossterm::cursor::MoveTo(area.x, area.y))?;
+        backend.write_all(sequence.as_bytes())?;
+        Write::flush(backend)?;
+        self.shown_graphic = wanted;
         Ok(())
     }
 
@@ -320,6 +1686,7 @@ impl App {
         let ctx = DrawContext {
             focus: self.focus,
             config: &self.config,
+            metrics: &self.metrics,
         };
 
         let vertical = Layout::vertical([
@@ -329,48 +1696,156 @@ impl App {
         ])
         .split(area);
 
-        let selected_device_name = self
-            .devices
-            .selected_device()
-            .map(|d| d.display_name());
-        draw_title_bar(frame, vertical[0], selected_device_name.as_deref());
+        let selected_device = self.devices.selected_device();
+        let selected_device_name = selected_device.map(|d| d.display_name());
+        let foreground = self
+            .foreground
+            .as_ref()
+            .filter(|(serial, _)| selected_device.is_some_and(|d| &d.serial == serial))
+            .and_then(|(_, activity)| activity.as_deref());
+        draw_title_bar(
+            frame,
+            vertical[0],
+            selected_device_name.as_deref(),
+            foreground,
+            self.recording.as_ref(),
+        );
 
         let middle = Layout::horizontal([Constraint::Percentage(20), Constraint::Percentage(80)])
             .split(vertical[1]);
 
         self.devices.draw(frame, middle[0], &ctx);
-        self.content.draw(frame, middle[1], &ctx);
-        draw_command_bar(frame, vertical[2], self.focus);
+        self.main_component().draw(frame, middle[1], &ctx);
+        self.areas.set(ScreenAreas {
+            screen: area,
+            devices: middle[0],
+            main: middle[1],
+        });
+
+        let status = self
+            .status
+            .as_ref()
+            .filter(|s| s.created.elapsed() < STATUS_TIMEOUT);
+        draw_command_bar(frame, vertical[2], self.focus, status);
 
         if let Some(ref modal) = self.modal {
             match modal {
                 Modal::Help(help) => help.draw(frame, area, &ctx),
                 Modal::Emulators(emulators) => emulators.draw(frame, area, &ctx),
+                Modal::Locale(locale) => locale.draw(frame, area, &ctx),
             }
         }
     }
 }
 
-fn draw_title_bar(frame: &mut Frame, area: Rect, selected_device: Option<&str>) {
+/// Drains a shell's output on its own thread; the read blocks until the device
+/// prints something or the session ends.
+fn spawn_shell_reader(
+    id: usize,
+    mut reader: Box<dyn Read + Send>,
+    msg_tx: mpsc::UnboundedSender<Msg>,
+) {
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        let error = loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break None,
+                Ok(n) => {
+                    let data = buf[..n].to_vec();
+                    if msg_tx.send(Msg::ShellOutput { id, data }).is_err() {
+                        return;
+                    }
+                }
+                Err(e) if e.raw_os_error() == Some(EIO) => break None,
+                Err(e) => break Some(e.to_string()),
+            }
+        };
+        let _ = msg_tx.send(Msg::ShellExited { id, error });
+    });
+}
+
+/// Injects input events one `adb shell input` at a time, so keys arrive in
+/// the order they were typed. Each message is a keystroke or a whole paste.
+fn spawn_input_sender(
+    adb: AdbClient,
+    msg_tx: mpsc::UnboundedSender<Msg>,
+) -> std::sync::mpsc::Sender<(String, Vec<InputEvent>)> {
+    let (tx, rx) = std::sync::mpsc::channel::<(String, Vec<InputEvent>)>();
+    std::thread::spawn(move || {
+        while let Ok(first) = rx.recv() {
+            // Whatever was typed while the last command ran goes out together
+            let mut queued = Vec::new();
+            for (serial, events) in std::iter::once(first).chain(rx.try_iter()) {
+                let needs_ime = events.iter().any(|e| matches!(e, InputEvent::ImeText(_)));
+                if needs_ime && let Err(e) = adb.check_adb_keyboard(&serial) {
+                    let error = e.to_string();
+                    let _ = msg_tx.send(Msg::InputFailed { serial, error });
+                    continue;
+                }
+                queued.extend(events.into_iter().map(|event| (serial.clone(), event)));
+            }
+            for (serial, event) in coalesce(queued) {
+                if let Err(e) = adb.send_input(&serial, &event) {
+                    let error = e.to_string();
+                    let _ = msg_tx.send(Msg::InputFailed { serial, error });
+                }
+            }
+        }
+    });
+    tx
+}
+
+fn draw_title_bar(
+    frame: &mut Frame,
+    area: Rect,
+    selected_device: Option<&str>,
+    foreground: Option<&str>,
+    recording: Option<&Recording>,
+) {
     let columns =
         Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area);
 
-    let title = Paragraph::new(Line::from(vec![Span::styled(
+    let mut title_spans = vec![Span::styled(
         "LazyADB v0.1.0",
         Style::default()
             .fg(Color::Green)
             .add_modifier(Modifier::BOLD),
-    )]));
+    )];
+    if let Some(activity) = foreground {
+        title_spans.push(Span::styled(
+            format!("  \u{25b8} {activity}"),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    let title = Paragraph::new(Line::from(title_spans));
 
     let (device_text, device_color) = match selected_device {
         Some(name) => (format!("device: {}", name), Color::White),
         None => ("device: <none>".to_string(), Color::DarkGray),
     };
 
-    let device_span = Line::from(vec![Span::styled(
-        device_text,
-        Style::default().fg(device_color),
-    )]);
+    let mut device_spans = Vec::new();
+    if let Some(recording) = recording {
+        let secs = recording.started.elapsed().as_secs();
+        let (text, color) = if recording.stop.load(Ordering::Relaxed) {
+            ("■ SAVING".to_string(), Color::Yellow)
+        } else {
+            (
+                format!("● REC {:02}:{:02}", secs / 60, secs % 60),
+                Color::Red,
+            )
+        };
+        device_spans.push(Span::styled(
+            text,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        device_spans.push(Span::styled(
+            format!(" {}  ", recording.device_name),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    device_spans.push(Span::styled(device_text, Style::default().fg(device_color)));
+    let device_span = Line::from(device_spans);
 
     let device = Paragraph::new(device_span).right_aligned();
 
@@ -378,17 +1853,176 @@ fn draw_title_bar(frame: &mut Frame, area: Rect, selected_device: Option<&str>)
     frame.render_widget(device, columns[1]);
 }
 
-fn draw_command_bar(frame: &mut Frame, area: Rect, focus: Pane) {
+fn draw_command_bar(frame: &mut Frame, area: Rect, focus: Pane, status: Option<&StatusLine>) {
     let columns = Layout::horizontal([Constraint::Min(0), Constraint::Length(8)]).split(area);
 
-    let mut hints = vec![("q", "Quit"), ("Tab", "Focus"), ("j/k", "Select")];
+    let right = Paragraph::new(Line::from(vec![Span::styled(
+        "? help",
+        Style::default().fg(Color::DarkGray),
+    )]))
+    .right_aligned();
+    frame.render_widget(right, columns[1]);
+
+    if let Some(status) = status {
+        let color = if status.is_error {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        let line = Line::from(Span::styled(
+            format!(" {}", status.text),
+            Style::default().fg(color),
+        ));
+        frame.render_widget(Paragraph::new(line), columns[0]);
+        return;
+    }
+
+    let mut hints = vec![("q", "Quit"), ("Tab", "Next view"), ("j/k", "Select")];
     match focus {
         Pane::DeviceList => {
             hints.push(("r", "Refresh"));
             hints.push(("x", "Disconnect"));
             hints.push(("e", "Emulators"));
+            hints.push(("a", "Apps"));
+            hints.push(("p", "Processes"));
+            hints.push(("s", "Shell"));
+            hints.push(("g", "Props"));
+            hints.push(("S", "Settings"));
+            hints.push(("c", "Screenshot"));
+            hints.push(("v", "Record"));
+            hints.push(("i", "Remote"));
+            hints.push(("I", "Intents"));
+            hints.push(("f", "Ports"));
+            hints.push(("n", "Network"));
+            hints.push(("d", "Display"));
+            hints.push(("o", "Dev options"));
+            hints.push(("D", "Dumpsys"));
+            hints.push(("b", "Bugreports"));
+            hints.push(("t", "Tasks"));
+            hints.push(("N", "Notifications"));
+            hints.push(("u", "UI hierarchy"));
+            hints.push(("l", "Locale"));
+            hints.push(("m", "Emulator"));
+        }
+        Pane::Apps => {
+            hints.push(("/", "Filter"));
+            hints.push(("Enter", "Permissions"));
+        }
+        Pane::Permissions => {
+            hints.push(("Space", "Grant/Revoke"));
+            hints.push(("o", "Perms/AppOps"));
+            hints.push(("m", "Mode"));
+            hints.push(("R", "Reset"));
+            hints.push(("h", "Back"));
+        }
+        Pane::Processes => {
+            hints.push(("/", "Filter"));
+            hints.push(("s", "Sort"));
+            hints.push(("x", "Kill"));
+            hints.push(("p", "Package"));
+            hints.push(("Enter", "Meminfo"));
+        }
+        Pane::Properties => {
+            hints.push(("/", "Search"));
+            hints.push(("g", "Group"));
+            hints.push(("e", "setprop"));
+            hints.push(("d", "Diff"));
+        }
+        Pane::Settings => {
+            hints.push(("/", "Search"));
+            hints.push(("o", "Namespace"));
+            hints.push(("e", "Edit"));
+            hints.push(("n", "Put"));
+            hints.push(("x", "Delete"));
+            hints.push(("R", "Revert all"));
+        }
+        Pane::Screenshot => {
+            hints.push(("c", "Capture again"));
+        }
+        Pane::Ports => {
+            hints.push(("a/A", "Forward/Reverse"));
+            hints.push(("x", "Remove"));
+            hints.push(("s", "Save set"));
+            hints.push(("o", "Sets"));
+        }
+        Pane::Network => {
+            hints.push(("p", "Proxy via host"));
+            hints.push(("r", "Refresh"));
+        }
+        Pane::Display => {
+            hints.push(("s/S", "Size"));
+            hints.push(("d/D", "Density"));
+            hints.push(("f", "Font"));
+            hints.push(("n", "Dark"));
+            hints.push(("l/o", "Lock/Rotate"));
+            hints.push(("p", "Save preset"));
+            hints.push(("Enter", "Apply"));
+        }
+        Pane::DevOptions => {
+            hints.push(("Space", "Toggle"));
+            hints.push(("d", "Default"));
+            hints.push(("r", "Refresh"));
+        }
+        Pane::Dumpsys => {
+            hints.push(("/", "Search"));
+            hints.push(("Enter", "Open"));
+            hints.push(("n/N", "Match"));
+            hints.push(("Space", "Fold"));
+            hints.push(("z/Z", "Fold/Unfold all"));
+            hints.push(("w", "Save"));
+        }
+        Pane::Bugreports => {
+            hints.push(("c", "Capture"));
+            hints.push(("Enter", "Open"));
+            hints.push(("l", "Main log"));
+            hints.push(("x", "Delete"));
+            hints.push(("/", "Search"));
+            hints.push(("Esc", "Back"));
+        }
+        Pane::Tasks => {
+            hints.push(("x", "Finish task"));
+            hints.push(("p", "Package"));
+            hints.push(("r", "Refresh"));
+        }
+        Pane::Notifications => {
+            hints.push(("/", "Filter package"));
+            hints.push(("x", "Dismiss"));
+            hints.push(("r", "Refresh"));
+        }
+        Pane::Hierarchy => {
+            hints.push(("/", "Search"));
+            hints.push(("t", "Tap"));
+            hints.push(("y", "Copy selector"));
+            hints.push(("r", "Dump again"));
+        }
+        Pane::EmulatorControls => {
+            hints.push(("Enter", "Set/Cycle"));
+            hints.push(("x", "Hang up"));
+        }
+        Pane::Intents => {
+            hints.push(("e", "Edit"));
+            hints.push(("a", "Add extra"));
+            hints.push(("l", "Launch"));
+            hints.push(("h", "History"));
+        }
+        Pane::Remote => {
+            hints.push(("Enter", "Take control"));
+            hints.push(("Esc", "Release"));
+        }
+        Pane::Shell => {
+            hints.push(("Enter", "Attach"));
+            hints.push(("c", "New shell"));
+            hints.push(("h/l", "Switch"));
+            hints.push(("x", "Close"));
+            hints.push(("C-a d", "Leave shell"));
+        }
+        Pane::Content => {
+            hints.push(("l", "Battery level"));
+            hints.push(("s", "Status"));
+            hints.push(("a/u", "AC/USB"));
+            hints.push(("U", "Unplug"));
+            hints.push(("R", "Reset battery"));
         }
-        _ => {}
     }
     let mut spans = Vec::new();
     for (i, (key, desc)) in hints.iter().enumerate() {
@@ -406,11 +2040,4 @@ fn draw_command_bar(frame: &mut Frame, area: Rect, focus: Pane) {
     }
     let left = Paragraph::new(Line::from(spans));
     frame.render_widget(left, columns[0]);
-
-    let right = Paragraph::new(Line::from(vec![Span::styled(
-        "? help",
-        Style::default().fg(Color::DarkGray),
-    )]))
-    .right_aligned();
-    frame.render_widget(right, columns[1]);
 }
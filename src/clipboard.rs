@@ -0,0 +1,19 @@
+//! Copying to the clipboard of the terminal, which also works over ssh.
+
+use base64::Engine;
+
+/// The OSC 52 escape that asks the terminal to put `text` on its clipboard.
+pub fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_base64_encoded() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
@@ -1,4 +1,18 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::adb::battery::BatteryChange;
+use crate::adb::console::ConsoleCommand;
+use crate::adb::dev_options::DevOption;
 use crate::adb::device::Device;
+use crate::adb::display::{DisplayChange, DisplayPreset};
+use crate::adb::forward::{PortRule, RuleSet};
+use crate::adb::input::InputEvent;
+use crate::adb::intent::{Intent, IntentHistory};
+use crate::adb::locale::LocaleScope;
+use crate::adb::permissions::AppOpMode;
+use crate::adb::processes::Signal;
+use crate::adb::settings::{SettingChange, SettingsNamespace};
 use crate::components::panes::Pane;
 
 #[allow(dead_code)]
@@ -7,11 +21,193 @@ pub enum Command {
     KillEmulator(String),
     OpenEmulatorsModal,
     CloseEmulatorsModal,
+    OpenLocaleModal(String),
+    CloseLocaleModal,
 
     RefreshDevices,
     RefreshDeviceInfo(String),
+    SimulateBattery {
+        serial: String,
+        change: BatteryChange,
+    },
     DisconnectDevice(String),
 
+    RefreshPackages(String),
+    OpenPermissions {
+        serial: String,
+        package: String,
+    },
+    RefreshPermissions {
+        serial: String,
+        package: String,
+    },
+    SetPermission {
+        serial: String,
+        package: String,
+        permission: String,
+        grant: bool,
+    },
+    ResetPermissions {
+        serial: String,
+        package: String,
+    },
+    SetAppOp {
+        serial: String,
+        package: String,
+        op: String,
+        mode: AppOpMode,
+    },
+
+    RefreshProcesses(String),
+    KillProcess {
+        serial: String,
+        pid: u32,
+        signal: Signal,
+    },
+    LoadProcessMemInfo {
+        serial: String,
+        pid: u32,
+    },
+    /// Opens the apps view at this package of the selected device.
+    ShowPackage(String),
+
+    RefreshProperties(String),
+    SetProperty {
+        serial: String,
+        key: String,
+        value: String,
+    },
+    DiffProperties {
+        left: String,
+        right: String,
+    },
+
+    RefreshSettings {
+        serial: String,
+        namespace: SettingsNamespace,
+    },
+    /// `change` carries the value being replaced, for the revert log.
+    PutSetting {
+        serial: String,
+        change: SettingChange,
+        value: String,
+    },
+    DeleteSetting {
+        serial: String,
+        change: SettingChange,
+    },
+    RevertSettings {
+        serial: String,
+        changes: Vec<SettingChange>,
+    },
+
+    TakeScreenshot(String),
+    ToggleRecording(String),
+    RefreshPorts(String),
+    AddPortRule {
+        serial: String,
+        rule: PortRule,
+    },
+    RemovePortRule {
+        serial: String,
+        rule: PortRule,
+    },
+    ApplyPortRules {
+        serial: String,
+        rules: Vec<PortRule>,
+    },
+    SavePortRuleSets(BTreeMap<String, RuleSet>),
+    RefreshNetwork(String),
+    SetHostProxy {
+        serial: String,
+        port: u16,
+        enable: bool,
+    },
+    RefreshDisplay(String),
+    ChangeDisplay {
+        serial: String,
+        changes: Vec<DisplayChange>,
+    },
+    SaveDisplayPresets(BTreeMap<String, DisplayPreset>),
+    RefreshDevOptions(String),
+    SetDevOption {
+        serial: String,
+        option: DevOption,
+        value: &'static str,
+    },
+    RefreshDumpsysServices(String),
+    RunDumpsys {
+        serial: String,
+        service: String,
+    },
+    SaveDumpsys {
+        serial: String,
+        service: String,
+        text: String,
+    },
+    RefreshActivityStack(String),
+    RemoveTask {
+        serial: String,
+        root_id: u32,
+    },
+    RefreshNotifications(String),
+    DismissNotification {
+        serial: String,
+        key: String,
+    },
+    SetLocale {
+        serial: String,
+        tag: String,
+        scope: LocaleScope,
+    },
+    SetTimezone {
+        serial: String,
+        zone: String,
+    },
+    RefreshUiHierarchy(String),
+    EmulatorConsole {
+        serial: String,
+        command: ConsoleCommand,
+    },
+    /// Sent to the terminal's clipboard with OSC 52.
+    CopyToClipboard(String),
+    CaptureBugreport(String),
+    RefreshBugreports,
+    OpenBugreport(PathBuf),
+    DeleteBugreport(PathBuf),
+    LaunchIntent {
+        serial: String,
+        intent: Intent,
+    },
+    SaveIntentHistory(IntentHistory),
+    SendInput {
+        serial: String,
+        event: InputEvent,
+    },
+    /// Types a paste, refused whole when part of it needs an absent keyboard.
+    PasteInput {
+        serial: String,
+        events: Vec<InputEvent>,
+    },
+
+    OpenShell {
+        id: usize,
+        serial: String,
+        rows: u16,
+        cols: u16,
+    },
+    ShellInput {
+        id: usize,
+        data: Vec<u8>,
+    },
+    ResizeShell {
+        id: usize,
+        rows: u16,
+        cols: u16,
+    },
+    CloseShell(usize),
+
     DeviceSelected(Option<Device>),
     Focus(Pane),
+    ShowPane(Pane),
 }
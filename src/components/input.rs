@@ -0,0 +1,169 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputOutcome {
+    Changed,
+    Submit,
+    Cancel,
+    Ignored,
+}
+
+/// Single-line text field used by filters, search boxes and forms.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in chars, not bytes.
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts pre-filled, with the cursor at the end.
+    pub fn with_value(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    fn insert(&mut self, c: char) {
+        let idx = self.byte_index();
+        self.value.insert(idx, c);
+        self.cursor += 1;
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> InputOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => InputOutcome::Submit,
+            KeyCode::Esc => InputOutcome::Cancel,
+            KeyCode::Char('u') if ctrl => {
+                self.clear();
+                InputOutcome::Changed
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.insert(c);
+                InputOutcome::Changed
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let idx = self.byte_index();
+                self.value.remove(idx);
+                InputOutcome::Changed
+            }
+            KeyCode::Delete if self.cursor < self.value.chars().count() => {
+                let idx = self.byte_index();
+                self.value.remove(idx);
+                InputOutcome::Changed
+            }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                InputOutcome::Ignored
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(self.value.chars().count());
+                InputOutcome::Ignored
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                InputOutcome::Ignored
+            }
+            KeyCode::End => {
+                self.cursor = self.value.chars().count();
+                InputOutcome::Ignored
+            }
+            _ => InputOutcome::Ignored,
+        }
+    }
+
+    /// Renders `label` followed by the value, with a block cursor when `active`.
+    pub fn line<'a>(&self, label: &str, active: bool) -> Line<'a> {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let value_style = Style::default().fg(Color::White);
+        let mut spans = vec![Span::styled(label.to_string(), label_style)];
+
+        if !active {
+            spans.push(Span::styled(self.value.clone(), value_style));
+            return Line::from(spans);
+        }
+
+        let idx = self.byte_index();
+        let (before, after) = self.value.split_at(idx);
+        let mut after_chars = after.chars();
+        let under_cursor = after_chars.next().map(String::from).unwrap_or(" ".into());
+        spans.push(Span::styled(before.to_string(), value_style));
+        spans.push(Span::styled(
+            under_cursor,
+            value_style.add_modifier(Modifier::REVERSED),
+        ));
+        spans.push(Span::styled(after_chars.as_str().to_string(), value_style));
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) -> InputOutcome {
+        input.handle_key(&KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn typing_and_editing() {
+        let mut input = TextInput::new();
+        for c in "héllo".chars() {
+            assert_eq!(press(&mut input, KeyCode::Char(c)), InputOutcome::Changed);
+        }
+        assert_eq!(input.value(), "héllo");
+
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "hélo");
+
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.value(), "élo");
+
+        press(&mut input, KeyCode::End);
+        press(&mut input, KeyCode::Char('!'));
+        assert_eq!(input.value(), "élo!");
+    }
+
+    #[test]
+    fn submit_cancel_and_clear() {
+        let mut input = TextInput::new();
+        press(&mut input, KeyCode::Char('a'));
+        assert_eq!(press(&mut input, KeyCode::Enter), InputOutcome::Submit);
+        assert_eq!(press(&mut input, KeyCode::Esc), InputOutcome::Cancel);
+        let ctrl_u = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(input.handle_key(&ctrl_u), InputOutcome::Changed);
+        assert_eq!(input.value(), "");
+        assert_eq!(press(&mut input, KeyCode::Backspace), InputOutcome::Ignored);
+    }
+}
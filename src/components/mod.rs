@@ -1,22 +1,71 @@
+use std::collections::HashMap;
+
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
 use crate::command::Command;
 use crate::config::Config;
+use crate::metrics::MetricsHistory;
 use crate::msg::Msg;
 use panes::Pane;
 
+pub mod input;
 pub mod modals;
 pub mod panes;
+pub mod viewer;
 
 #[allow(dead_code)]
 pub struct DrawContext<'a> {
     pub focus: Pane,
     pub config: &'a Config,
+    /// Readings per serial, recorded by the app whichever pane is visible.
+    pub metrics: &'a HashMap<String, MetricsHistory>,
 }
 
 pub trait Component {
     fn update(&mut self, action: &Msg) -> Vec<Command>;
     fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext);
+    #[allow(dead_code)]
     fn id(&self) -> &str;
+
+    /// Whether the component is editing text and wants keys before global bindings.
+    fn captures_input(&self) -> bool {
+        false
+    }
+
+    /// Closes an open sub-view on Esc. Returns `false` when there was none.
+    fn back(&mut self) -> bool {
+        false
+    }
+}
+
+/// The item on terminal row `row` of a bordered list with one line per item,
+/// drawn in `area` with a fresh `ListState`, which scrolls just far enough to
+/// show `selected`.
+pub fn list_item_at(area: Rect, row: u16, selected: usize, len: usize) -> Option<usize> {
+    let height = area.height.saturating_sub(2) as usize;
+    let line = row.checked_sub(area.y + 1)? as usize;
+    if line >= height {
+        return None;
+    }
+    let index = (selected + 1).saturating_sub(height) + line;
+    (index < len).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_rows_follow_the_scroll_offset() {
+        let area = Rect::new(0, 2, 20, 6);
+        assert_eq!(list_item_at(area, 2, 0, 10), None);
+        assert_eq!(list_item_at(area, 3, 0, 10), Some(0));
+        assert_eq!(list_item_at(area, 6, 0, 10), Some(3));
+        assert_eq!(list_item_at(area, 7, 0, 10), None);
+        assert_eq!(list_item_at(area, 5, 1, 2), None);
+        // Selecting the sixth item scrolls the first two out of view
+        assert_eq!(list_item_at(area, 3, 5, 10), Some(2));
+        assert_eq!(list_item_at(area, 6, 5, 10), Some(5));
+    }
 }
@@ -9,7 +9,7 @@ use ratatui::{
 use crate::{
     adb::emulator::Avd,
     command::Command,
-    components::{Component, DrawContext, modals::centered_rect, panes::Pane},
+    components::{Component, DrawContext, list_item_at, modals::centered_rect, panes::Pane},
     config::keymap::SectionKeymap,
     msg::Msg,
 };
@@ -28,6 +28,17 @@ impl EmulatorsModal {
             keymap,
         }
     }
+
+    /// Starts the selected AVD, or closes the popup if it is already running.
+    fn select(&self) -> Vec<Command> {
+        match self.items.get(self.selected_index) {
+            Some(avd) if avd.is_running() => {
+                vec![Command::CloseEmulatorsModal, Command::Focus(Pane::Content)]
+            }
+            Some(avd) => vec![Command::StartEmulator(avd.name.clone())],
+            None => Vec::new(),
+        }
+    }
 }
 
 enum EmulatorAction {
@@ -78,18 +89,19 @@ impl Component for EmulatorsModal {
                             return vec![Command::KillEmulator(serial.clone())];
                         }
                     }
-                    EmulatorAction::Select => {
-                        if let Some(avd) = self.items.get(self.selected_index) {
-                            if avd.is_running() {
-                                return vec![
-                                    Command::CloseEmulatorsModal,
-                                    Command::Focus(Pane::Content),
-                                ];
-                            } else {
-                                return vec![Command::StartEmulator(avd.name.clone())];
-                            }
-                        }
-                    }
+                    EmulatorAction::Select => return self.select(),
+                }
+                Vec::new()
+            }
+            Msg::Click { area, row, double } => {
+                let rect = centered_rect(50, 50, *area);
+                let Some(index) = list_item_at(rect, *row, self.selected_index, self.items.len())
+                else {
+                    return Vec::new();
+                };
+                self.selected_index = index;
+                if *double {
+                    return self.select();
                 }
                 Vec::new()
             }
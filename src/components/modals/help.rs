@@ -32,15 +32,92 @@ impl Component for HelpModal {
 Keybindings
 ───────────
 q         Quit
-Tab       Cycle focus (Devices → Content)
+Tab/S-Tab Next / previous view, device list included
+l / s     Simulate battery level / cycle status (content)
+a / u / U Toggle AC / toggle USB / unplug all (content)
+R         Restore the real battery state (content)
 j / ↓     Select next item
 k / ↑     Select previous item
 x         Disconnect device (TCP/Emulator)
 e         Open emulators popup
+a         Open apps view
+/         Filter packages (apps)
+Enter     Open package permissions (apps)
+Space     Grant / revoke permission
+o         Switch permissions / app ops
+m         Cycle app op mode
+R         Reset runtime permissions
+h         Back to apps (permissions)
+p         Open processes view
+s         Cycle sort: CPU / RSS / name (processes)
+x         Kill with signal choice (processes)
+p         Jump to owning package (processes)
+Enter     Show meminfo (processes)
+g         Open system properties view
+g         Cycle prefix group: ro. / persist. / sys. (properties)
+e         setprop the selected property (properties)
+d         Diff properties against another device
+S         Open settings database view
+o         Switch system / secure / global (settings)
+e / n / x Edit / put new / delete setting
+R         Revert every setting changed on the device
+c         Take a screenshot and preview it
+v         Start / stop screen recording
+i         Open remote control view
+I         Open intent launcher
+f         Open port forward / reverse view
+a / A     Add forward / reverse rule (ports)
+s / o     Save rules as a set / switch to sets (ports)
+n         Open network view
+p         Toggle device proxy through the host (network)
+d         Open display view
+s/d S/D   Override / reset size and density (display)
+f / n     Font scale / dark mode (display)
+l / o     Rotation lock / rotate (display)
+p / Enter Save / apply display preset
+o         Open developer options
+Space     Toggle / cycle option; d resets it (developer options)
+D         Open dumpsys service browser
+/ n N     Search output / next / previous match (dumpsys)
+Space z Z Fold section / fold all / unfold all (dumpsys)
+w         Save dumpsys output to the capture folder
+b         Open bugreport archive
+c         Capture a bugreport from the selected device (bugreports)
+Enter / l Open report sections / its system log (bugreports)
+x         Delete the selected report (bugreports)
+t         Open task stack viewer
+x / p     Finish task / jump to its package (tasks)
+N         Open notification inspector
+/ x       Filter by package / dismiss (notifications)
+u         Open UI hierarchy inspector
+/ n N     Search nodes / next / previous match (hierarchy)
+t / y     Tap node center / copy its UiSelector (hierarchy)
+l         Change locale or time zone
+Tab / /   Switch column / filter or type a value (locale)
+Enter / a Set the system locale (root) / the front app's (locale)
+m         Open emulator console controls
+Enter     Set location, SMS, call, battery / cycle the rest (emulator)
+x         Hang up the simulated call (emulator)
+e / t     Edit field / cycle kind or extra type (intents)
+a / l     Add extra / launch intent (intents)
+h         Launch history; Enter re-fires (intents)
+Enter     Drive the device from the keyboard (remote)
+F1-F8     Home / recents / menu / power / volume (remote)
+Paste     Type the pasted text on the device (remote)
+s         Open shell view
+Enter     Attach to the shell, starting one if needed (shell)
+c         Start another shell on the selected device (shell)
+C-a d     Leave the shell without closing it
+C-a c/x   New / close shell session
+C-a n/p   Next / previous shell session
+S-PgUp    Scroll shell history
 Enter     Start / select emulator (popup)
 x         Kill running emulator (popup)
+Click     Focus a pane / select a device or emulator
+Dbl-click Show device info / start emulator
+Wheel     Scroll the list or view under the pointer
 ?         Toggle help
-Esc       Close modal";
+Esc       Close modal / back to device info";
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -0,0 +1,438 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    adb::locale::{LOCALES, LocaleScope, LocaleState, TIME_ZONES, is_locale_tag, is_zone_id},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        modals::centered_rect,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum LocaleAction {
+    Up,
+    Down,
+    SwitchColumn,
+    Filter,
+    Apply,
+    ApplyToApp,
+}
+
+impl FromStr for LocaleAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "SwitchColumn" => Ok(Self::SwitchColumn),
+            "Filter" => Ok(Self::Filter),
+            "Apply" => Ok(Self::Apply),
+            "ApplyToApp" => Ok(Self::ApplyToApp),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Locales,
+    TimeZones,
+}
+
+pub struct LocaleModal {
+    serial: String,
+    /// The device's current settings; `None` while they load.
+    state: Option<Result<LocaleState, String>>,
+    column: Column,
+    /// Selection in the locale and time zone columns.
+    selected: [usize; 2],
+    /// Filters the focused column; applied as typed when nothing matches.
+    query: TextInput,
+    typing: bool,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl LocaleModal {
+    pub fn new(serial: String, keymap: SectionKeymap) -> Self {
+        Self {
+            serial,
+            state: None,
+            column: Column::Locales,
+            selected: [0, 0],
+            query: TextInput::new(),
+            typing: false,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn column_index(&self) -> usize {
+        self.column as usize
+    }
+
+    /// `(value, label)` of the focused column's items matching the query.
+    fn visible(&self, column: Column) -> Vec<(&'static str, &'static str)> {
+        let needle = self.query.value().to_lowercase();
+        let items: Vec<(&str, &str)> = match column {
+            Column::Locales => LOCALES.to_vec(),
+            Column::TimeZones => TIME_ZONES.iter().map(|zone| (*zone, "")).collect(),
+        };
+        if column != self.column || needle.is_empty() {
+            return items;
+        }
+        items
+            .into_iter()
+            .filter(|(value, label)| {
+                value.to_lowercase().contains(&needle) || label.to_lowercase().contains(&needle)
+            })
+            .collect()
+    }
+
+    /// Applies the selection; `to_app` sets the locale for the foreground app
+    /// only instead of the system.
+    fn apply(&mut self, to_app: bool) -> Vec<Command> {
+        let index = self.selected[self.column_index()];
+        let typed = self.query.value().trim().to_string();
+        let value = match self.visible(self.column).get(index) {
+            Some((value, _)) => value.to_string(),
+            None if !typed.is_empty() => typed,
+            None => return Vec::new(),
+        };
+        let serial = self.serial.clone();
+        let state = self.state.as_ref().and_then(|s| s.as_ref().ok());
+        let command = match self.column {
+            Column::Locales if is_locale_tag(&value) => {
+                let scope = if to_app {
+                    match state.and_then(|s| s.foreground.clone()) {
+                        Some(package) => LocaleScope::App(package),
+                        None => {
+                            self.notice = Some("No app is in the foreground".into());
+                            return Vec::new();
+                        }
+                    }
+                } else {
+                    LocaleScope::System
+                };
+                if let Some(error) = state.and_then(|s| s.scope_error(&scope)) {
+                    self.notice = Some(error.into());
+                    return Vec::new();
+                }
+                Command::SetLocale {
+                    serial,
+                    tag: value,
+                    scope,
+                }
+            }
+            Column::TimeZones if to_app => {
+                self.notice = Some("The time zone is system-wide".into());
+                return Vec::new();
+            }
+            Column::TimeZones if is_zone_id(&value) => Command::SetTimezone {
+                serial,
+                zone: value,
+            },
+            _ => {
+                self.notice = Some(format!("'{value}' is not a valid value"));
+                return Vec::new();
+            }
+        };
+        vec![command, Command::CloseLocaleModal]
+    }
+
+    fn handle_query_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.query.handle_key(key) {
+            InputOutcome::Submit => {
+                self.typing = false;
+                return self.apply(false);
+            }
+            InputOutcome::Cancel => {
+                self.typing = false;
+                self.query.clear();
+            }
+            InputOutcome::Changed => self.selected[self.column_index()] = 0,
+            InputOutcome::Ignored => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: LocaleAction) -> Vec<Command> {
+        let column = self.column_index();
+        let last = self.visible(self.column).len().saturating_sub(1);
+        match action {
+            LocaleAction::Up => self.selected[column] = self.selected[column].saturating_sub(1),
+            LocaleAction::Down => self.selected[column] = (self.selected[column] + 1).min(last),
+            LocaleAction::SwitchColumn => {
+                self.query.clear();
+                self.column = match self.column {
+                    Column::Locales => Column::TimeZones,
+                    Column::TimeZones => Column::Locales,
+                };
+            }
+            LocaleAction::Filter => self.typing = true,
+            LocaleAction::Apply => return self.apply(false),
+            LocaleAction::ApplyToApp => return self.apply(true),
+        }
+        Vec::new()
+    }
+
+    fn status_lines(&self) -> Vec<Line<'_>> {
+        let dim = Style::default().fg(Color::DarkGray);
+        let state = match self.state {
+            None => return vec![Line::raw(" Reading current settings...")],
+            Some(Err(ref e)) => {
+                return vec![Line::from(Span::styled(
+                    format!(" {e}"),
+                    Style::default().fg(Color::Red),
+                ))];
+            }
+            Some(Ok(ref state)) => state,
+        };
+        let available = |scope: &LocaleScope| {
+            Style::default().fg(match state.scope_error(scope) {
+                None => Color::Yellow,
+                Some(_) => Color::DarkGray,
+            })
+        };
+        let app = state.foreground.clone().map(LocaleScope::App);
+        let app_scope = match app {
+            Some(ref scope) => Span::styled(format!("a {scope}"), available(scope)),
+            None => Span::styled("a no app in front", dim),
+        };
+        let scopes = vec![
+            Span::styled(" Enter ", dim),
+            Span::styled(
+                "system (root, restarts the framework)",
+                available(&LocaleScope::System),
+            ),
+            Span::styled("  or  ", dim),
+            app_scope,
+            Span::styled(" (Android 13+)", dim),
+        ];
+        vec![
+            Line::from(vec![
+                Span::styled(" Current  ", dim),
+                Span::styled(
+                    format!("{}  {}", state.locale, state.timezone),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  (API {})", state.api_level), dim),
+            ]),
+            Line::from(scopes),
+        ]
+    }
+
+    fn draw_column(&self, frame: &mut Frame, area: Rect, column: Column) {
+        let focused = column == self.column;
+        let title = match column {
+            Column::Locales => " Locales ",
+            Column::TimeZones => " Time zones ",
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(if focused {
+                Color::Green
+            } else {
+                Color::DarkGray
+            }));
+        let items: Vec<ListItem> = self
+            .visible(column)
+            .into_iter()
+            .map(|(value, label)| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!(" {value:<8}")),
+                    Span::styled(format!(" {label}"), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        let mut list = List::new(items).block(block);
+        if focused {
+            list = list.highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+        }
+        let selected = self.selected[column as usize];
+        let mut list_state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+}
+
+impl Component for LocaleModal {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.typing && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_query_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| LocaleAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::LocaleStateUpdated { serial, result } if *serial == self.serial => {
+                self.state = Some(result.clone());
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, _ctx: &DrawContext) {
+        let rect = centered_rect(70, 70, area);
+        frame.render_widget(Clear, rect);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" LOCALE & TIME ZONE ")
+            .border_style(Style::default().fg(Color::Green));
+        let inner = block.inner(rect);
+        frame.render_widget(block, rect);
+
+        let rows = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+        frame.render_widget(Paragraph::new(self.status_lines()), rows[0]);
+        frame.render_widget(Paragraph::new(self.query.line(" / ", self.typing)), rows[1]);
+
+        let columns = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(rows[2]);
+        self.draw_column(frame, columns[0], Column::Locales);
+        self.draw_column(frame, columns[1], Column::TimeZones);
+
+        let footer = match self.notice {
+            Some(ref notice) => Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(Color::Yellow),
+            )),
+            None => Line::from(Span::styled(
+                " Tab switch column, / filter or type any value, Enter apply, a apply to app, Esc close",
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        frame.render_widget(Paragraph::new(footer), rows[3]);
+    }
+
+    fn id(&self) -> &'static str {
+        "Locale"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.typing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn make_modal() -> LocaleModal {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |code: KeyCode, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(code, KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind(KeyCode::Char('j'), "Down");
+        bind(KeyCode::Tab, "SwitchColumn");
+        bind(KeyCode::Char('/'), "Filter");
+        bind(KeyCode::Enter, "Apply");
+        bind(KeyCode::Char('a'), "ApplyToApp");
+        LocaleModal::new("DEV0".into(), keymap)
+    }
+
+    fn press(modal: &mut LocaleModal, code: KeyCode) -> Vec<Command> {
+        modal.update(&Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty())))
+    }
+
+    fn type_text(modal: &mut LocaleModal, text: &str) {
+        press(modal, KeyCode::Char('/'));
+        for c in text.chars() {
+            press(modal, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn filter_picks_a_pseudo_locale() {
+        let mut modal = make_modal();
+        type_text(&mut modal, "right-to");
+        assert!(matches!(
+            press(&mut modal, KeyCode::Enter).as_slice(),
+            [Command::SetLocale { tag, scope: LocaleScope::System, .. }, Command::CloseLocaleModal]
+                if tag == "ar-XB"
+        ));
+    }
+
+    #[test]
+    fn app_scope_is_picked_explicitly() {
+        let mut modal = make_modal();
+        modal.update(&Msg::LocaleStateUpdated {
+            serial: "DEV0".into(),
+            result: Ok(LocaleState {
+                api_level: 34,
+                foreground: Some("com.example.app".into()),
+                ..Default::default()
+            }),
+        });
+
+        // Without root the system locale is refused rather than narrowed
+        assert!(press(&mut modal, KeyCode::Enter).is_empty());
+        assert!(modal.notice.is_some());
+
+        assert!(matches!(
+            press(&mut modal, KeyCode::Char('a')).as_slice(),
+            [Command::SetLocale { tag, scope: LocaleScope::App(package), .. }, _]
+                if tag == "en-US" && package == "com.example.app"
+        ));
+    }
+
+    #[test]
+    fn unlisted_zones_are_applied_as_typed() {
+        let mut modal = make_modal();
+        press(&mut modal, KeyCode::Tab);
+        press(&mut modal, KeyCode::Char('j'));
+        assert!(matches!(
+            press(&mut modal, KeyCode::Enter).as_slice(),
+            [Command::SetTimezone { zone, .. }, _] if zone == "America/Anchorage"
+        ));
+
+        type_text(&mut modal, "Etc/GMT+3");
+        assert!(matches!(
+            press(&mut modal, KeyCode::Enter).as_slice(),
+            [Command::SetTimezone { zone, .. }, _] if zone == "Etc/GMT+3"
+        ));
+        type_text(&mut modal, "nowhere; reboot");
+        assert!(press(&mut modal, KeyCode::Enter).is_empty());
+        assert!(modal.notice.is_some());
+    }
+}
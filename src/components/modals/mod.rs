@@ -1,13 +1,15 @@
 use ratatui::layout::{Constraint, Layout, Rect};
 
-use crate::components::modals::{emulators::EmulatorsModal, help::HelpModal};
+use crate::components::modals::{emulators::EmulatorsModal, help::HelpModal, locale::LocaleModal};
 
 pub mod emulators;
 pub mod help;
+pub mod locale;
 
 pub enum Modal {
     Help(HelpModal),
     Emulators(EmulatorsModal),
+    Locale(LocaleModal),
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
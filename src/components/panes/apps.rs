@@ -0,0 +1,385 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum AppsAction {
+    Up,
+    Down,
+    Filter,
+    Permissions,
+    Refresh,
+}
+
+impl FromStr for AppsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Filter" => Ok(Self::Filter),
+            "Permissions" => Ok(Self::Permissions),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct AppsPane {
+    serial: Option<String>,
+    packages: Vec<String>,
+    error: Option<String>,
+    loaded: bool,
+    filter: TextInput,
+    filtering: bool,
+    selected_index: usize,
+    /// Package to select once the list for the current device arrives.
+    pending_focus: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl AppsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            packages: Vec::new(),
+            error: None,
+            loaded: false,
+            filter: TextInput::new(),
+            filtering: false,
+            selected_index: 0,
+            pending_focus: None,
+            keymap,
+        }
+    }
+
+    fn focus_package(&mut self, package: &str) {
+        self.filter.clear();
+        match self.packages.iter().position(|p| p == package) {
+            Some(index) => self.selected_index = index,
+            None => self.pending_focus = Some(package.to_string()),
+        }
+    }
+
+    fn visible_packages(&self) -> Vec<&String> {
+        let needle = self.filter.value().to_lowercase();
+        self.packages
+            .iter()
+            .filter(|p| needle.is_empty() || p.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    fn selected_package(&self) -> Option<&String> {
+        self.visible_packages().get(self.selected_index).copied()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible_packages().len();
+        self.selected_index = self.selected_index.min(len.saturating_sub(1));
+    }
+
+    fn handle_filter_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.filter.handle_key(key) {
+            InputOutcome::Submit => self.filtering = false,
+            InputOutcome::Cancel => {
+                self.filtering = false;
+                self.filter.clear();
+            }
+            InputOutcome::Changed => self.selected_index = 0,
+            InputOutcome::Ignored => {}
+        }
+        self.clamp_selection();
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: AppsAction) -> Vec<Command> {
+        match action {
+            AppsAction::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            AppsAction::Down => {
+                let len = self.visible_packages().len();
+                if len > 0 {
+                    self.selected_index = (self.selected_index + 1).min(len - 1);
+                }
+            }
+            AppsAction::Filter => {
+                self.filtering = true;
+            }
+            AppsAction::Permissions => {
+                if let (Some(serial), Some(package)) = (&self.serial, self.selected_package()) {
+                    return vec![Command::OpenPermissions {
+                        serial: serial.clone(),
+                        package: package.clone(),
+                    }];
+                }
+            }
+            AppsAction::Refresh => {
+                if let Some(ref serial) = self.serial {
+                    return vec![Command::RefreshPackages(serial.clone())];
+                }
+            }
+        }
+        Vec::new()
+    }
+}
+
+impl Component for AppsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.filtering && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_filter_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| AppsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial.clone();
+                self.packages.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                match new_serial {
+                    Some(serial) => vec![Command::RefreshPackages(serial)],
+                    None => Vec::new(),
+                }
+            }
+            Msg::PackagesUpdated { serial, packages } if self.serial.as_ref() == Some(serial) => {
+                self.loaded = true;
+                match packages {
+                    Ok(packages) => {
+                        self.packages = packages.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                if let Some(package) = self.pending_focus.take() {
+                    self.focus_package(&package);
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            Msg::FocusPackage(package) => {
+                self.focus_package(package);
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Apps;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let mut title = vec![Span::raw(" APPS ")];
+        // With packages still listed, a failed refresh would go unnoticed below
+        if let Some(ref e) = self.error
+            && !self.packages.is_empty()
+        {
+            title.push(Span::styled(
+                format!(" refresh failed: {e} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(title))
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(inner);
+        frame.render_widget(
+            Paragraph::new(self.filter.line(" / ", self.filtering)),
+            rows[0],
+        );
+
+        let packages = self.visible_packages();
+        if packages.is_empty() {
+            let paragraph = match (&self.serial, &self.error, self.loaded) {
+                (None, _, _) => Paragraph::new("Select a device to begin"),
+                (Some(_), Some(e), _) => {
+                    Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+                }
+                (Some(_), None, false) => Paragraph::new("Loading packages..."),
+                (Some(_), None, true) => Paragraph::new("(no packages)"),
+            };
+            frame.render_widget(paragraph, rows[1]);
+            return;
+        }
+
+        let items: Vec<ListItem> = packages
+            .iter()
+            .map(|p| ListItem::new(Line::raw(format!(" {p}"))))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(list, rows[1], &mut list_state);
+    }
+
+    fn id(&self) -> &'static str {
+        "Apps"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.filtering
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn make_keymap() -> SectionKeymap {
+        let mut keymap = SectionKeymap::new();
+        let bind = |c: char| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        keymap.insert(bind('j'), "Down".into());
+        keymap.insert(bind('k'), "Up".into());
+        keymap.insert(bind('/'), "Filter".into());
+        keymap.insert(bind('p'), "Permissions".into());
+        keymap
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn loaded_pane() -> AppsPane {
+        let mut pane = AppsPane::new(make_keymap());
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::PackagesUpdated {
+            serial: "DEV0".into(),
+            packages: Ok(vec!["com.android.chrome".into(), "com.example.app".into()]),
+        });
+        pane
+    }
+
+    #[test]
+    fn filter_narrows_list_and_captures_keys() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('/')));
+        assert!(pane.captures_input());
+
+        for c in "EXAM".chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+        assert_eq!(pane.visible_packages(), vec!["com.example.app"]);
+
+        pane.update(&key(KeyCode::Enter));
+        assert!(!pane.captures_input());
+        assert_eq!(pane.visible_packages().len(), 1);
+
+        pane.update(&key(KeyCode::Char('/')));
+        pane.update(&key(KeyCode::Esc));
+        assert_eq!(pane.visible_packages().len(), 2);
+    }
+
+    #[test]
+    fn permissions_action_opens_selected_package() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('j')));
+        let cmds = pane.update(&key(KeyCode::Char('p')));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::OpenPermissions { serial, package }
+                if serial == "DEV0" && package == "com.example.app"
+        )));
+    }
+
+    #[test]
+    fn device_change_requests_package_list() {
+        let mut pane = loaded_pane();
+        let cmds = pane.update(&Msg::DeviceSelected(None));
+        assert!(cmds.is_empty());
+        assert!(pane.visible_packages().is_empty());
+
+        let device = crate::adb::device::Device {
+            serial: "DEV1".into(),
+            state: crate::adb::device::DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: crate::adb::device::ConnectionType::Usb,
+        };
+        let cmds = pane.update(&Msg::DeviceSelected(Some(device)));
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::RefreshPackages(s) if s == "DEV1"))
+        );
+    }
+
+    #[test]
+    fn focus_package_waits_for_list() {
+        let mut pane = AppsPane::new(make_keymap());
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::FocusPackage("com.example.app".into()));
+        pane.update(&Msg::PackagesUpdated {
+            serial: "DEV0".into(),
+            packages: Ok(vec!["com.android.chrome".into(), "com.example.app".into()]),
+        });
+        assert_eq!(pane.selected_package().unwrap(), "com.example.app");
+    }
+
+    #[test]
+    fn stale_package_list_is_ignored() {
+        let mut pane = loaded_pane();
+        pane.update(&Msg::PackagesUpdated {
+            serial: "OTHER".into(),
+            packages: Ok(vec![]),
+        });
+        assert_eq!(pane.visible_packages().len(), 2);
+    }
+
+    #[test]
+    fn failed_refresh_keeps_previous_list() {
+        let mut pane = loaded_pane();
+        pane.update(&Msg::PackagesUpdated {
+            serial: "DEV0".into(),
+            packages: Err("device offline".into()),
+        });
+        assert_eq!(pane.visible_packages().len(), 2);
+        assert_eq!(pane.error.as_deref(), Some("device offline"));
+    }
+}
@@ -0,0 +1,695 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+    },
+};
+
+use crate::{
+    adb::bugreport::{BugreportContents, BugreportSummary},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+        viewer::TextViewer,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+/// Lines moved by PageUp and PageDown.
+const PAGE: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+enum BugreportsAction {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Open,
+    MainLog,
+    Capture,
+    Delete,
+    Search,
+    NextMatch,
+    PrevMatch,
+    ToggleFold,
+    FoldAll,
+    UnfoldAll,
+    Refresh,
+}
+
+impl FromStr for BugreportsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "PageUp" => Ok(Self::PageUp),
+            "PageDown" => Ok(Self::PageDown),
+            "Top" => Ok(Self::Top),
+            "Bottom" => Ok(Self::Bottom),
+            "Open" => Ok(Self::Open),
+            "MainLog" => Ok(Self::MainLog),
+            "Capture" => Ok(Self::Capture),
+            "Delete" => Ok(Self::Delete),
+            "Search" => Ok(Self::Search),
+            "NextMatch" => Ok(Self::NextMatch),
+            "PrevMatch" => Ok(Self::PrevMatch),
+            "ToggleFold" => Ok(Self::ToggleFold),
+            "FoldAll" => Ok(Self::FoldAll),
+            "UnfoldAll" => Ok(Self::UnfoldAll),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A report opened from the archive: its section list, then one section.
+struct OpenReport {
+    summary: BugreportSummary,
+    contents: Option<Result<Arc<BugreportContents>, String>>,
+    section_index: usize,
+    /// The open section's name and text.
+    section: Option<(String, TextViewer)>,
+    /// Opened with MainLog: show the system log as soon as it is read.
+    main_log_pending: bool,
+}
+
+pub struct BugreportsPane {
+    serial: Option<String>,
+    /// `(done, total)` of the captures running, by serial.
+    captures: BTreeMap<String, (u64, u64)>,
+    reports: Vec<BugreportSummary>,
+    error: Option<String>,
+    loaded: bool,
+    selected_index: usize,
+    report: Option<OpenReport>,
+    confirm_delete: Option<PathBuf>,
+    /// Searches the open section.
+    query: TextInput,
+    typing: bool,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl BugreportsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            captures: BTreeMap::new(),
+            reports: Vec::new(),
+            error: None,
+            loaded: false,
+            selected_index: 0,
+            report: None,
+            confirm_delete: None,
+            query: TextInput::new(),
+            typing: false,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn capture(&mut self) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            self.notice = Some("Select a device to capture from".into());
+            return Vec::new();
+        };
+        if self.captures.contains_key(&serial) {
+            self.notice = Some(format!("Already capturing a bugreport from {serial}"));
+            return Vec::new();
+        }
+        self.captures.insert(serial.clone(), (0, 0));
+        vec![Command::CaptureBugreport(serial)]
+    }
+
+    fn open_section(&mut self, index: usize) {
+        let Some(ref mut report) = self.report else {
+            return;
+        };
+        let Some(Ok(ref contents)) = report.contents else {
+            return;
+        };
+        if let Some(section) = contents.sections.get(index) {
+            report.section_index = index;
+            let viewer = TextViewer::new(contents.section_text(section));
+            report.section = Some((section.name.clone(), viewer));
+            self.query.clear();
+        }
+    }
+
+    fn search(&mut self, forward: bool) {
+        let Some(OpenReport {
+            section: Some((_, ref mut viewer)),
+            ..
+        }) = self.report
+        else {
+            return;
+        };
+        let query = self.query.value();
+        if !query.is_empty() && !viewer.find(query, forward) {
+            self.notice = Some(format!("No match for '{query}'"));
+        }
+    }
+
+    fn handle_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        if let Some(path) = self.confirm_delete.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => return vec![Command::DeleteBugreport(path)],
+                KeyCode::Char('n') | KeyCode::Esc => {}
+                _ => self.confirm_delete = Some(path),
+            }
+            return Vec::new();
+        }
+        match self.query.handle_key(key) {
+            InputOutcome::Submit => {
+                self.typing = false;
+                self.search(true);
+            }
+            InputOutcome::Cancel => {
+                self.typing = false;
+                self.query.clear();
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_section_action(&mut self, action: BugreportsAction) -> Vec<Command> {
+        match action {
+            BugreportsAction::Search => {
+                self.query.clear();
+                self.typing = true;
+                return Vec::new();
+            }
+            BugreportsAction::NextMatch => {
+                self.search(true);
+                return Vec::new();
+            }
+            BugreportsAction::PrevMatch => {
+                self.search(false);
+                return Vec::new();
+            }
+            _ => {}
+        }
+        let Some(OpenReport {
+            section: Some((_, ref mut viewer)),
+            ..
+        }) = self.report
+        else {
+            return Vec::new();
+        };
+        match action {
+            BugreportsAction::Up => viewer.move_cursor(-1),
+            BugreportsAction::Down => viewer.move_cursor(1),
+            BugreportsAction::PageUp => viewer.move_cursor(-(PAGE as isize)),
+            BugreportsAction::PageDown => viewer.move_cursor(PAGE as isize),
+            BugreportsAction::Top => viewer.move_cursor(isize::MIN),
+            BugreportsAction::Bottom => viewer.move_cursor(isize::MAX),
+            BugreportsAction::ToggleFold => viewer.toggle_fold(),
+            BugreportsAction::FoldAll => viewer.fold_all(),
+            BugreportsAction::UnfoldAll => viewer.unfold_all(),
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_report_action(&mut self, action: BugreportsAction) -> Vec<Command> {
+        let Some(ref mut report) = self.report else {
+            return Vec::new();
+        };
+        let len = match report.contents {
+            Some(Ok(ref contents)) => contents.sections.len(),
+            _ => 0,
+        };
+        let last = len.saturating_sub(1);
+        let index = &mut report.section_index;
+        match action {
+            BugreportsAction::Up => *index = index.saturating_sub(1),
+            BugreportsAction::Down => *index = (*index + 1).min(last),
+            BugreportsAction::PageUp => *index = index.saturating_sub(PAGE),
+            BugreportsAction::PageDown => *index = (*index + PAGE).min(last),
+            BugreportsAction::Top => *index = 0,
+            BugreportsAction::Bottom => *index = last,
+            BugreportsAction::Open => {
+                let index = *index;
+                self.open_section(index);
+            }
+            BugreportsAction::MainLog => self.open_main_log(),
+            BugreportsAction::Refresh => {
+                report.contents = None;
+                return vec![Command::OpenBugreport(report.summary.path.clone())];
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn open_main_log(&mut self) {
+        let main_log = match self.report {
+            Some(OpenReport {
+                contents: Some(Ok(ref contents)),
+                ..
+            }) => contents.main_log(),
+            _ => return,
+        };
+        match main_log {
+            Some(index) => self.open_section(index),
+            None => self.notice = Some("This report has no system log section".into()),
+        }
+    }
+
+    fn handle_list_action(&mut self, action: BugreportsAction) -> Vec<Command> {
+        let last = self.reports.len().saturating_sub(1);
+        match action {
+            BugreportsAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            BugreportsAction::Down => self.selected_index = (self.selected_index + 1).min(last),
+            BugreportsAction::PageUp => {
+                self.selected_index = self.selected_index.saturating_sub(PAGE);
+            }
+            BugreportsAction::PageDown => {
+                self.selected_index = (self.selected_index + PAGE).min(last);
+            }
+            BugreportsAction::Top => self.selected_index = 0,
+            BugreportsAction::Bottom => self.selected_index = last,
+            BugreportsAction::Capture => return self.capture(),
+            BugreportsAction::Refresh => return vec![Command::RefreshBugreports],
+            BugreportsAction::Open | BugreportsAction::MainLog => {
+                if let Some(summary) = self.reports.get(self.selected_index) {
+                    let path = summary.path.clone();
+                    self.report = Some(OpenReport {
+                        summary: summary.clone(),
+                        contents: None,
+                        section_index: 0,
+                        section: None,
+                        main_log_pending: action == BugreportsAction::MainLog,
+                    });
+                    return vec![Command::OpenBugreport(path)];
+                }
+            }
+            BugreportsAction::Delete => {
+                if let Some(summary) = self.reports.get(self.selected_index) {
+                    self.confirm_delete = Some(summary.path.clone());
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn footer(&self) -> Option<Line<'_>> {
+        if self.typing {
+            return Some(self.query.line(" Search: ", true));
+        }
+        if let Some(ref path) = self.confirm_delete {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            return Some(Line::from(vec![
+                Span::styled(
+                    format!(" Delete {name}?"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    "  y confirm, n cancel",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        self.notice.as_ref().map(|notice| {
+            Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(Color::Yellow),
+            ))
+        })
+    }
+
+    fn draw_captures(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical(vec![Constraint::Length(1); self.captures.len()]).split(area);
+        for ((serial, (done, total)), row) in self.captures.iter().zip(rows.iter()) {
+            let ratio = if *total > 0 {
+                (*done as f64 / *total as f64).min(1.0)
+            } else {
+                0.0
+            };
+            let label = if *total > 0 {
+                format!("{serial}: capturing bugreport {:.0}%", ratio * 100.0)
+            } else {
+                format!("{serial}: starting bugreport...")
+            };
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(Color::Green).bg(Color::Black))
+                .ratio(ratio)
+                .label(label);
+            frame.render_widget(gauge, *row);
+        }
+    }
+
+    fn draw_reports(&self, frame: &mut Frame, area: Rect) {
+        if self.reports.is_empty() {
+            let paragraph = match (&self.error, self.loaded) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, false) => Paragraph::new("Loading bugreports..."),
+                (None, true) => Paragraph::new("(no bugreports yet; press c to capture one)")
+                    .style(Style::default().fg(Color::DarkGray)),
+            };
+            frame.render_widget(paragraph, area);
+            return;
+        }
+        let header = Row::new(["TIME", "DEVICE", "BUILD FINGERPRINT"])
+            .style(Style::default().fg(Color::DarkGray));
+        let rows: Vec<Row> = self
+            .reports
+            .iter()
+            .map(|r| {
+                Row::new([
+                    r.time.clone(),
+                    r.device.clone(),
+                    r.fingerprint.clone().unwrap_or_default(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(20),
+                Constraint::Length(20),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut table_state = TableState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+
+    fn draw_report(&self, frame: &mut Frame, area: Rect, report: &OpenReport) {
+        let contents = match report.contents {
+            None => {
+                frame.render_widget(Paragraph::new("Reading report..."), area);
+                return;
+            }
+            Some(Err(ref e)) => {
+                let paragraph = Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red));
+                frame.render_widget(paragraph, area);
+                return;
+            }
+            Some(Ok(ref contents)) => contents,
+        };
+        if let Some((_, ref viewer)) = report.section {
+            viewer.draw(frame, area, self.query.value());
+            return;
+        }
+        let main_log = contents.main_log();
+        let items: Vec<ListItem> = contents
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| {
+                let style = if Some(i) == main_log {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {}", section.name), style),
+                    Span::styled(
+                        format!("  {} KB", section.range.len().div_ceil(1024)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(report.section_index));
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+}
+
+impl Component for BugreportsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.captures_input() && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_prompt_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| BugreportsAction::from_str(s).ok());
+                let Some(action) = action else {
+                    return Vec::new();
+                };
+                match self.report {
+                    _ if action == BugreportsAction::Capture => self.capture(),
+                    Some(OpenReport {
+                        section: Some(_), ..
+                    }) => self.handle_section_action(action),
+                    Some(_) => self.handle_report_action(action),
+                    None => self.handle_list_action(action),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                self.serial = device.as_ref().map(|d| d.serial.clone());
+                Vec::new()
+            }
+            Msg::BugreportProgress {
+                serial,
+                done,
+                total,
+            } => {
+                if let Some(progress) = self.captures.get_mut(serial) {
+                    *progress = (*done, *total);
+                }
+                Vec::new()
+            }
+            Msg::BugreportCaptured { serial, result } => {
+                self.captures.remove(serial);
+                match result {
+                    Ok(_) => vec![Command::RefreshBugreports],
+                    Err(_) => Vec::new(),
+                }
+            }
+            Msg::BugreportsListed(result) => {
+                self.loaded = true;
+                match result {
+                    Ok(reports) => {
+                        self.reports = reports.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.selected_index = self
+                    .selected_index
+                    .min(self.reports.len().saturating_sub(1));
+                Vec::new()
+            }
+            Msg::BugreportOpened { path, result } => {
+                if let Some(ref mut report) = self.report
+                    && report.summary.path == *path
+                {
+                    report.contents = Some(result.clone());
+                    report.section = None;
+                    report.section_index = 0;
+                    if std::mem::take(&mut report.main_log_pending) {
+                        self.open_main_log();
+                    }
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Bugreports;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = match self.report {
+            Some(ref report) => {
+                let summary = &report.summary;
+                match report.section {
+                    Some((ref name, _)) => format!(" BUGREPORT {} {} ", summary.device, name),
+                    None => format!(" BUGREPORT {} {} ", summary.device, summary.time),
+                }
+            }
+            None => " BUGREPORTS ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let footer = self.footer();
+        let rows = Layout::vertical([
+            Constraint::Length(self.captures.len() as u16),
+            Constraint::Min(1),
+            Constraint::Length(if footer.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        self.draw_captures(frame, rows[0]);
+        match self.report {
+            Some(ref report) => self.draw_report(frame, rows[1], report),
+            None => self.draw_reports(frame, rows[1]),
+        }
+        if let Some(footer) = footer {
+            frame.render_widget(Paragraph::new(footer), rows[2]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Bugreports"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.typing || self.confirm_delete.is_some()
+    }
+
+    fn back(&mut self) -> bool {
+        self.query.clear();
+        match self.report {
+            Some(OpenReport {
+                section: ref mut section @ Some(_),
+                ..
+            }) => {
+                *section = None;
+                true
+            }
+            Some(_) => {
+                self.report = None;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::bugreport::parse_sections;
+
+    fn make_pane() -> BugreportsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |c: char, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind('c', "Capture");
+        bind('l', "MainLog");
+        bind('x', "Delete");
+        let mut pane = BugreportsPane::new(keymap);
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::BugreportsListed(Ok(vec![BugreportSummary {
+            path: PathBuf::from("/tmp/Pixel_7_20250309-140507.zip"),
+            device: "Pixel 7".into(),
+            fingerprint: None,
+            time: "2025-03-09 14:05:07".into(),
+            modified: SystemTime::UNIX_EPOCH,
+        }])));
+        pane
+    }
+
+    fn press(pane: &mut BugreportsPane, c: char) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char(c),
+            KeyModifiers::empty(),
+        )))
+    }
+
+    #[test]
+    fn capture_tracks_progress_until_the_zip_is_pulled() {
+        let mut pane = make_pane();
+        assert!(matches!(
+            press(&mut pane, 'c').as_slice(),
+            [Command::CaptureBugreport(serial)] if serial == "DEV0"
+        ));
+        assert!(press(&mut pane, 'c').is_empty());
+        pane.update(&Msg::BugreportProgress {
+            serial: "DEV0".into(),
+            done: 30,
+            total: 100,
+        });
+        assert_eq!(pane.captures["DEV0"], (30, 100));
+        let cmds = pane.update(&Msg::BugreportCaptured {
+            serial: "DEV0".into(),
+            result: Ok(PathBuf::from("/tmp/report.zip")),
+        });
+        assert!(pane.captures.is_empty());
+        assert!(matches!(cmds.as_slice(), [Command::RefreshBugreports]));
+    }
+
+    #[test]
+    fn main_log_opens_once_the_report_is_read() {
+        let mut pane = make_pane();
+        let cmds = press(&mut pane, 'l');
+        let [Command::OpenBugreport(path)] = cmds.as_slice() else {
+            panic!("expected OpenBugreport");
+        };
+        let text = "== dumpstate: now\n------ UPTIME (uptime) ------\nup\n\
+                    ------ SYSTEM LOG (logcat -d) ------\nI Tag: hi\n";
+        pane.update(&Msg::BugreportOpened {
+            path: path.clone(),
+            result: Ok(Arc::new(BugreportContents {
+                text: text.into(),
+                sections: parse_sections(text),
+            })),
+        });
+        let report = pane.report.as_ref().unwrap();
+        assert_eq!(report.section_index, 2);
+        assert!(matches!(report.section, Some((ref name, _)) if name.starts_with("SYSTEM LOG")));
+
+        assert!(pane.back());
+        assert!(pane.report.as_ref().unwrap().section.is_none());
+        assert!(pane.back());
+        assert!(!pane.back());
+    }
+
+    #[test]
+    fn delete_asks_first() {
+        let mut pane = make_pane();
+        assert!(press(&mut pane, 'x').is_empty());
+        assert!(pane.captures_input());
+        assert!(matches!(
+            press(&mut pane, 'y').as_slice(),
+            [Command::DeleteBugreport(_)]
+        ));
+        assert!(!pane.captures_input());
+    }
+}
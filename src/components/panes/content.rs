@@ -1,64 +1,193 @@
-use std::time::Instant;
+use std::str::FromStr;
 
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Sparkline},
 };
 
 use crate::{
-    adb::device_info::DeviceInfo,
+    adb::{
+        battery::{BatteryChange, next_status},
+        cpu::CpuLoad,
+        device_info::{BatteryInfo, DeviceInfo},
+    },
     command::Command,
-    components::{Component, DrawContext, panes::Pane},
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    metrics::{Metric, MetricsHistory, downsample, format_window},
     msg::Msg,
 };
 
-const DEVICE_INFO_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+#[derive(Debug, Clone, PartialEq)]
+enum ContentAction {
+    SetBatteryLevel,
+    CycleBatteryStatus,
+    ToggleAc,
+    ToggleUsb,
+    Unplug,
+    ResetBattery,
+}
+
+impl FromStr for ContentAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SetBatteryLevel" => Ok(Self::SetBatteryLevel),
+            "CycleBatteryStatus" => Ok(Self::CycleBatteryStatus),
+            "ToggleAc" => Ok(Self::ToggleAc),
+            "ToggleUsb" => Ok(Self::ToggleUsb),
+            "Unplug" => Ok(Self::Unplug),
+            "ResetBattery" => Ok(Self::ResetBattery),
+            _ => Err(()),
+        }
+    }
+}
 
 pub struct ContentPane {
     device_info: Option<DeviceInfo>,
     selected_serial: Option<String>,
-    last_refresh: Instant,
+    /// The simulated battery level being typed.
+    level_input: Option<TextInput>,
+    keymap: SectionKeymap,
 }
 
 impl ContentPane {
-    pub fn new() -> Self {
+    pub fn new(keymap: SectionKeymap) -> Self {
         Self {
             device_info: None,
             selected_serial: None,
-            last_refresh: Instant::now(),
+            level_input: None,
+            keymap,
+        }
+    }
+
+    fn draw_history(&self, frame: &mut Frame, area: Rect, history: &MetricsHistory) {
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .title(format!(
+                " HISTORY (last {}) ",
+                format_window(history.window())
+            ))
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical(Metric::ALL.map(|_| Constraint::Length(2))).split(inner);
+        for (metric, row) in Metric::ALL.iter().zip(rows.iter()) {
+            let columns =
+                Layout::horizontal([Constraint::Length(22), Constraint::Min(0)]).split(*row);
+            let latest = history
+                .latest(*metric)
+                .map(|v| metric.format(v))
+                .unwrap_or_else(|| "-".to_string());
+            frame.render_widget(
+                Paragraph::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {:>12}  ", metric.label()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(latest, Style::default().fg(Color::White)),
+                ])),
+                columns[0],
+            );
+
+            // Tenths keep some resolution in the u64 bars
+            let data: Vec<u64> = downsample(&history.values(*metric), columns[1].width as usize)
+                .iter()
+                .map(|v| (v * 10.0).round() as u64)
+                .collect();
+            let mut sparkline = Sparkline::default()
+                .data(&data)
+                .style(Style::default().fg(metric_color(*metric)));
+            if let Some(max) = metric.max() {
+                sparkline = sparkline.max((max * 10.0) as u64);
+            }
+            frame.render_widget(sparkline, columns[1]);
+        }
+    }
+
+    fn simulate(&self, change: BatteryChange) -> Vec<Command> {
+        match self.selected_serial {
+            Some(ref serial) => vec![Command::SimulateBattery {
+                serial: serial.clone(),
+                change,
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_level_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(mut input)) = (msg, self.level_input.take()) else {
+            return Vec::new();
+        };
+        match input.handle_key(key) {
+            InputOutcome::Submit => match input.value().trim().parse::<u8>() {
+                Ok(level) if level <= 100 => return self.simulate(BatteryChange::Level(level)),
+                _ => self.level_input = Some(input),
+            },
+            InputOutcome::Cancel => {}
+            _ => self.level_input = Some(input),
         }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: ContentAction) -> Vec<Command> {
+        let Some(battery) = self.device_info.as_ref().and_then(|i| i.battery.as_ref()) else {
+            return Vec::new();
+        };
+        let change = match action {
+            ContentAction::SetBatteryLevel => {
+                self.level_input = Some(TextInput::with_value(&battery.level.to_string()));
+                return Vec::new();
+            }
+            ContentAction::CycleBatteryStatus => {
+                BatteryChange::Status(next_status(battery.status_code))
+            }
+            ContentAction::ToggleAc => BatteryChange::Ac(!battery.ac_powered),
+            ContentAction::ToggleUsb => BatteryChange::Usb(!battery.usb_powered),
+            ContentAction::Unplug => BatteryChange::Unplug,
+            ContentAction::ResetBattery => BatteryChange::Reset,
+        };
+        self.simulate(change)
     }
 }
 
 impl Component for ContentPane {
     fn update(&mut self, action: &Msg) -> Vec<Command> {
+        if matches!(action, Msg::KeyPress(_)) && self.level_input.is_some() {
+            return self.handle_level_key(action);
+        }
         match action {
-            Msg::Tick => {
-                if let Some(ref serial) = self.selected_serial {
-                    if self.last_refresh.elapsed() >= DEVICE_INFO_REFRESH_INTERVAL {
-                        self.last_refresh = Instant::now();
-                        return vec![Command::RefreshDeviceInfo(serial.clone())];
-                    }
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| ContentAction::from_str(s).ok());
+                if let Some(action) = action {
+                    return self.handle_action(action);
                 }
             }
             Msg::DeviceSelected(device) => {
                 let new_serial = device.as_ref().map(|d| d.serial.clone());
                 if new_serial != self.selected_serial {
-                    self.selected_serial = new_serial.clone();
+                    self.selected_serial = new_serial;
                     self.device_info = None;
-                    if let Some(serial) = new_serial {
-                        self.last_refresh = Instant::now();
-                        return vec![Command::RefreshDeviceInfo(serial)];
-                    }
+                    self.level_input = None;
                 }
             }
-            Msg::DeviceInfoUpdated(info) => {
-                if self.selected_serial.as_ref() == Some(&info.serial) {
-                    self.device_info = Some(info.clone());
-                }
+            Msg::DeviceInfoUpdated {
+                serial,
+                result: Ok(info),
+            } if self.selected_serial.as_ref() == Some(serial) => {
+                self.device_info = Some(info.as_ref().clone());
             }
             _ => {}
         }
@@ -108,11 +237,15 @@ impl Component for ContentPane {
 
         let rows: Vec<(&str, String)> = vec![
             ("Serial", info.serial.clone()),
-            ("Android", format!("{} (API {})", info.android_version, info.api_level)),
+            (
+                "Android",
+                format!("{} (API {})", info.android_version, info.api_level),
+            ),
             ("State", info.state.clone()),
             ("Connection", info.connection_type.clone()),
             ("ABI", info.abi.clone()),
             ("Locale", info.locale.clone()),
+            ("Timezone", info.timezone.clone()),
         ];
 
         for (label, value) in &rows {
@@ -145,40 +278,235 @@ impl Component for ContentPane {
 
         // Battery bar
         if let Some(ref battery) = info.battery {
-            lines.push(render_bar_line("Battery", battery.level as f64, 100.0, &battery.status, Color::Green));
+            let mut bar = render_bar_line(
+                "Battery",
+                battery.level as f64,
+                100.0,
+                &battery.status,
+                Color::Green,
+            );
+            if battery.simulated {
+                bar.push_span(Span::styled(
+                    "  SIMULATED ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            lines.push(bar);
+            lines.extend(battery_detail_lines(battery));
         }
 
         // Storage bar
         if let Some(ref storage) = info.storage {
             let pct_label = format!("{:.1}/{:.1} GB", storage.used_gb, storage.total_gb);
-            lines.push(render_bar_line("Storage", storage.used_gb, storage.total_gb, &pct_label, Color::Yellow));
+            lines.push(render_bar_line(
+                "Storage",
+                storage.used_gb,
+                storage.total_gb,
+                &pct_label,
+                Color::Yellow,
+            ));
         }
 
         // RAM bar
         if let Some(ref ram) = info.ram {
             let pct_label = format!("{:.1}/{:.1} GB", ram.used_gb, ram.total_gb);
-            lines.push(render_bar_line("RAM", ram.used_gb, ram.total_gb, &pct_label, Color::Magenta));
+            lines.push(render_bar_line(
+                "RAM",
+                ram.used_gb,
+                ram.total_gb,
+                &pct_label,
+                Color::Magenta,
+            ));
+        }
+
+        // CPU
+        let history = ctx.metrics.get(&info.serial);
+        let cpu = cpu_lines(info, history.and_then(|h| h.cpu_load()));
+        if !cpu.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(cpu);
         }
 
+        let rows = Layout::vertical([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Min(0),
+            Constraint::Length(if self.level_input.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
         let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, inner);
+        frame.render_widget(paragraph, rows[0]);
+        if let Some(history) = history {
+            self.draw_history(frame, rows[1], history);
+        }
+        if let Some(ref input) = self.level_input {
+            frame.render_widget(
+                Paragraph::new(input.line(" Simulated battery level (0-100): ", true)),
+                rows[2],
+            );
+        }
     }
 
     fn id(&self) -> &'static str {
         "Content"
     }
+
+    fn captures_input(&self) -> bool {
+        self.level_input.is_some()
+    }
 }
 
-fn render_bar_line<'a>(
-    label: &str,
-    used: f64,
-    total: f64,
-    suffix: &str,
-    color: Color,
-) -> Line<'a> {
+/// Total and per-core load with frequencies, then the hottest thermal zones.
+/// Capped cores and hot zones are highlighted.
+fn cpu_lines<'a>(info: &DeviceInfo, load: Option<&CpuLoad>) -> Vec<Line<'a>> {
+    let label_style = Style::default().fg(Color::DarkGray);
+    let warning_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+
+    let throttled = info.cpu_freqs.iter().filter(|f| f.throttled()).count();
+    let total = load.and_then(|l| l.total);
+    if total.is_some() || !info.cpu_freqs.is_empty() {
+        let suffix = match total {
+            Some(total) => format!("{total:.0}%"),
+            None => "measuring...".to_string(),
+        };
+        let mut line = render_bar_line("CPU", total.unwrap_or(0.0), 100.0, &suffix, Color::Cyan);
+        if throttled > 0 {
+            line.push_span(Span::styled(
+                format!("  THROTTLED ({throttled}/{} cores)", info.cpu_freqs.len()),
+                warning_style,
+            ));
+        }
+        lines.push(line);
+    }
+
+    // Cores with a frequency reading, else whichever /proc/stat listed
+    let mut cores: Vec<u32> = info.cpu_freqs.iter().map(|f| f.core).collect();
+    if cores.is_empty() {
+        cores = load
+            .map(|l| l.cores.iter().map(|(c, _)| *c).collect())
+            .unwrap_or_default();
+    }
+    for core in cores {
+        let busy = load
+            .and_then(|l| l.cores.iter().find(|(c, _)| *c == core))
+            .map(|(_, b)| *b);
+        let freq = info.cpu_freqs.iter().find(|f| f.core == core);
+        let mut suffix = match busy {
+            Some(busy) => format!("{busy:>3.0}%"),
+            None => "  -".to_string(),
+        };
+        if let Some(freq) = freq {
+            let ghz = |khz: Option<u64>| {
+                khz.map_or("-".to_string(), |k| format!("{:.2}", k as f64 / 1e6))
+            };
+            suffix.push_str(&format!(
+                "  {}/{} GHz",
+                ghz(freq.cur_khz),
+                ghz(freq.max_khz)
+            ));
+        }
+        let mut line = render_bar_line(
+            &format!("cpu{core}"),
+            busy.unwrap_or(0.0),
+            100.0,
+            &suffix,
+            Color::Cyan,
+        );
+        if let Some(freq) = freq.filter(|f| f.throttled()) {
+            let limit = freq.limit_khz.unwrap_or_default() as f64 / 1e6;
+            line.push_span(Span::styled(
+                format!("  capped at {limit:.2} GHz"),
+                warning_style,
+            ));
+        }
+        lines.push(line);
+    }
+
+    if !info.thermal.is_empty() {
+        let mut spans = vec![Span::styled(format!(" {:>12}  ", "Thermal"), label_style)];
+        // Zones arrive hottest first
+        for (i, zone) in info.thermal.iter().take(4).enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" \u{b7} ", label_style));
+            }
+            let style = if zone.is_hot() {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            spans.push(Span::styled(
+                format!("{} {:.1}\u{b0}C", zone.kind, zone.temp_c),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn metric_color(metric: Metric) -> Color {
+    match metric {
+        Metric::Cpu => Color::Cyan,
+        Metric::Ram => Color::Magenta,
+        Metric::Battery => Color::Green,
+        Metric::Temperature => Color::Red,
+        Metric::Storage => Color::Yellow,
+    }
+}
+
+/// Health, temperature, voltage, technology and charge counter, plus a
+/// warning while the values are simulated.
+fn battery_detail_lines<'a>(battery: &BatteryInfo) -> Vec<Line<'a>> {
+    let label_style = Style::default().fg(Color::DarkGray);
+    let value_style = Style::default().fg(Color::White);
+    let mut details = Vec::new();
+    if let Some(ref health) = battery.health {
+        details.push(health.clone());
+    }
+    if let Some(temperature) = battery.temperature_c {
+        details.push(format!("{temperature:.1}\u{b0}C"));
+    }
+    if let Some(voltage) = battery.voltage_mv {
+        details.push(format!("{voltage} mV"));
+    }
+    if let Some(ref technology) = battery.technology {
+        details.push(technology.clone());
+    }
+    if let Some(counter) = battery.charge_counter_uah {
+        details.push(format!("{} mAh", counter / 1000));
+    }
+
+    let mut lines = Vec::new();
+    if !details.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:>12}  ", ""), label_style),
+            Span::styled(details.join(" \u{b7} "), value_style),
+        ]));
+    }
+    if battery.simulated {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:>12}  ", ""), label_style),
+            Span::styled(
+                "Battery state is simulated; R restores the real one",
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+    }
+    lines
+}
+
+fn render_bar_line<'a>(label: &str, used: f64, total: f64, suffix: &str, color: Color) -> Line<'a> {
     let label_style = Style::default().fg(Color::DarkGray);
     let bar_width = 20;
-    let ratio = if total > 0.0 { (used / total).clamp(0.0, 1.0) } else { 0.0 };
+    let ratio = if total > 0.0 {
+        (used / total).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
     let filled = (ratio * bar_width as f64).round() as usize;
     let empty = bar_width - filled;
 
@@ -189,9 +517,111 @@ fn render_bar_line<'a>(
         Span::styled(format!(" {:>12}  ", label), label_style),
         Span::styled(bar_filled, Style::default().fg(color)),
         Span::styled(bar_empty, Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!(" {}", suffix),
-            Style::default().fg(Color::White),
-        ),
+        Span::styled(format!(" {}", suffix), Style::default().fg(Color::White)),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::{
+        cpu::{parse_cpufreq, parse_thermal},
+        device_info::parse_battery,
+    };
+
+    fn make_pane() -> ContentPane {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('l'), KeyModifiers::empty())],
+            "SetBatteryLevel".into(),
+        );
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('u'), KeyModifiers::empty())],
+            "ToggleUsb".into(),
+        );
+        let mut pane = ContentPane::new(keymap);
+        pane.selected_serial = Some("DEV0".into());
+        pane.device_info = Some(DeviceInfo {
+            serial: "DEV0".into(),
+            model: "Pixel".into(),
+            android_version: "14".into(),
+            api_level: "34".into(),
+            state: "device".into(),
+            connection_type: "USB".into(),
+            abi: "arm64-v8a".into(),
+            locale: "en-US".into(),
+            timezone: "Europe/Berlin".into(),
+            battery: parse_battery("  USB powered: true\n  status: 2\n  level: 80\n"),
+            storage: None,
+            ram: None,
+            screen: None,
+            wifi: None,
+            cpu: None,
+            cpu_freqs: Vec::new(),
+            thermal: Vec::new(),
+        });
+        pane
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn charger_toggle_flips_the_reported_state() {
+        let mut pane = make_pane();
+        let cmds = pane.update(&key(KeyCode::Char('u')));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SimulateBattery {
+                change: BatteryChange::Usb(false),
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn capped_cores_and_hot_zones_are_flagged() {
+        let mut info = make_pane().device_info.unwrap();
+        info.cpu_freqs = parse_cpufreq(
+            "/sys/devices/system/cpu/cpu4/cpufreq/scaling_max_freq:1497600\n\
+             /sys/devices/system/cpu/cpu4/cpufreq/cpuinfo_max_freq:2419200\n",
+        );
+        info.thermal = parse_thermal("/sys/class/thermal/thermal_zone1/temp:81000\n");
+        let load = CpuLoad {
+            total: Some(40.0),
+            cores: vec![(4, 90.0)],
+        };
+        let text: Vec<String> = cpu_lines(&info, Some(&load))
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text.len(), 3);
+        assert!(text[0].contains("THROTTLED (1/1 cores)"));
+        assert!(text[1].contains(" 90%") && text[1].contains("capped at 1.50 GHz"));
+        assert!(text[2].contains("zone1 81.0"));
+    }
+
+    #[test]
+    fn level_prompt_rejects_values_over_100() {
+        let mut pane = make_pane();
+        pane.update(&key(KeyCode::Char('l')));
+        assert!(pane.captures_input());
+        pane.update(&key(KeyCode::Char('5')));
+        assert!(pane.update(&key(KeyCode::Enter)).is_empty());
+        assert!(pane.captures_input());
+
+        pane.update(&key(KeyCode::Backspace));
+        pane.update(&key(KeyCode::Backspace));
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SimulateBattery {
+                change: BatteryChange::Level(8),
+                ..
+            }]
+        ));
+    }
+}
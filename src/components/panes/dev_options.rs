@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::dev_options::DEV_OPTIONS,
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum DevOptionsAction {
+    Up,
+    Down,
+    Toggle,
+    Default,
+    Refresh,
+}
+
+impl FromStr for DevOptionsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Toggle" => Ok(Self::Toggle),
+            "Default" => Ok(Self::Default),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct DevOptionsPane {
+    serial: Option<String>,
+    state: Option<Result<BTreeMap<String, String>, String>>,
+    selected_index: usize,
+    keymap: SectionKeymap,
+}
+
+impl DevOptionsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            state: None,
+            selected_index: 0,
+            keymap,
+        }
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshDevOptions(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_action(&mut self, action: DevOptionsAction) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        let option = DEV_OPTIONS[self.selected_index];
+        match action {
+            DevOptionsAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            DevOptionsAction::Down => {
+                self.selected_index = (self.selected_index + 1).min(DEV_OPTIONS.len() - 1);
+            }
+            DevOptionsAction::Toggle => {
+                if let Some(Ok(ref state)) = self.state {
+                    let value = option.next_value(option.current(state));
+                    return vec![Command::SetDevOption {
+                        serial,
+                        option,
+                        value,
+                    }];
+                }
+            }
+            DevOptionsAction::Default => {
+                return vec![Command::SetDevOption {
+                    serial,
+                    option,
+                    value: option.default,
+                }];
+            }
+            DevOptionsAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn draw_options(&self, frame: &mut Frame, area: Rect, state: &BTreeMap<String, String>) {
+        let rows: Vec<Row> = DEV_OPTIONS
+            .iter()
+            .map(|option| {
+                let value = option.current(state);
+                let label = option.describe(value);
+                let color = if option.is_switch() {
+                    if value == option.values[1].0 {
+                        Color::Green
+                    } else {
+                        Color::DarkGray
+                    }
+                } else if value == option.default {
+                    Color::White
+                } else {
+                    Color::Yellow
+                };
+                let mark = match (option.is_switch(), value == option.values[1].0) {
+                    (true, true) => "[x]",
+                    (true, false) => "[ ]",
+                    (false, _) => "   ",
+                };
+                Row::new([
+                    Span::raw(mark),
+                    Span::raw(option.label),
+                    Span::styled(label.to_string(), Style::default().fg(color)),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(28),
+                Constraint::Min(0),
+            ],
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut table_state = TableState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+}
+
+impl Component for DevOptionsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| DevOptionsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.state = None;
+                self.refresh_command()
+            }
+            Msg::DevOptionsUpdated { serial, result } => {
+                if self.serial.as_ref() == Some(serial) {
+                    self.state = Some(result.clone());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::DevOptions;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" DEVELOPER OPTIONS ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let state = match (&self.serial, &self.state) {
+            (None, _) => {
+                frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+                return;
+            }
+            (Some(_), None) => {
+                frame.render_widget(Paragraph::new("Loading developer options..."), inner);
+                return;
+            }
+            (Some(_), Some(Err(e))) => {
+                let paragraph = Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red));
+                frame.render_widget(paragraph, inner);
+                return;
+            }
+            (Some(_), Some(Ok(state))) => state,
+        };
+
+        self.draw_options(frame, inner, state);
+    }
+
+    fn id(&self) -> &'static str {
+        "DevOptions"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::{
+        dev_options::parse_state,
+        device::{ConnectionType, Device, DeviceState},
+    };
+
+    fn make_pane() -> DevOptionsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |c: char, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind('j', "Down");
+        bind(' ', "Toggle");
+        bind('d', "Default");
+        let mut pane = DevOptionsPane::new(keymap);
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane.update(&Msg::DevOptionsUpdated {
+            serial: "DEV0".into(),
+            result: Ok(parse_state("show_touches=1\n")),
+        });
+        pane
+    }
+
+    fn press(pane: &mut DevOptionsPane, c: char) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char(c),
+            KeyModifiers::empty(),
+        )))
+    }
+
+    #[test]
+    fn toggle_writes_the_opposite_of_the_current_value() {
+        let mut pane = make_pane();
+        press(&mut pane, 'j');
+        let cmds = press(&mut pane, ' ');
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SetDevOption { option, value: "0", .. }] if option.label == "Show touches"
+        ));
+    }
+
+    #[test]
+    fn default_writes_the_option_default() {
+        let mut pane = make_pane();
+        while DEV_OPTIONS[pane.selected_index].store.key() != "window_animation_scale" {
+            press(&mut pane, 'j');
+        }
+        assert!(matches!(
+            press(&mut pane, 'd').as_slice(),
+            [Command::SetDevOption { value: "1", .. }]
+        ));
+    }
+}
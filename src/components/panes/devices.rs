@@ -11,7 +11,7 @@ use ratatui::{
 
 use crate::adb::device::{ConnectionType, Device, DeviceState};
 use crate::command::Command;
-use crate::components::{Component, DrawContext, panes::Pane};
+use crate::components::{Component, DrawContext, list_item_at, panes::Pane};
 use crate::config::keymap::SectionKeymap;
 use crate::msg::Msg;
 
@@ -24,6 +24,26 @@ enum DeviceAction {
     Disconnect,
     Refresh,
     OpenEmulators,
+    OpenApps,
+    OpenProcesses,
+    OpenShell,
+    OpenProperties,
+    OpenSettings,
+    Screenshot,
+    ToggleRecording,
+    OpenRemote,
+    OpenIntents,
+    OpenPorts,
+    OpenNetwork,
+    OpenDisplay,
+    OpenDevOptions,
+    OpenDumpsys,
+    OpenBugreports,
+    OpenTasks,
+    OpenNotifications,
+    OpenHierarchy,
+    OpenLocale,
+    OpenEmulatorControls,
 }
 
 impl FromStr for DeviceAction {
@@ -36,6 +56,26 @@ impl FromStr for DeviceAction {
             "Disconnect" => Ok(Self::Disconnect),
             "Refresh" => Ok(Self::Refresh),
             "OpenEmulators" => Ok(Self::OpenEmulators),
+            "OpenApps" => Ok(Self::OpenApps),
+            "OpenProcesses" => Ok(Self::OpenProcesses),
+            "OpenShell" => Ok(Self::OpenShell),
+            "OpenProperties" => Ok(Self::OpenProperties),
+            "OpenSettings" => Ok(Self::OpenSettings),
+            "Screenshot" => Ok(Self::Screenshot),
+            "ToggleRecording" => Ok(Self::ToggleRecording),
+            "OpenRemote" => Ok(Self::OpenRemote),
+            "OpenIntents" => Ok(Self::OpenIntents),
+            "OpenPorts" => Ok(Self::OpenPorts),
+            "OpenNetwork" => Ok(Self::OpenNetwork),
+            "OpenDisplay" => Ok(Self::OpenDisplay),
+            "OpenDevOptions" => Ok(Self::OpenDevOptions),
+            "OpenDumpsys" => Ok(Self::OpenDumpsys),
+            "OpenBugreports" => Ok(Self::OpenBugreports),
+            "OpenTasks" => Ok(Self::OpenTasks),
+            "OpenNotifications" => Ok(Self::OpenNotifications),
+            "OpenHierarchy" => Ok(Self::OpenHierarchy),
+            "OpenLocale" => Ok(Self::OpenLocale),
+            "OpenEmulatorControls" => Ok(Self::OpenEmulatorControls),
             _ => Err(()),
         }
     }
@@ -130,8 +170,77 @@ impl DevicesPane {
             DeviceAction::OpenEmulators => {
                 return vec![Command::OpenEmulatorsModal];
             }
+            DeviceAction::OpenApps => {
+                return vec![Command::ShowPane(Pane::Apps)];
+            }
+            DeviceAction::OpenProcesses => {
+                return vec![Command::ShowPane(Pane::Processes)];
+            }
+            DeviceAction::OpenShell => {
+                return vec![Command::ShowPane(Pane::Shell)];
+            }
+            DeviceAction::OpenProperties => {
+                return vec![Command::ShowPane(Pane::Properties)];
+            }
+            DeviceAction::OpenSettings => {
+                return vec![Command::ShowPane(Pane::Settings)];
+            }
+            DeviceAction::Screenshot => {
+                if let Some(device) = self.selected_device() {
+                    return vec![Command::TakeScreenshot(device.serial.clone())];
+                }
+            }
+            DeviceAction::OpenPorts => {
+                return vec![Command::ShowPane(Pane::Ports)];
+            }
+            DeviceAction::OpenNetwork => {
+                return vec![Command::ShowPane(Pane::Network)];
+            }
+            DeviceAction::OpenDisplay => {
+                return vec![Command::ShowPane(Pane::Display)];
+            }
+            DeviceAction::OpenDevOptions => {
+                return vec![Command::ShowPane(Pane::DevOptions)];
+            }
+            DeviceAction::OpenDumpsys => {
+                return vec![Command::ShowPane(Pane::Dumpsys)];
+            }
+            DeviceAction::OpenBugreports => {
+                return vec![
+                    Command::RefreshBugreports,
+                    Command::ShowPane(Pane::Bugreports),
+                ];
+            }
+            DeviceAction::OpenTasks => {
+                return vec![Command::ShowPane(Pane::Tasks)];
+            }
+            DeviceAction::OpenNotifications => {
+                return vec![Command::ShowPane(Pane::Notifications)];
+            }
+            DeviceAction::OpenHierarchy => {
+                return vec![Command::ShowPane(Pane::Hierarchy)];
+            }
+            DeviceAction::OpenLocale => {
+                if let Some(device) = self.selected_device() {
+                    return vec![Command::OpenLocaleModal(device.serial.clone())];
+                }
+            }
+            DeviceAction::OpenEmulatorControls => {
+                return vec![Command::ShowPane(Pane::EmulatorControls)];
+            }
+            DeviceAction::OpenIntents => {
+                return vec![Command::ShowPane(Pane::Intents)];
+            }
+            DeviceAction::OpenRemote => {
+                return vec![Command::ShowPane(Pane::Remote)];
+            }
+            DeviceAction::ToggleRecording => {
+                if let Some(device) = self.selected_device() {
+                    return vec![Command::ToggleRecording(device.serial.clone())];
+                }
+            }
         }
-        return Vec::new();
+        Vec::new()
     }
 }
 
@@ -150,6 +259,20 @@ impl Component for DevicesPane {
                     None => Vec::new(),
                 }
             }
+            Msg::Click { area, row, double } => {
+                let Some(index) =
+                    list_item_at(*area, *row, self.selected_index, self.devices.len())
+                else {
+                    return Vec::new();
+                };
+                self.selected_index = index;
+                let mut commands: Vec<Command> =
+                    self.selection_changed_command().into_iter().collect();
+                if *double {
+                    commands.push(Command::ShowPane(Pane::Content));
+                }
+                commands
+            }
             Msg::Tick => {
                 if self.last_refresh.elapsed() >= REFRESH_INTERVAL {
                     self.last_refresh = Instant::now();
@@ -481,6 +604,29 @@ mod tests {
         assert!(!cmds.is_empty());
     }
 
+    #[test]
+    fn click_selects_and_double_click_shows_device_info() {
+        let mut pane = pane_with_devices(3);
+        let area = Rect::new(0, 1, 30, 10);
+        let click = |row, double| Msg::Click { area, row, double };
+
+        let cmds = pane.update(&click(4, false));
+        assert_eq!(pane.selected_index, 2);
+        assert!(
+            matches!(cmds.as_slice(), [Command::DeviceSelected(Some(d))] if d.serial == "DEV2")
+        );
+
+        // Below the last device
+        assert!(pane.update(&click(6, false)).is_empty());
+        assert_eq!(pane.selected_index, 2);
+
+        let cmds = pane.update(&click(4, true));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ShowPane(Pane::Content)]
+        ));
+    }
+
     #[test]
     fn devices_accessor_returns_slice() {
         let pane = pane_with_devices(3);
@@ -0,0 +1,570 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::display::{
+        DisplayChange, DisplayPreset, DisplayState, parse_density, parse_font_scale, parse_size,
+    },
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum DisplayAction {
+    Up,
+    Down,
+    SetSize,
+    ResetSize,
+    SetDensity,
+    ResetDensity,
+    SetFontScale,
+    ToggleNightMode,
+    ToggleRotationLock,
+    Rotate,
+    ApplyPreset,
+    SavePreset,
+    DeletePreset,
+    Refresh,
+}
+
+impl FromStr for DisplayAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "SetSize" => Ok(Self::SetSize),
+            "ResetSize" => Ok(Self::ResetSize),
+            "SetDensity" => Ok(Self::SetDensity),
+            "ResetDensity" => Ok(Self::ResetDensity),
+            "SetFontScale" => Ok(Self::SetFontScale),
+            "ToggleNightMode" => Ok(Self::ToggleNightMode),
+            "ToggleRotationLock" => Ok(Self::ToggleRotationLock),
+            "Rotate" => Ok(Self::Rotate),
+            "ApplyPreset" => Ok(Self::ApplyPreset),
+            "SavePreset" => Ok(Self::SavePreset),
+            "DeletePreset" => Ok(Self::DeletePreset),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+enum Prompt {
+    Size(TextInput),
+    Density(TextInput),
+    FontScale(TextInput),
+    PresetName(TextInput),
+    /// Waiting for y/n before deleting the named preset.
+    ConfirmDelete(String),
+}
+
+pub struct DisplayPane {
+    serial: Option<String>,
+    state: Option<Result<DisplayState, String>>,
+    /// Named presets, kept in step with the config through `SaveDisplayPresets`.
+    presets: BTreeMap<String, DisplayPreset>,
+    preset_index: usize,
+    prompt: Option<Prompt>,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl DisplayPane {
+    pub fn new(keymap: SectionKeymap, presets: BTreeMap<String, DisplayPreset>) -> Self {
+        Self {
+            serial: None,
+            state: None,
+            presets,
+            preset_index: 0,
+            prompt: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn current(&self) -> Option<&DisplayState> {
+        self.state.as_ref().and_then(|s| s.as_ref().ok())
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshDisplay(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn change(&self, changes: Vec<DisplayChange>) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::ChangeDisplay {
+                serial: serial.clone(),
+                changes,
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn save_presets(&self) -> Vec<Command> {
+        vec![Command::SaveDisplayPresets(self.presets.clone())]
+    }
+
+    fn handle_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(prompt)) = (msg, self.prompt.take()) else {
+            return Vec::new();
+        };
+        match prompt {
+            Prompt::Size(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit => match parse_size(input.value()) {
+                    Ok(size) => return self.submitted(vec![DisplayChange::Size(Some(size))]),
+                    Err(e) => self.reprompt(Prompt::Size(input), e),
+                },
+                InputOutcome::Cancel => self.notice = None,
+                _ => self.prompt = Some(Prompt::Size(input)),
+            },
+            Prompt::Density(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit => match parse_density(input.value()) {
+                    Ok(dpi) => return self.submitted(vec![DisplayChange::Density(Some(dpi))]),
+                    Err(e) => self.reprompt(Prompt::Density(input), e),
+                },
+                InputOutcome::Cancel => self.notice = None,
+                _ => self.prompt = Some(Prompt::Density(input)),
+            },
+            Prompt::FontScale(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit => match parse_font_scale(input.value()) {
+                    Ok(scale) => return self.submitted(vec![DisplayChange::FontScale(scale)]),
+                    Err(e) => self.reprompt(Prompt::FontScale(input), e),
+                },
+                InputOutcome::Cancel => self.notice = None,
+                _ => self.prompt = Some(Prompt::FontScale(input)),
+            },
+            Prompt::PresetName(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit if !input.value().trim().is_empty() => {
+                    let Some(state) = self.current() else {
+                        return Vec::new();
+                    };
+                    let name = input.value().trim().to_string();
+                    let preset = DisplayPreset::from_state(state);
+                    self.notice = Some(format!("Saved the current display as '{name}'"));
+                    self.presets.insert(name, preset);
+                    return self.save_presets();
+                }
+                InputOutcome::Cancel => {}
+                _ => self.prompt = Some(Prompt::PresetName(input)),
+            },
+            Prompt::ConfirmDelete(name) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.presets.remove(&name);
+                    self.preset_index = self.preset_index.min(self.presets.len().saturating_sub(1));
+                    return self.save_presets();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {}
+                _ => self.prompt = Some(Prompt::ConfirmDelete(name)),
+            },
+        }
+        Vec::new()
+    }
+
+    fn submitted(&mut self, changes: Vec<DisplayChange>) -> Vec<Command> {
+        self.notice = None;
+        self.change(changes)
+    }
+
+    fn reprompt(&mut self, prompt: Prompt, error: color_eyre::Report) {
+        self.notice = Some(error.to_string());
+        self.prompt = Some(prompt);
+    }
+
+    fn handle_action(&mut self, action: DisplayAction) -> Vec<Command> {
+        if self.serial.is_none() {
+            return Vec::new();
+        }
+        match action {
+            DisplayAction::Up => self.preset_index = self.preset_index.saturating_sub(1),
+            DisplayAction::Down => {
+                self.preset_index =
+                    (self.preset_index + 1).min(self.presets.len().saturating_sub(1));
+            }
+            DisplayAction::ResetSize => return self.change(vec![DisplayChange::Size(None)]),
+            DisplayAction::ResetDensity => return self.change(vec![DisplayChange::Density(None)]),
+            DisplayAction::ApplyPreset => {
+                if let Some((name, preset)) = self.presets.iter().nth(self.preset_index) {
+                    match preset.changes() {
+                        Ok(changes) => return self.change(changes),
+                        Err(e) => self.notice = Some(format!("{name}: {e}")),
+                    }
+                }
+            }
+            DisplayAction::DeletePreset => {
+                if let Some(name) = self.presets.keys().nth(self.preset_index) {
+                    self.prompt = Some(Prompt::ConfirmDelete(name.clone()));
+                }
+            }
+            DisplayAction::Refresh => return self.refresh_command(),
+            // The rest start from what the device shows now
+            _ => {
+                let Some(state) = self.current().cloned() else {
+                    return Vec::new();
+                };
+                match action {
+                    DisplayAction::SetSize => {
+                        let input = TextInput::with_value(state.size.effective());
+                        self.prompt = Some(Prompt::Size(input));
+                    }
+                    DisplayAction::SetDensity => {
+                        let input = TextInput::with_value(state.density.effective());
+                        self.prompt = Some(Prompt::Density(input));
+                    }
+                    DisplayAction::SetFontScale => {
+                        let input = TextInput::with_value(&state.font_scale.to_string());
+                        self.prompt = Some(Prompt::FontScale(input));
+                    }
+                    DisplayAction::ToggleNightMode => {
+                        let on = !state.night_mode.unwrap_or(false);
+                        return self.change(vec![DisplayChange::NightMode(on)]);
+                    }
+                    DisplayAction::ToggleRotationLock => {
+                        let locked = !state.rotation_locked;
+                        return self.change(vec![DisplayChange::RotationLock(locked)]);
+                    }
+                    DisplayAction::Rotate => {
+                        // The first press only locks, keeping the current orientation
+                        let turns = if state.rotation_locked {
+                            (state.user_rotation + 1) % 4
+                        } else {
+                            state.user_rotation
+                        };
+                        return self.change(vec![DisplayChange::Rotation(turns)]);
+                    }
+                    DisplayAction::SavePreset => {
+                        self.prompt = Some(Prompt::PresetName(TextInput::new()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    fn state_lines(state: &DisplayState) -> Vec<Line<'static>> {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let value_style = Style::default().fg(Color::White);
+        let override_style = Style::default().fg(Color::Yellow);
+        let row = |label: &str, value: String, overridden: bool| {
+            Line::from(vec![
+                Span::styled(format!(" {label:>12}  "), label_style),
+                Span::styled(
+                    value,
+                    if overridden {
+                        override_style
+                    } else {
+                        value_style
+                    },
+                ),
+            ])
+        };
+        let night = match state.night_mode {
+            Some(true) => "on",
+            Some(false) => "off",
+            None => "scheduled",
+        };
+        let rotation = if state.rotation_locked {
+            format!("locked at {}\u{b0}", state.user_rotation as u32 * 90)
+        } else {
+            "auto".to_string()
+        };
+        vec![
+            row(
+                "Resolution",
+                state.size.describe(str::to_string),
+                state.size.overridden.is_some(),
+            ),
+            row(
+                "Density",
+                state.density.describe(|d| format!("{d}dpi")),
+                state.density.overridden.is_some(),
+            ),
+            row(
+                "Font scale",
+                format!("{}", state.font_scale),
+                state.font_scale != 1.0,
+            ),
+            row("Dark mode", night.to_string(), false),
+            row("Rotation", rotation, false),
+        ]
+    }
+
+    fn prompt_line(&self) -> Option<Line<'static>> {
+        let line = match self.prompt.as_ref() {
+            Some(Prompt::Size(input)) => input.line(" wm size (WxH): ", true),
+            Some(Prompt::Density(input)) => input.line(" wm density (dpi): ", true),
+            Some(Prompt::FontScale(input)) => input.line(" Font scale: ", true),
+            Some(Prompt::PresetName(input)) => input.line(" Save display as preset: ", true),
+            Some(Prompt::ConfirmDelete(name)) => Line::from(vec![
+                Span::styled(
+                    format!(" Delete preset '{name}'?"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    "  y confirm, n cancel",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            None => Line::from(Span::styled(
+                format!(" {}", self.notice.as_ref()?),
+                Style::default().fg(Color::Yellow),
+            )),
+        };
+        Some(line)
+    }
+
+    fn draw_presets(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .title(" PRESETS ")
+            .border_style(Style::default().fg(Color::DarkGray));
+        if self.presets.is_empty() {
+            let paragraph = Paragraph::new(" (none; p saves the current display)")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+        let rows: Vec<Row> = self
+            .presets
+            .iter()
+            .map(|(name, preset)| {
+                let mut parts = Vec::new();
+                if let Some(ref size) = preset.size {
+                    parts.push(size.clone());
+                }
+                if let Some(dpi) = preset.density {
+                    parts.push(format!("{dpi}dpi"));
+                }
+                if let Some(scale) = preset.font_scale {
+                    parts.push(format!("font {scale}"));
+                }
+                if let Some(on) = preset.night_mode {
+                    parts.push(if on { "dark" } else { "light" }.to_string());
+                }
+                if let Some(turns) = preset.rotation {
+                    parts.push(format!("{}\u{b0}", turns as u32 * 90));
+                }
+                Row::new([name.clone(), parts.join(", ")])
+            })
+            .collect();
+        let table = Table::new(rows, [Constraint::Percentage(30), Constraint::Min(0)])
+            .block(block)
+            .row_highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut state = TableState::default().with_selected(Some(self.preset_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+impl Component for DisplayPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) && self.prompt.is_some() {
+            return self.handle_prompt_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| DisplayAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.state = None;
+                self.prompt = None;
+                self.notice = None;
+                self.refresh_command()
+            }
+            Msg::DisplayUpdated { serial, result } => {
+                if self.serial.as_ref() == Some(serial) {
+                    self.state = Some(result.clone());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Display;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" DISPLAY ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let state = match (&self.serial, &self.state) {
+            (None, _) => Paragraph::new("Select a device to begin"),
+            (Some(_), None) => Paragraph::new("Loading display..."),
+            (Some(_), Some(Err(e))) => {
+                Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+            }
+            (Some(_), Some(Ok(state))) => Paragraph::new(Self::state_lines(state)),
+        };
+        if self.serial.is_none() {
+            frame.render_widget(state, inner);
+            return;
+        }
+
+        let prompt = self.prompt_line();
+        let rows = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Min(3),
+            Constraint::Length(if prompt.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+
+        frame.render_widget(state, rows[0]);
+        self.draw_presets(frame, rows[1]);
+        if let Some(prompt) = prompt {
+            frame.render_widget(Paragraph::new(prompt), rows[2]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Display"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, Device, DeviceState};
+    use crate::adb::display::parse_display_state;
+
+    fn make_pane() -> DisplayPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |code: KeyCode, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(code, KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind(KeyCode::Char('s'), "SetSize");
+        bind(KeyCode::Char('o'), "Rotate");
+        bind(KeyCode::Char('p'), "SavePreset");
+        bind(KeyCode::Enter, "ApplyPreset");
+        let mut pane = DisplayPane::new(keymap, BTreeMap::new());
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane.update(&Msg::DisplayUpdated {
+            serial: "DEV0".into(),
+            result: Ok(parse_display_state(
+                "Physical size: 1080x2400\nPhysical density: 420\nNight mode: no\n\
+                 accelerometer_rotation: 1\nuser_rotation: 0\n",
+            )),
+        });
+        pane
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(pane: &mut DisplayPane, text: &str) {
+        for c in text.chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn size_prompt_validates_before_overriding() {
+        let mut pane = make_pane();
+        pane.update(&key(KeyCode::Char('s')));
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+        )));
+        type_text(&mut pane, "720");
+        assert!(pane.update(&key(KeyCode::Enter)).is_empty());
+        assert!(pane.captures_input());
+
+        type_text(&mut pane, "x1280");
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ChangeDisplay { changes, .. }]
+                if changes == &[DisplayChange::Size(Some("720x1280".into()))]
+        ));
+    }
+
+    #[test]
+    fn first_rotate_locks_the_current_orientation() {
+        let mut pane = make_pane();
+        let cmds = pane.update(&key(KeyCode::Char('o')));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ChangeDisplay { changes, .. }] if changes == &[DisplayChange::Rotation(0)]
+        ));
+    }
+
+    #[test]
+    fn current_display_is_saved_and_applied_as_a_preset() {
+        let mut pane = make_pane();
+        pane.update(&key(KeyCode::Char('p')));
+        type_text(&mut pane, "pixel");
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SaveDisplayPresets(presets)]
+                if presets["pixel"].size.as_deref() == Some("1080x2400")
+        ));
+
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ChangeDisplay { changes, .. }] if changes.len() == 4
+        ));
+    }
+}
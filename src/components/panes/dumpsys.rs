@@ -0,0 +1,448 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+        viewer::TextViewer,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+/// Lines moved by PageUp and PageDown in the viewer.
+const PAGE: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+enum DumpsysAction {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Open,
+    ToggleFold,
+    FoldAll,
+    UnfoldAll,
+    Save,
+    Refresh,
+}
+
+impl FromStr for DumpsysAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "PageUp" => Ok(Self::PageUp),
+            "PageDown" => Ok(Self::PageDown),
+            "Top" => Ok(Self::Top),
+            "Bottom" => Ok(Self::Bottom),
+            "Search" => Ok(Self::Search),
+            "NextMatch" => Ok(Self::NextMatch),
+            "PrevMatch" => Ok(Self::PrevMatch),
+            "Open" => Ok(Self::Open),
+            "ToggleFold" => Ok(Self::ToggleFold),
+            "FoldAll" => Ok(Self::FoldAll),
+            "UnfoldAll" => Ok(Self::UnfoldAll),
+            "Save" => Ok(Self::Save),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One service's output, loaded into a viewer once dumpsys returns.
+struct DumpView {
+    service: String,
+    output: Option<Result<TextViewer, String>>,
+}
+
+pub struct DumpsysPane {
+    serial: Option<String>,
+    services: Vec<String>,
+    error: Option<String>,
+    loaded: bool,
+    selected_index: usize,
+    /// Filters the service list, or searches the open viewer.
+    query: TextInput,
+    typing: bool,
+    viewer: Option<DumpView>,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl DumpsysPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            services: Vec::new(),
+            error: None,
+            loaded: false,
+            selected_index: 0,
+            query: TextInput::new(),
+            typing: false,
+            viewer: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn visible_services(&self) -> Vec<&String> {
+        let needle = self.query.value().to_lowercase();
+        self.services
+            .iter()
+            .filter(|s| needle.is_empty() || s.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    fn run(&self, service: String) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RunDumpsys {
+                serial: serial.clone(),
+                service,
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn search(&mut self, forward: bool) {
+        let Some(DumpView {
+            output: Some(Ok(ref mut viewer)),
+            ..
+        }) = self.viewer
+        else {
+            return;
+        };
+        let query = self.query.value();
+        if !query.is_empty() && !viewer.find(query, forward) {
+            self.notice = Some(format!("No match for '{query}'"));
+        }
+    }
+
+    fn handle_query_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.query.handle_key(key) {
+            InputOutcome::Submit => {
+                self.typing = false;
+                self.search(true);
+            }
+            InputOutcome::Cancel => {
+                self.typing = false;
+                self.query.clear();
+            }
+            InputOutcome::Changed if self.viewer.is_none() => self.selected_index = 0,
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_viewer_action(&mut self, action: DumpsysAction) -> Vec<Command> {
+        let Some(ref mut view) = self.viewer else {
+            return Vec::new();
+        };
+        match action {
+            DumpsysAction::Search => {
+                self.query.clear();
+                self.typing = true;
+                return Vec::new();
+            }
+            DumpsysAction::NextMatch => {
+                self.search(true);
+                return Vec::new();
+            }
+            DumpsysAction::PrevMatch => {
+                self.search(false);
+                return Vec::new();
+            }
+            DumpsysAction::Refresh | DumpsysAction::Open => {
+                let service = view.service.clone();
+                view.output = None;
+                return self.run(service);
+            }
+            _ => {}
+        }
+        let Some(Ok(ref mut viewer)) = view.output else {
+            return Vec::new();
+        };
+        match action {
+            DumpsysAction::Up => viewer.move_cursor(-1),
+            DumpsysAction::Down => viewer.move_cursor(1),
+            DumpsysAction::PageUp => viewer.move_cursor(-(PAGE as isize)),
+            DumpsysAction::PageDown => viewer.move_cursor(PAGE as isize),
+            DumpsysAction::Top => viewer.move_cursor(isize::MIN),
+            DumpsysAction::Bottom => viewer.move_cursor(isize::MAX),
+            DumpsysAction::ToggleFold => viewer.toggle_fold(),
+            DumpsysAction::FoldAll => viewer.fold_all(),
+            DumpsysAction::UnfoldAll => viewer.unfold_all(),
+            DumpsysAction::Save => {
+                if let Some(ref serial) = self.serial {
+                    return vec![Command::SaveDumpsys {
+                        serial: serial.clone(),
+                        service: view.service.clone(),
+                        text: viewer.text(),
+                    }];
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_list_action(&mut self, action: DumpsysAction) -> Vec<Command> {
+        let len = self.visible_services().len();
+        match action {
+            DumpsysAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            DumpsysAction::Down => {
+                self.selected_index = (self.selected_index + 1).min(len.saturating_sub(1));
+            }
+            DumpsysAction::PageUp => self.selected_index = self.selected_index.saturating_sub(PAGE),
+            DumpsysAction::PageDown => {
+                self.selected_index = (self.selected_index + PAGE).min(len.saturating_sub(1));
+            }
+            DumpsysAction::Top => self.selected_index = 0,
+            DumpsysAction::Bottom => self.selected_index = len.saturating_sub(1),
+            DumpsysAction::Search => self.typing = true,
+            DumpsysAction::Open => {
+                if let Some(service) = self.visible_services().get(self.selected_index) {
+                    let service = service.to_string();
+                    self.query.clear();
+                    self.viewer = Some(DumpView {
+                        service: service.clone(),
+                        output: None,
+                    });
+                    return self.run(service);
+                }
+            }
+            DumpsysAction::Refresh => {
+                if let Some(ref serial) = self.serial {
+                    return vec![Command::RefreshDumpsysServices(serial.clone())];
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn draw_services(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(area);
+        frame.render_widget(Paragraph::new(self.query.line(" / ", self.typing)), rows[0]);
+
+        let services = self.visible_services();
+        if services.is_empty() {
+            let paragraph = match (&self.error, self.loaded) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, false) => Paragraph::new("Loading services..."),
+                (None, true) => Paragraph::new("(no services)"),
+            };
+            frame.render_widget(paragraph, rows[1]);
+            return;
+        }
+        let items: Vec<ListItem> = services
+            .iter()
+            .map(|s| ListItem::new(Line::raw(format!(" {s}"))))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(list, rows[1], &mut list_state);
+    }
+
+    fn draw_viewer(&self, frame: &mut Frame, area: Rect, view: &DumpView) {
+        let footer = if self.typing {
+            Some(self.query.line(" Search: ", true))
+        } else {
+            self.notice.as_ref().map(|notice| {
+                Line::from(Span::styled(
+                    format!(" {notice}"),
+                    Style::default().fg(Color::Yellow),
+                ))
+            })
+        };
+        let rows = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(if footer.is_some() { 1 } else { 0 }),
+        ])
+        .split(area);
+        if let Some(footer) = footer {
+            frame.render_widget(Paragraph::new(footer), rows[1]);
+        }
+
+        match view.output {
+            None => {
+                let text = format!("Running dumpsys {}...", view.service);
+                frame.render_widget(Paragraph::new(text), rows[0]);
+            }
+            Some(Err(ref e)) => {
+                let paragraph = Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red));
+                frame.render_widget(paragraph, rows[0]);
+            }
+            Some(Ok(ref viewer)) => viewer.draw(frame, rows[0], self.query.value()),
+        }
+    }
+}
+
+impl Component for DumpsysPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.typing && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_query_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| DumpsysAction::from_str(s).ok());
+                match action {
+                    Some(action) if self.viewer.is_some() => self.handle_viewer_action(action),
+                    Some(action) => self.handle_list_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial.clone();
+                self.services.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.viewer = None;
+                self.typing = false;
+                self.query.clear();
+                match new_serial {
+                    Some(serial) => vec![Command::RefreshDumpsysServices(serial)],
+                    None => Vec::new(),
+                }
+            }
+            Msg::DumpsysServices { serial, result } if self.serial.as_ref() == Some(serial) => {
+                self.loaded = true;
+                match result {
+                    Ok(services) => {
+                        self.services = services.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                let len = self.visible_services().len();
+                self.selected_index = self.selected_index.min(len.saturating_sub(1));
+                Vec::new()
+            }
+            Msg::DumpsysOutput {
+                serial,
+                service,
+                result,
+            } if self.serial.as_ref() == Some(serial) => {
+                if let Some(ref mut view) = self.viewer
+                    && view.service == *service
+                {
+                    view.output = Some(
+                        result
+                            .as_ref()
+                            .map(|text| TextViewer::new(text))
+                            .map_err(Clone::clone),
+                    );
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Dumpsys;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = match self.viewer {
+            Some(ref view) => format!(" DUMPSYS {} ", view.service),
+            None => " DUMPSYS ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+            return;
+        }
+        match self.viewer {
+            Some(ref view) => self.draw_viewer(frame, inner, view),
+            None => self.draw_services(frame, inner),
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Dumpsys"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.typing
+    }
+
+    fn back(&mut self) -> bool {
+        self.query.clear();
+        self.viewer.take().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn opening_a_service_runs_dumpsys_and_esc_returns_to_the_list() {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())],
+            "Open".into(),
+        );
+        let mut pane = DumpsysPane::new(keymap);
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::DumpsysServices {
+            serial: "DEV0".into(),
+            result: Ok(vec!["activity".into(), "window".into()]),
+        });
+        let cmds = pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::empty(),
+        )));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::RunDumpsys { service, .. }] if service == "activity"
+        ));
+        assert!(pane.back());
+        assert!(!pane.back());
+    }
+}
@@ -0,0 +1,554 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::{
+        console::{
+            ConsoleCommand, NETWORK_DELAYS, NETWORK_SPEEDS, POWER_STATUSES, parse_geo_fix,
+            parse_phone_number,
+        },
+        device::ConnectionType,
+    },
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum EmulatorControlsAction {
+    Up,
+    Down,
+    Run,
+    CancelCall,
+}
+
+impl FromStr for EmulatorControlsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Run" => Ok(Self::Run),
+            "CancelCall" => Ok(Self::CancelCall),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Control {
+    Location,
+    Sms,
+    Call,
+    Battery,
+    Charging,
+    NetworkSpeed,
+    NetworkDelay,
+    Fingerprint,
+}
+
+const CONTROLS: [Control; 8] = [
+    Control::Location,
+    Control::Sms,
+    Control::Call,
+    Control::Battery,
+    Control::Charging,
+    Control::NetworkSpeed,
+    Control::NetworkDelay,
+    Control::Fingerprint,
+];
+
+impl Control {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Location => "Location",
+            Self::Sms => "SMS",
+            Self::Call => "Phone call",
+            Self::Battery => "Battery level",
+            Self::Charging => "Charging",
+            Self::NetworkSpeed => "Network speed",
+            Self::NetworkDelay => "Network delay",
+            Self::Fingerprint => "Fingerprint",
+        }
+    }
+
+    /// What Enter does on the row.
+    fn hint(self) -> &'static str {
+        match self {
+            Self::Location => "geo fix longitude latitude",
+            Self::Sms => "sms send from a number",
+            Self::Call => "gsm call from a number; x hangs up",
+            Self::Battery => "power capacity 0-100",
+            Self::Charging => "cycle power status",
+            Self::NetworkSpeed => "cycle network speed",
+            Self::NetworkDelay => "cycle network delay",
+            Self::Fingerprint => "finger touch with an enrolled id",
+        }
+    }
+
+    /// The values Enter cycles through, for rows without a prompt.
+    fn choices(self) -> Option<&'static [&'static str]> {
+        match self {
+            Self::Charging => Some(&POWER_STATUSES),
+            Self::NetworkSpeed => Some(&NETWORK_SPEEDS),
+            Self::NetworkDelay => Some(&NETWORK_DELAYS),
+            _ => None,
+        }
+    }
+
+    fn prompt_label(self) -> &'static str {
+        match self {
+            Self::Location => " Longitude latitude: ",
+            Self::Sms => " From number, then the message: ",
+            Self::Call => " Incoming call from: ",
+            Self::Battery => " Battery level (%): ",
+            _ => " Finger id: ",
+        }
+    }
+}
+
+pub struct EmulatorControlsPane {
+    serial: Option<String>,
+    /// Whether the selected device is an emulator, which has a console.
+    emulator: bool,
+    selected_index: usize,
+    /// What each control last set on the emulator, in `CONTROLS` order.
+    values: [Option<String>; CONTROLS.len()],
+    /// Number of the simulated call still ringing or in progress.
+    active_call: Option<String>,
+    prompt: Option<(Control, TextInput)>,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl EmulatorControlsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            emulator: false,
+            selected_index: 0,
+            values: Default::default(),
+            active_call: None,
+            prompt: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn value(&self, control: Control) -> Option<&str> {
+        self.values[control as usize].as_deref()
+    }
+
+    fn send(&self, command: ConsoleCommand) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) if self.emulator => vec![Command::EmulatorConsole {
+                serial: serial.clone(),
+                command,
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    fn open_prompt(&mut self, control: Control) {
+        let value = match control {
+            Control::Location => self.value(control).unwrap_or_default(),
+            Control::Call => self.active_call.as_deref().unwrap_or_default(),
+            Control::Battery => self
+                .value(control)
+                .unwrap_or_default()
+                .trim_end_matches('%'),
+            Control::Fingerprint => "1",
+            _ => "",
+        };
+        self.prompt = Some((control, TextInput::with_value(value)));
+    }
+
+    fn parse_prompt(control: Control, value: &str) -> Result<ConsoleCommand, String> {
+        let value = value.trim();
+        match control {
+            Control::Location => parse_geo_fix(value).map_err(|e| e.to_string()),
+            Control::Sms => {
+                let (from, text) = value
+                    .split_once(' ')
+                    .filter(|(_, text)| !text.trim().is_empty())
+                    .ok_or("Enter the sender's number, a space, then the message")?;
+                Ok(ConsoleCommand::Sms {
+                    from: parse_phone_number(from).map_err(|e| e.to_string())?,
+                    text: text.trim().to_string(),
+                })
+            }
+            Control::Call => parse_phone_number(value)
+                .map(ConsoleCommand::Call)
+                .map_err(|e| e.to_string()),
+            Control::Battery => match value.trim_end_matches('%').parse::<u8>() {
+                Ok(percent) if percent <= 100 => Ok(ConsoleCommand::PowerCapacity(percent)),
+                _ => Err(format!("'{value}' is not a level from 0 to 100")),
+            },
+            _ => match value.parse::<u32>() {
+                Ok(id) if id > 0 => Ok(ConsoleCommand::FingerTouch(id)),
+                _ => Err(format!("'{value}' is not a finger id")),
+            },
+        }
+    }
+
+    fn handle_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some((control, mut input))) = (msg, self.prompt.take()) else {
+            return Vec::new();
+        };
+        match input.handle_key(key) {
+            InputOutcome::Submit => match Self::parse_prompt(control, input.value()) {
+                Ok(command) => {
+                    self.notice = None;
+                    return self.send(command);
+                }
+                Err(e) => {
+                    self.notice = Some(e);
+                    self.prompt = Some((control, input));
+                }
+            },
+            InputOutcome::Cancel => self.notice = None,
+            _ => self.prompt = Some((control, input)),
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: EmulatorControlsAction) -> Vec<Command> {
+        if !self.emulator {
+            return Vec::new();
+        }
+        let control = CONTROLS[self.selected_index];
+        match action {
+            EmulatorControlsAction::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            EmulatorControlsAction::Down => {
+                self.selected_index = (self.selected_index + 1).min(CONTROLS.len() - 1);
+            }
+            EmulatorControlsAction::Run => {
+                let Some(choices) = control.choices() else {
+                    self.open_prompt(control);
+                    return Vec::new();
+                };
+                let next = match self.value(control) {
+                    Some(current) => choices
+                        .iter()
+                        .position(|c| *c == current)
+                        .map_or(0, |i| (i + 1) % choices.len()),
+                    None => 0,
+                };
+                let command = match control {
+                    Control::Charging => ConsoleCommand::PowerStatus(choices[next]),
+                    Control::NetworkSpeed => ConsoleCommand::NetworkSpeed(choices[next]),
+                    _ => ConsoleCommand::NetworkDelay(choices[next]),
+                };
+                return self.send(command);
+            }
+            EmulatorControlsAction::CancelCall => match self.active_call.clone() {
+                Some(number) => return self.send(ConsoleCommand::CancelCall(number)),
+                None => self.notice = Some("No simulated call to hang up".into()),
+            },
+        }
+        Vec::new()
+    }
+
+    /// Shows what a command the console accepted changed.
+    fn record(&mut self, command: &ConsoleCommand) {
+        let (control, value) = match command {
+            ConsoleCommand::GeoFix {
+                longitude,
+                latitude,
+            } => (Control::Location, format!("{longitude} {latitude}")),
+            ConsoleCommand::Sms { from, text } => (Control::Sms, format!("{from}: {text}")),
+            ConsoleCommand::Call(number) => {
+                self.active_call = Some(number.clone());
+                (Control::Call, format!("{number} calling"))
+            }
+            ConsoleCommand::CancelCall(number) => {
+                self.active_call = None;
+                (Control::Call, format!("{number} hung up"))
+            }
+            ConsoleCommand::PowerCapacity(percent) => (Control::Battery, format!("{percent}%")),
+            ConsoleCommand::PowerStatus(status) => (Control::Charging, status.to_string()),
+            ConsoleCommand::NetworkSpeed(speed) => (Control::NetworkSpeed, speed.to_string()),
+            ConsoleCommand::NetworkDelay(delay) => (Control::NetworkDelay, delay.to_string()),
+            ConsoleCommand::FingerTouch(id) => (Control::Fingerprint, format!("finger {id}")),
+        };
+        self.values[control as usize] = Some(value);
+    }
+
+    fn footer(&self) -> Option<Line<'static>> {
+        let notice = self
+            .notice
+            .as_ref()
+            .map(|n| Span::styled(format!("  {n}"), Style::default().fg(Color::Yellow)));
+        let Some((control, ref input)) = self.prompt else {
+            return notice.map(Line::from);
+        };
+        let mut line = input.line(control.prompt_label(), true);
+        line.spans.extend(notice);
+        Some(line)
+    }
+
+    fn draw_controls(&self, frame: &mut Frame, area: Rect) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let rows: Vec<Row> = CONTROLS
+            .iter()
+            .map(|control| {
+                let value = match self.value(*control) {
+                    Some(value) => {
+                        Span::styled(value.to_string(), Style::default().fg(Color::Cyan))
+                    }
+                    None => Span::styled("-", dim),
+                };
+                Row::new(vec![
+                    Span::raw(control.label()),
+                    value,
+                    Span::styled(control.hint(), dim),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Percentage(40),
+                Constraint::Min(0),
+            ],
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+impl Component for EmulatorControlsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) && self.prompt.is_some() {
+            return self.handle_prompt_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| EmulatorControlsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.emulator = device
+                    .as_ref()
+                    .is_some_and(|d| d.connection_type == ConnectionType::Emulator);
+                self.values = Default::default();
+                self.active_call = None;
+                self.prompt = None;
+                self.notice = None;
+                Vec::new()
+            }
+            Msg::EmulatorConsoleReplied {
+                serial,
+                command,
+                result,
+            } if self.serial.as_ref() == Some(serial) => {
+                match result {
+                    Ok(output) => {
+                        self.record(command);
+                        self.notice = (!output.is_empty()).then(|| output.clone());
+                    }
+                    Err(e) => self.notice = Some(format!("{command}: {e}")),
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::EmulatorControls;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" EMULATOR CONTROLS ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        match self.serial {
+            None => {
+                frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+                return;
+            }
+            Some(ref serial) if !self.emulator => {
+                let text =
+                    format!("{serial} is not an emulator; these controls use the emulator console");
+                frame.render_widget(
+                    Paragraph::new(text).style(Style::default().fg(Color::DarkGray)),
+                    inner,
+                );
+                return;
+            }
+            Some(_) => {}
+        }
+
+        let footer = self.footer();
+        let rows = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(if footer.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        self.draw_controls(frame, rows[0]);
+        if let Some(footer) = footer {
+            frame.render_widget(Paragraph::new(footer), rows[1]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "EmulatorControls"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{Device, DeviceState};
+
+    fn make_pane(connection_type: ConnectionType) -> EmulatorControlsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |code: KeyCode, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(code, KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind(KeyCode::Char('j'), "Down");
+        bind(KeyCode::Enter, "Run");
+        bind(KeyCode::Char('x'), "CancelCall");
+        let mut pane = EmulatorControlsPane::new(keymap);
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "emulator-5554".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type,
+        })));
+        pane
+    }
+
+    fn press(pane: &mut EmulatorControlsPane, code: KeyCode) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty())))
+    }
+
+    fn type_text(pane: &mut EmulatorControlsPane, text: &str) {
+        for c in text.chars() {
+            press(pane, KeyCode::Char(c));
+        }
+    }
+
+    fn reply(pane: &mut EmulatorControlsPane, commands: &[Command]) {
+        let [Command::EmulatorConsole { serial, command }] = commands else {
+            panic!("expected one console command");
+        };
+        pane.update(&Msg::EmulatorConsoleReplied {
+            serial: serial.clone(),
+            command: command.clone(),
+            result: Ok(String::new()),
+        });
+    }
+
+    #[test]
+    fn prompts_validate_before_sending() {
+        let mut pane = make_pane(ConnectionType::Emulator);
+        press(&mut pane, KeyCode::Enter);
+        type_text(&mut pane, "-122.08");
+        assert!(press(&mut pane, KeyCode::Enter).is_empty());
+        assert!(pane.captures_input());
+        assert!(pane.notice.is_some());
+
+        type_text(&mut pane, " 37.42");
+        let cmds = press(&mut pane, KeyCode::Enter);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::EmulatorConsole { command: ConsoleCommand::GeoFix { longitude, .. }, .. }]
+                if *longitude == -122.08
+        ));
+        assert!(!pane.captures_input());
+    }
+
+    #[test]
+    fn choices_cycle_and_calls_hang_up() {
+        let mut pane = make_pane(ConnectionType::Emulator);
+        press(&mut pane, KeyCode::Char('j'));
+        press(&mut pane, KeyCode::Char('j'));
+        press(&mut pane, KeyCode::Enter);
+        type_text(&mut pane, "+1 555-0100");
+        let cmds = press(&mut pane, KeyCode::Enter);
+        reply(&mut pane, &cmds);
+        assert!(matches!(
+            press(&mut pane, KeyCode::Char('x')).as_slice(),
+            [Command::EmulatorConsole { command: ConsoleCommand::CancelCall(number), .. }]
+                if number == "+15550100"
+        ));
+
+        press(&mut pane, KeyCode::Char('j'));
+        press(&mut pane, KeyCode::Char('j'));
+        let cmds = press(&mut pane, KeyCode::Enter);
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::EmulatorConsole {
+                command: ConsoleCommand::PowerStatus("charging"),
+                ..
+            }]
+        ));
+        reply(&mut pane, &cmds);
+        assert!(matches!(
+            press(&mut pane, KeyCode::Enter).as_slice(),
+            [Command::EmulatorConsole {
+                command: ConsoleCommand::PowerStatus("discharging"),
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn physical_devices_get_no_console_commands() {
+        let mut pane = make_pane(ConnectionType::Usb);
+        assert!(press(&mut pane, KeyCode::Enter).is_empty());
+        assert!(!pane.captures_input());
+    }
+}
@@ -0,0 +1,464 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::{
+    adb::{
+        input::InputEvent,
+        ui_hierarchy::{UiNode, selector},
+    },
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+const PAGE: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+enum HierarchyAction {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Tap,
+    CopySelector,
+    Refresh,
+}
+
+impl FromStr for HierarchyAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "PageUp" => Ok(Self::PageUp),
+            "PageDown" => Ok(Self::PageDown),
+            "Top" => Ok(Self::Top),
+            "Bottom" => Ok(Self::Bottom),
+            "Search" => Ok(Self::Search),
+            "NextMatch" => Ok(Self::NextMatch),
+            "PrevMatch" => Ok(Self::PrevMatch),
+            "Tap" => Ok(Self::Tap),
+            "CopySelector" => Ok(Self::CopySelector),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct HierarchyPane {
+    serial: Option<String>,
+    nodes: Vec<UiNode>,
+    error: Option<String>,
+    /// Whether a dump is running; they take a few seconds.
+    loading: bool,
+    selected_index: usize,
+    query: TextInput,
+    typing: bool,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl HierarchyPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            nodes: Vec::new(),
+            error: None,
+            loading: false,
+            selected_index: 0,
+            query: TextInput::new(),
+            typing: false,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn refresh_command(&mut self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => {
+                self.loading = true;
+                vec![Command::RefreshUiHierarchy(serial.clone())]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the next node matching the query, wrapping around; a new
+    /// query can match the selected node itself.
+    fn search(&mut self, forward: bool, from_current: bool) {
+        let needle = self.query.value().to_lowercase();
+        let len = self.nodes.len();
+        if needle.is_empty() || len == 0 {
+            return;
+        }
+        let first = usize::from(!from_current);
+        let found = (first..first + len)
+            .map(|step| {
+                if forward {
+                    (self.selected_index + step) % len
+                } else {
+                    (self.selected_index + len - step % len) % len
+                }
+            })
+            .find(|&i| self.nodes[i].matches(&needle));
+        match found {
+            Some(i) => self.selected_index = i,
+            None => self.notice = Some(format!("No match for '{}'", self.query.value())),
+        }
+    }
+
+    fn handle_query_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.query.handle_key(key) {
+            InputOutcome::Submit => {
+                self.typing = false;
+                self.search(true, true);
+            }
+            InputOutcome::Cancel => {
+                self.typing = false;
+                self.query.clear();
+            }
+            InputOutcome::Changed | InputOutcome::Ignored => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: HierarchyAction) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        let last = self.nodes.len().saturating_sub(1);
+        match action {
+            HierarchyAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            HierarchyAction::Down => self.selected_index = (self.selected_index + 1).min(last),
+            HierarchyAction::PageUp => {
+                self.selected_index = self.selected_index.saturating_sub(PAGE)
+            }
+            HierarchyAction::PageDown => {
+                self.selected_index = (self.selected_index + PAGE).min(last)
+            }
+            HierarchyAction::Top => self.selected_index = 0,
+            HierarchyAction::Bottom => self.selected_index = last,
+            HierarchyAction::Search => {
+                self.query.clear();
+                self.typing = true;
+            }
+            HierarchyAction::NextMatch => self.search(true, false),
+            HierarchyAction::PrevMatch => self.search(false, false),
+            HierarchyAction::Tap => {
+                let Some(node) = self.nodes.get(self.selected_index) else {
+                    return Vec::new();
+                };
+                let Some(bounds) = node.bounds.filter(|b| !b.is_empty()) else {
+                    self.notice = Some(format!("{} has no area to tap", node.short_class()));
+                    return Vec::new();
+                };
+                let (x, y) = bounds.center();
+                self.notice = Some(format!("Tapped {x},{y}; r to dump again"));
+                return vec![Command::SendInput {
+                    serial,
+                    event: InputEvent::Tap(x, y),
+                }];
+            }
+            HierarchyAction::CopySelector => {
+                if self.selected_index < self.nodes.len() {
+                    return vec![Command::CopyToClipboard(selector(
+                        &self.nodes,
+                        self.selected_index,
+                    ))];
+                }
+            }
+            HierarchyAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn node_line(&self, node: &UiNode, needle: &str) -> Line<'_> {
+        let class_style = if node.clickable {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![
+            Span::raw(" ".repeat(1 + node.depth * 2)),
+            Span::styled(node.short_class().to_string(), class_style),
+        ];
+        if let Some(id) = node.short_id() {
+            spans.push(Span::styled(
+                format!(" #{id}"),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        if let Some(ref text) = node.text {
+            spans.push(Span::raw(format!(" {text:?}")));
+        }
+        if let Some(ref desc) = node.content_desc {
+            spans.push(Span::styled(
+                format!(" [{desc}]"),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        let line = Line::from(spans);
+        if !needle.is_empty() && node.matches(needle) {
+            line.style(Style::default().fg(Color::Yellow))
+        } else {
+            line
+        }
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect, index: usize) {
+        let node = &self.nodes[index];
+        let label =
+            |name: &str| Span::styled(format!(" {name:<9}"), Style::default().fg(Color::DarkGray));
+        let value = |v: &Option<String>| Span::raw(v.clone().unwrap_or_default());
+        let bounds = node
+            .bounds
+            .map(|b| {
+                let (x, y) = b.center();
+                format!(
+                    "[{},{}][{},{}]  center {x},{y}",
+                    b.left, b.top, b.right, b.bottom
+                )
+            })
+            .unwrap_or_default();
+        let lines = vec![
+            Line::from(vec![label("class"), Span::raw(node.class.clone())]),
+            Line::from(vec![label("id"), value(&node.resource_id)]),
+            Line::from(vec![label("text"), value(&node.text)]),
+            Line::from(vec![label("desc"), value(&node.content_desc)]),
+            Line::from(vec![label("package"), value(&node.package)]),
+            Line::from(vec![label("bounds"), Span::raw(bounds)]),
+            Line::from(vec![
+                label("selector"),
+                Span::styled(
+                    selector(&self.nodes, index),
+                    Style::default().fg(Color::Cyan),
+                ),
+            ]),
+        ];
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn footer(&self) -> Option<Line<'_>> {
+        if self.typing {
+            return Some(self.query.line(" Search: ", true));
+        }
+        self.notice.as_ref().map(|notice| {
+            Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(Color::Yellow),
+            ))
+        })
+    }
+}
+
+impl Component for HierarchyPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.typing && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_query_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| HierarchyAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.nodes.clear();
+                self.error = None;
+                self.selected_index = 0;
+                self.refresh_command()
+            }
+            Msg::UiHierarchyUpdated { serial, result } if self.serial.as_ref() == Some(serial) => {
+                self.loading = false;
+                match result {
+                    Ok(nodes) => {
+                        self.nodes = nodes.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.selected_index = self.selected_index.min(self.nodes.len().saturating_sub(1));
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Hierarchy;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = if self.loading {
+            " UI HIERARCHY (dumping...) ".to_string()
+        } else {
+            format!(" UI HIERARCHY ({} nodes) ", self.nodes.len())
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+            return;
+        }
+        let footer = self.footer();
+        let rows = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(8),
+            Constraint::Length(if footer.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        if let Some(footer) = footer {
+            frame.render_widget(Paragraph::new(footer), rows[2]);
+        }
+
+        if self.nodes.is_empty() {
+            let paragraph = match (&self.error, self.loading) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, true) => Paragraph::new("Dumping view hierarchy..."),
+                (None, false) => Paragraph::new("(no nodes)"),
+            };
+            frame.render_widget(paragraph, rows[0]);
+            return;
+        }
+        let needle = self.query.value().to_lowercase();
+        let items: Vec<ListItem> = self
+            .nodes
+            .iter()
+            .map(|node| ListItem::new(self.node_line(node, &needle)))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(list, rows[0], &mut list_state);
+        self.draw_details(frame, rows[1], self.selected_index);
+    }
+
+    fn id(&self) -> &'static str {
+        "Hierarchy"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.typing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::ui_hierarchy::parse_hierarchy;
+
+    const DUMP: &str = r#"<hierarchy rotation="0"><node class="android.widget.FrameLayout" bounds="[0,0][1080,2400]"><node text="Email" resource-id="com.example:id/email" class="android.widget.EditText" bounds="[0,100][1080,200]" /><node text="Next" class="android.widget.Button" clickable="true" bounds="[40,300][240,400]" /><node class="android.view.View" bounds="[0,0][0,0]" /></node></hierarchy>"#;
+
+    fn make_pane() -> HierarchyPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |c: char, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind('/', "Search");
+        bind('n', "NextMatch");
+        bind('t', "Tap");
+        bind('y', "CopySelector");
+        let mut pane = HierarchyPane::new(keymap);
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::UiHierarchyUpdated {
+            serial: "DEV0".into(),
+            result: Ok(parse_hierarchy(DUMP).unwrap()),
+        });
+        pane
+    }
+
+    fn press(pane: &mut HierarchyPane, code: KeyCode) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty())))
+    }
+
+    #[test]
+    fn search_then_tap_the_center_of_the_match() {
+        let mut pane = make_pane();
+        press(&mut pane, KeyCode::Char('/'));
+        for c in "next".chars() {
+            press(&mut pane, KeyCode::Char(c));
+        }
+        press(&mut pane, KeyCode::Enter);
+        assert_eq!(pane.selected_index, 2);
+        assert!(matches!(
+            press(&mut pane, KeyCode::Char('t')).as_slice(),
+            [Command::SendInput {
+                event: InputEvent::Tap(140, 350),
+                ..
+            }]
+        ));
+        assert!(matches!(
+            press(&mut pane, KeyCode::Char('y')).as_slice(),
+            [Command::CopyToClipboard(s)] if s == r#"new UiSelector().text("Next")"#
+        ));
+    }
+
+    #[test]
+    fn nodes_without_area_are_not_tapped() {
+        let mut pane = make_pane();
+        pane.selected_index = 3;
+        assert!(press(&mut pane, KeyCode::Char('t')).is_empty());
+        assert!(pane.notice.is_some());
+        // A search without matches keeps the selection
+        press(&mut pane, KeyCode::Char('/'));
+        press(&mut pane, KeyCode::Char('q'));
+        press(&mut pane, KeyCode::Enter);
+        assert_eq!(pane.selected_index, 3);
+    }
+}
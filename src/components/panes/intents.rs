@@ -0,0 +1,612 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::intent::{AmResult, Extra, Intent, IntentHistory},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+const LABEL_WIDTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+enum IntentAction {
+    Up,
+    Down,
+    Edit,
+    CycleType,
+    AddExtra,
+    Delete,
+    Launch,
+    History,
+    Clear,
+}
+
+impl FromStr for IntentAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Edit" => Ok(Self::Edit),
+            "CycleType" => Ok(Self::CycleType),
+            "AddExtra" => Ok(Self::AddExtra),
+            "Delete" => Ok(Self::Delete),
+            "Launch" => Ok(Self::Launch),
+            "History" => Ok(Self::History),
+            "Clear" => Ok(Self::Clear),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A row of the form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Kind,
+    Action,
+    Data,
+    Component,
+    Categories,
+    Flags,
+    Extra(usize),
+}
+
+impl Field {
+    const FIXED: [Field; 6] = [
+        Self::Kind,
+        Self::Action,
+        Self::Data,
+        Self::Component,
+        Self::Categories,
+        Self::Flags,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Kind => "Kind",
+            Self::Action => "Action",
+            Self::Data => "Data URI",
+            Self::Component => "Component",
+            Self::Categories => "Categories",
+            Self::Flags => "Flags",
+            Self::Extra(_) => "Extra",
+        }
+    }
+}
+
+pub struct IntentsPane {
+    serial: Option<String>,
+    intent: Intent,
+    selected_index: usize,
+    /// Text being typed into the selected row.
+    editing: Option<TextInput>,
+    showing_history: bool,
+    history_index: usize,
+    history: IntentHistory,
+    launching: bool,
+    result: Option<Result<AmResult, String>>,
+    keymap: SectionKeymap,
+}
+
+impl IntentsPane {
+    pub fn new(keymap: SectionKeymap, history: IntentHistory) -> Self {
+        Self {
+            serial: None,
+            intent: Intent::default(),
+            selected_index: 0,
+            editing: None,
+            showing_history: false,
+            history_index: 0,
+            history,
+            launching: false,
+            result: None,
+            keymap,
+        }
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let extras = (0..self.intent.extras.len()).map(Field::Extra);
+        Field::FIXED.into_iter().chain(extras).collect()
+    }
+
+    fn selected_field(&self) -> Field {
+        self.fields()
+            .get(self.selected_index)
+            .copied()
+            .unwrap_or(Field::Kind)
+    }
+
+    /// The field as text, the way it is edited.
+    fn field_text(&self, field: Field) -> String {
+        match field {
+            Field::Kind => self.intent.kind.to_string(),
+            Field::Action => self.intent.action.clone(),
+            Field::Data => self.intent.data.clone(),
+            Field::Component => self.intent.component.clone(),
+            Field::Categories => self.intent.categories.join(","),
+            Field::Flags => self.intent.flags.clone(),
+            Field::Extra(i) => self
+                .intent
+                .extras
+                .get(i)
+                .map(|e| format!("{}={}", e.key, e.value))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn set_field(&mut self, field: Field, text: &str) {
+        let text = text.trim();
+        match field {
+            Field::Kind => {}
+            Field::Action => self.intent.action = text.to_string(),
+            Field::Data => self.intent.data = text.to_string(),
+            Field::Component => self.intent.component = text.to_string(),
+            Field::Categories => {
+                self.intent.categories = text
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            Field::Flags => self.intent.flags = text.to_string(),
+            Field::Extra(i) => {
+                if let Some(extra) = self.intent.extras.get_mut(i) {
+                    let (key, value) = text.split_once('=').unwrap_or((text, ""));
+                    extra.key = key.trim().to_string();
+                    extra.value = value.trim().to_string();
+                }
+            }
+        }
+    }
+
+    fn launch(&mut self) -> Vec<Command> {
+        let Some(ref serial) = self.serial else {
+            return Vec::new();
+        };
+        if self.intent.is_empty() || self.launching {
+            return Vec::new();
+        }
+        self.launching = true;
+        self.result = None;
+        vec![Command::LaunchIntent {
+            serial: serial.clone(),
+            intent: self.intent.clone(),
+        }]
+    }
+
+    fn handle_edit_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(mut input)) = (msg, self.editing.take()) else {
+            return Vec::new();
+        };
+        match input.handle_key(key) {
+            InputOutcome::Submit => self.set_field(self.selected_field(), input.value()),
+            InputOutcome::Cancel => {}
+            _ => self.editing = Some(input),
+        }
+        Vec::new()
+    }
+
+    fn handle_history_action(&mut self, action: IntentAction) -> Vec<Command> {
+        let len = self.history.entries().len();
+        match action {
+            IntentAction::Up => self.history_index = self.history_index.saturating_sub(1),
+            IntentAction::Down => {
+                if len > 0 {
+                    self.history_index = (self.history_index + 1).min(len - 1);
+                }
+            }
+            // Re-fires the entry; it also lands in the form for tweaking
+            IntentAction::Edit | IntentAction::Launch => {
+                let entry = self.history.entries().get(self.history_index).cloned();
+                if let Some((_, _, intent)) = entry {
+                    self.intent = intent.clone();
+                    self.selected_index = 0;
+                    self.showing_history = false;
+                    return self.launch();
+                }
+            }
+            IntentAction::Delete => {
+                let entry = self.history.entries().get(self.history_index).cloned();
+                if let Some((target, index, _)) = entry {
+                    let target = target.to_string();
+                    self.history.remove(&target, index);
+                    self.history_index = self.history_index.min(len.saturating_sub(2));
+                    return vec![Command::SaveIntentHistory(self.history.clone())];
+                }
+            }
+            IntentAction::History => self.showing_history = false,
+            IntentAction::CycleType | IntentAction::AddExtra | IntentAction::Clear => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: IntentAction) -> Vec<Command> {
+        if self.showing_history {
+            return self.handle_history_action(action);
+        }
+        match action {
+            IntentAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            IntentAction::Down => {
+                self.selected_index = (self.selected_index + 1).min(self.fields().len() - 1);
+            }
+            IntentAction::Edit => match self.selected_field() {
+                Field::Kind => self.intent.kind = self.intent.kind.next(),
+                field => self.editing = Some(TextInput::with_value(&self.field_text(field))),
+            },
+            IntentAction::CycleType => match self.selected_field() {
+                Field::Kind => self.intent.kind = self.intent.kind.next(),
+                Field::Extra(i) => {
+                    let extra = &mut self.intent.extras[i];
+                    extra.kind = extra.kind.next();
+                }
+                _ => {}
+            },
+            IntentAction::AddExtra => {
+                self.intent.extras.push(Extra::default());
+                self.selected_index = self.fields().len() - 1;
+                self.editing = Some(TextInput::new());
+            }
+            IntentAction::Delete => match self.selected_field() {
+                Field::Extra(i) => {
+                    self.intent.extras.remove(i);
+                    self.selected_index = self.selected_index.min(self.fields().len() - 1);
+                }
+                field => self.set_field(field, ""),
+            },
+            IntentAction::Launch => return self.launch(),
+            IntentAction::History => {
+                self.showing_history = true;
+                self.history_index = 0;
+            }
+            IntentAction::Clear => {
+                self.intent = Intent::default();
+                self.selected_index = 0;
+                self.result = None;
+            }
+        }
+        Vec::new()
+    }
+
+    fn form_lines(&self) -> Vec<Line<'static>> {
+        let selected_style = Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+        self.fields()
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let label = match field {
+                    Field::Extra(e) => format!(
+                        " {:<w$}",
+                        self.intent.extras[e].kind.flag(),
+                        w = LABEL_WIDTH
+                    ),
+                    _ => format!(" {:<w$}", field.label(), w = LABEL_WIDTH),
+                };
+                let selected = i == self.selected_index;
+                if let (true, Some(input)) = (selected, &self.editing) {
+                    return input.line(&label, true);
+                }
+                let value = self.field_text(field);
+                let line = Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::DarkGray)),
+                    Span::raw(value),
+                ]);
+                if selected {
+                    line.style(selected_style)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn result_lines(&self) -> Vec<Line<'static>> {
+        if self.launching {
+            return vec![Line::from(Span::styled(
+                " Launching...",
+                Style::default().fg(Color::Yellow),
+            ))];
+        }
+        match &self.result {
+            None => Vec::new(),
+            Some(Err(e)) => vec![Line::from(Span::styled(
+                format!(" {e}"),
+                Style::default().fg(Color::Red),
+            ))],
+            Some(Ok(result)) => {
+                let mut lines: Vec<Line> = result
+                    .fields
+                    .iter()
+                    .map(|(key, value)| {
+                        let color = match (key.as_str(), value.as_str()) {
+                            ("Status", "ok") => Color::Green,
+                            ("Status", _) => Color::Yellow,
+                            _ => Color::White,
+                        };
+                        Line::from(vec![
+                            Span::styled(
+                                format!(" {key:<w$}", w = LABEL_WIDTH),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                            Span::styled(value.clone(), Style::default().fg(color)),
+                        ])
+                    })
+                    .collect();
+                lines.extend(result.lines.iter().map(|l| {
+                    Line::from(Span::styled(
+                        format!(" {l}"),
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                }));
+                lines
+            }
+        }
+    }
+
+    fn draw_history(&self, frame: &mut Frame, area: Rect) {
+        let entries = self.history.entries();
+        if entries.is_empty() {
+            frame.render_widget(Paragraph::new(" No launched intents yet"), area);
+            return;
+        }
+        let rows: Vec<Row> = entries
+            .iter()
+            .map(|(target, _, intent)| {
+                Row::new([
+                    target.to_string(),
+                    intent.kind.am_command().to_string(),
+                    intent.summary(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Length(14),
+                Constraint::Min(0),
+            ],
+        )
+        .header(Row::new(["TARGET", "AM", "INTENT"]).style(Style::default().fg(Color::DarkGray)))
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(Some(self.history_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+impl Component for IntentsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) && self.editing.is_some() {
+            return self.handle_edit_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| IntentAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial != self.serial {
+                    self.serial = new_serial;
+                    self.launching = false;
+                    self.result = None;
+                }
+                Vec::new()
+            }
+            Msg::IntentLaunched {
+                serial,
+                intent,
+                result,
+            } => {
+                if self.serial.as_ref() != Some(serial) {
+                    return Vec::new();
+                }
+                self.launching = false;
+                self.result = Some(result.clone());
+                if result.is_ok() {
+                    self.history.record(intent);
+                    return vec![Command::SaveIntentHistory(self.history.clone())];
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Intents;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = if self.showing_history {
+            " INTENTS - HISTORY "
+        } else {
+            " INTENTS "
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            let msg = Paragraph::new("Select a device to begin")
+                .style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(msg, inner);
+            return;
+        }
+        if self.showing_history {
+            self.draw_history(frame, inner);
+            return;
+        }
+
+        let form = self.form_lines();
+        let rows = Layout::vertical([
+            Constraint::Length(form.len() as u16 + 1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+        frame.render_widget(Paragraph::new(form), rows[0]);
+        frame.render_widget(
+            Paragraph::new(self.result_lines()).block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .title(" RESULT ")
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+            rows[1],
+        );
+    }
+
+    fn id(&self) -> &'static str {
+        "Intents"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    fn back(&mut self) -> bool {
+        std::mem::take(&mut self.showing_history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, Device, DeviceState};
+    use crate::adb::intent::{ExtraType, IntentKind};
+
+    fn make_pane() -> IntentsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |code: KeyCode, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(code, KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind(KeyCode::Char('j'), "Down");
+        bind(KeyCode::Enter, "Edit");
+        bind(KeyCode::Char('t'), "CycleType");
+        bind(KeyCode::Char('a'), "AddExtra");
+        bind(KeyCode::Char('l'), "Launch");
+        bind(KeyCode::Char('h'), "History");
+        let mut pane = IntentsPane::new(keymap, IntentHistory::default());
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(pane: &mut IntentsPane, text: &str) {
+        for c in text.chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn form_builds_intent_with_typed_extras() {
+        let mut pane = make_pane();
+        pane.update(&key(KeyCode::Enter));
+        assert_eq!(pane.intent.kind, IntentKind::Service);
+
+        pane.update(&key(KeyCode::Char('j')));
+        pane.update(&key(KeyCode::Enter));
+        type_text(&mut pane, "com.example.SYNC");
+        pane.update(&key(KeyCode::Enter));
+        assert_eq!(pane.intent.action, "com.example.SYNC");
+
+        pane.update(&key(KeyCode::Char('a')));
+        type_text(&mut pane, "count=3");
+        pane.update(&key(KeyCode::Enter));
+        pane.update(&key(KeyCode::Char('t')));
+        assert_eq!(
+            pane.intent.extras,
+            [Extra {
+                kind: ExtraType::Int,
+                key: "count".into(),
+                value: "3".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn successful_launch_is_saved_and_refired_from_history() {
+        let mut pane = make_pane();
+        pane.intent.data = "myapp://orders/42".into();
+        let cmds = pane.update(&key(KeyCode::Char('l')));
+        assert!(matches!(cmds.as_slice(), [Command::LaunchIntent { .. }]));
+
+        let cmds = pane.update(&Msg::IntentLaunched {
+            serial: "DEV0".into(),
+            intent: pane.intent.clone(),
+            result: Ok(AmResult::default()),
+        });
+        assert!(matches!(cmds.as_slice(), [Command::SaveIntentHistory(_)]));
+
+        pane.update(&key(KeyCode::Char('h')));
+        pane.intent = Intent::default();
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::LaunchIntent { intent, .. }] if intent.data == "myapp://orders/42"
+        ));
+        assert!(!pane.showing_history);
+    }
+
+    #[test]
+    fn failed_launch_is_not_saved() {
+        let mut pane = make_pane();
+        pane.intent.action = "bogus".into();
+        pane.update(&key(KeyCode::Char('l')));
+        let cmds = pane.update(&Msg::IntentLaunched {
+            serial: "DEV0".into(),
+            intent: pane.intent.clone(),
+            result: Err("unable to resolve Intent".into()),
+        });
+        assert!(cmds.is_empty());
+        assert!(pane.history.entries().is_empty());
+    }
+}
@@ -1,21 +1,78 @@
+pub mod apps;
+pub mod bugreports;
 pub mod content;
+pub mod dev_options;
 pub mod devices;
+pub mod display;
+pub mod dumpsys;
+pub mod emulator_controls;
+pub mod hierarchy;
+pub mod intents;
+pub mod network;
+pub mod notifications;
+pub mod permissions;
+pub mod ports;
+pub mod processes;
+pub mod properties;
+pub mod remote;
+pub mod screenshot;
+pub mod settings;
+pub mod shell;
+pub mod tasks;
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum Pane {
     #[default]
-    DeviceList = 0,
-    Content = 1,
+    DeviceList,
+    Content,
+    Apps,
+    Permissions,
+    Processes,
+    Properties,
+    Settings,
+    Screenshot,
+    Remote,
+    Intents,
+    Ports,
+    Network,
+    Display,
+    DevOptions,
+    Dumpsys,
+    Bugreports,
+    Tasks,
+    Notifications,
+    Hierarchy,
+    EmulatorControls,
+    Shell,
 }
 
-const PANE_COUNT: u8 = 2;
+const PANE_COUNT: u8 = 21;
 
 impl Pane {
     fn from_index(i: u8) -> Self {
         match i {
             0 => Pane::DeviceList,
             1 => Pane::Content,
+            2 => Pane::Apps,
+            3 => Pane::Permissions,
+            4 => Pane::Processes,
+            5 => Pane::Properties,
+            6 => Pane::Settings,
+            7 => Pane::Screenshot,
+            8 => Pane::Remote,
+            9 => Pane::Intents,
+            10 => Pane::Ports,
+            11 => Pane::Network,
+            12 => Pane::Display,
+            13 => Pane::DevOptions,
+            14 => Pane::Dumpsys,
+            15 => Pane::Bugreports,
+            16 => Pane::Tasks,
+            17 => Pane::Notifications,
+            18 => Pane::Hierarchy,
+            19 => Pane::EmulatorControls,
+            20 => Pane::Shell,
             _ => unreachable!(),
         }
     }
@@ -28,3 +85,18 @@ impl Pane {
         Self::from_index((self as u8 + PANE_COUNT - 1) % PANE_COUNT)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_and_prev_visit_every_pane() {
+        for i in 0..PANE_COUNT {
+            assert_eq!(Pane::from_index(i) as u8, i);
+        }
+        assert_eq!(Pane::DeviceList.prev(), Pane::Shell);
+        assert_eq!(Pane::Shell.next(), Pane::DeviceList);
+        assert_eq!(Pane::Apps.next().prev(), Pane::Apps);
+    }
+}
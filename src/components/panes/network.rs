@@ -0,0 +1,312 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    adb::network::NetworkInfo,
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum NetworkAction {
+    Up,
+    Down,
+    ToggleProxy,
+    Refresh,
+}
+
+impl FromStr for NetworkAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "ToggleProxy" => Ok(Self::ToggleProxy),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct NetworkPane {
+    serial: Option<String>,
+    info: Option<Result<NetworkInfo, String>>,
+    scroll: u16,
+    /// Host port the proxy toggle reverses, from `proxy_port` in the config.
+    proxy_port: u16,
+    keymap: SectionKeymap,
+}
+
+impl NetworkPane {
+    pub fn new(keymap: SectionKeymap, proxy_port: u16) -> Self {
+        Self {
+            serial: None,
+            info: None,
+            scroll: 0,
+            proxy_port,
+            keymap,
+        }
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshNetwork(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_action(&mut self, action: NetworkAction) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        match action {
+            NetworkAction::Up => self.scroll = self.scroll.saturating_sub(1),
+            NetworkAction::Down => self.scroll = self.scroll.saturating_add(1),
+            NetworkAction::ToggleProxy => {
+                if let Some(Ok(ref info)) = self.info {
+                    return vec![Command::SetHostProxy {
+                        serial,
+                        port: self.proxy_port,
+                        enable: !info.proxies_to_host(self.proxy_port),
+                    }];
+                }
+            }
+            NetworkAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn info_lines(&self, info: &NetworkInfo) -> Vec<Line<'static>> {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let value_style = Style::default().fg(Color::White);
+        let heading = |title: &str| {
+            Line::from(Span::styled(
+                format!(" {title}"),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        };
+        let row = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!(" {label:>12}  "), label_style),
+                Span::styled(value, value_style),
+            ])
+        };
+
+        let mut lines = vec![heading("ACTIVE NETWORK")];
+        match info.active {
+            Some(ref active) => {
+                let transport = active.transport.as_deref().unwrap_or("unknown");
+                let interface = active.interface.as_deref().unwrap_or("-");
+                lines.push(row(
+                    "Default",
+                    format!("{transport} on {interface} (network {})", active.id),
+                ));
+                let dns = if active.dns.is_empty() {
+                    "-".to_string()
+                } else {
+                    active.dns.join(", ")
+                };
+                lines.push(row("DNS", dns));
+            }
+            None => lines.push(row("Default", "none".to_string())),
+        }
+        let proxy = match info.http_proxy {
+            Some(ref proxy) if info.proxies_to_host(self.proxy_port) => {
+                format!("{proxy} (host port {} via adb reverse)", self.proxy_port)
+            }
+            Some(ref proxy) => proxy.clone(),
+            None => "none".to_string(),
+        };
+        lines.push(row("HTTP proxy", proxy));
+
+        lines.push(Line::from(""));
+        lines.push(heading("INTERFACES"));
+        for interface in &info.interfaces {
+            let state_color = match interface.state.as_str() {
+                "UP" => Color::Green,
+                "DOWN" => Color::Red,
+                _ => Color::DarkGray,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:<14}", interface.name), value_style),
+                Span::styled(
+                    format!("{:<8}", interface.state),
+                    Style::default().fg(state_color),
+                ),
+                Span::styled(
+                    format!("{:<19}", interface.mac.as_deref().unwrap_or("-")),
+                    label_style,
+                ),
+                Span::styled(interface.addresses.join(", "), value_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("ROUTES"));
+        if info.routes.is_empty() {
+            lines.push(Line::from(Span::styled(" (no routes)", label_style)));
+        }
+        for route in &info.routes {
+            let mut spans = vec![Span::styled(
+                format!(" {:<28}", route.destination),
+                value_style,
+            )];
+            for (key, value) in [
+                ("via", &route.gateway),
+                ("dev", &route.device),
+                ("table", &route.table),
+            ] {
+                if let Some(value) = value {
+                    spans.push(Span::styled(format!("{key} "), label_style));
+                    spans.push(Span::styled(format!("{value}  "), value_style));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+impl Component for NetworkPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| NetworkAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.info = None;
+                self.scroll = 0;
+                self.refresh_command()
+            }
+            Msg::NetworkUpdated { serial, result } => {
+                if self.serial.as_ref() == Some(serial) {
+                    self.info = Some(result.clone());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Network;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" NETWORK ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let paragraph = match (&self.serial, &self.info) {
+            (None, _) => Paragraph::new("Select a device to begin"),
+            (Some(_), None) => Paragraph::new("Loading network..."),
+            (Some(_), Some(Err(e))) => {
+                Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+            }
+            (Some(_), Some(Ok(info))) => {
+                Paragraph::new(self.info_lines(info)).scroll((self.scroll, 0))
+            }
+        };
+        frame.render_widget(paragraph, inner);
+    }
+
+    fn id(&self) -> &'static str {
+        "Network"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, Device, DeviceState};
+
+    fn make_pane() -> NetworkPane {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty())],
+            "ToggleProxy".into(),
+        );
+        let mut pane = NetworkPane::new(keymap, 8080);
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane
+    }
+
+    fn toggle(pane: &mut NetworkPane) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char('p'),
+            KeyModifiers::empty(),
+        )))
+    }
+
+    #[test]
+    fn proxy_toggle_follows_the_current_setting() {
+        let mut pane = make_pane();
+        assert!(toggle(&mut pane).is_empty());
+
+        let mut info = NetworkInfo {
+            http_proxy: Some("10.0.0.5:3128".into()),
+            ..Default::default()
+        };
+        pane.update(&Msg::NetworkUpdated {
+            serial: "DEV0".into(),
+            result: Ok(info.clone()),
+        });
+        assert!(matches!(
+            toggle(&mut pane).as_slice(),
+            [Command::SetHostProxy {
+                port: 8080,
+                enable: true,
+                ..
+            }]
+        ));
+
+        info.http_proxy = Some("127.0.0.1:8080".into());
+        pane.update(&Msg::NetworkUpdated {
+            serial: "DEV0".into(),
+            result: Ok(info),
+        });
+        assert!(matches!(
+            toggle(&mut pane).as_slice(),
+            [Command::SetHostProxy { enable: false, .. }]
+        ));
+    }
+}
@@ -0,0 +1,411 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::{
+    adb::notifications::Notification,
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum NotificationsAction {
+    Up,
+    Down,
+    Filter,
+    Dismiss,
+    Refresh,
+}
+
+impl FromStr for NotificationsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Filter" => Ok(Self::Filter),
+            "Dismiss" => Ok(Self::Dismiss),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct NotificationsPane {
+    serial: Option<String>,
+    notifications: Vec<Notification>,
+    error: Option<String>,
+    loaded: bool,
+    selected_index: usize,
+    /// Filters by package.
+    filter: TextInput,
+    typing: bool,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl NotificationsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            notifications: Vec::new(),
+            error: None,
+            loaded: false,
+            selected_index: 0,
+            filter: TextInput::new(),
+            typing: false,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn visible(&self) -> Vec<&Notification> {
+        let needle = self.filter.value().to_lowercase();
+        self.notifications
+            .iter()
+            .filter(|n| needle.is_empty() || n.package.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        self.selected_index = self.selected_index.min(len.saturating_sub(1));
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshNotifications(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_filter_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.filter.handle_key(key) {
+            InputOutcome::Submit => self.typing = false,
+            InputOutcome::Cancel => {
+                self.typing = false;
+                self.filter.clear();
+            }
+            InputOutcome::Changed => self.selected_index = 0,
+            InputOutcome::Ignored => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: NotificationsAction) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        match action {
+            NotificationsAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            NotificationsAction::Down => {
+                let last = self.visible().len().saturating_sub(1);
+                self.selected_index = (self.selected_index + 1).min(last);
+            }
+            NotificationsAction::Filter => self.typing = true,
+            NotificationsAction::Dismiss => {
+                let Some(notification) = self.visible().get(self.selected_index).copied() else {
+                    return Vec::new();
+                };
+                if !notification.is_clearable() {
+                    self.notice = Some(format!(
+                        "{} is ongoing; only {} can remove it",
+                        notification.title().unwrap_or("This notification"),
+                        notification.package
+                    ));
+                    return Vec::new();
+                }
+                return vec![Command::DismissNotification {
+                    serial,
+                    key: notification.key.clone(),
+                }];
+            }
+            NotificationsAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn draw_list(&self, frame: &mut Frame, area: Rect, visible: &[&Notification]) {
+        let header = Row::new(["PACKAGE", "CHANNEL", "IMPORTANCE", "POSTED", "TITLE"])
+            .style(Style::default().fg(Color::DarkGray));
+        let rows: Vec<Row> = visible
+            .iter()
+            .map(|n| {
+                let color = if n.is_clearable() {
+                    Color::White
+                } else {
+                    Color::Cyan
+                };
+                Row::new([
+                    n.package.clone(),
+                    n.channel.clone().unwrap_or_default(),
+                    n.importance_label().to_string(),
+                    n.posted_at("%m-%d %H:%M:%S").unwrap_or_default(),
+                    n.title().unwrap_or_default().to_string(),
+                ])
+                .style(Style::default().fg(color))
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Percentage(15),
+                Constraint::Length(10),
+                Constraint::Length(14),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut table_state = TableState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect, notification: &Notification) {
+        let label =
+            |name: &str| Span::styled(format!(" {name:<11}"), Style::default().fg(Color::DarkGray));
+        let mut lines = vec![
+            Line::from(vec![label("key"), Span::raw(notification.key.clone())]),
+            Line::from(vec![
+                label("title"),
+                Span::styled(
+                    notification.title().unwrap_or_default().to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(vec![
+                label("text"),
+                Span::raw(notification.text().unwrap_or_default().to_string()),
+            ]),
+            Line::from(vec![
+                label("flags"),
+                Span::raw(notification.flag_names().join(" | ")),
+            ]),
+            Line::from(vec![
+                label("posted"),
+                Span::raw(
+                    notification
+                        .posted_at("%Y-%m-%d %H:%M:%S")
+                        .unwrap_or_default(),
+                ),
+            ]),
+            Line::from(label("extras")),
+        ];
+        lines.extend(notification.extras.iter().map(|(name, value)| {
+            Line::from(vec![
+                Span::styled(format!("   {name}="), Style::default().fg(Color::DarkGray)),
+                Span::raw(value.clone()),
+            ])
+        }));
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+}
+
+impl Component for NotificationsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.typing && matches!(msg, Msg::KeyPress(_)) {
+            return self.handle_filter_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| NotificationsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.notifications.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.refresh_command()
+            }
+            Msg::NotificationsUpdated { serial, result }
+                if self.serial.as_ref() == Some(serial) =>
+            {
+                self.loaded = true;
+                match result {
+                    Ok(notifications) => {
+                        self.notifications = notifications.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Notifications;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" NOTIFICATIONS ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+            return;
+        }
+        let rows = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Percentage(50),
+            Constraint::Min(0),
+            Constraint::Length(if self.notice.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        frame.render_widget(
+            Paragraph::new(self.filter.line(" package: ", self.typing)),
+            rows[0],
+        );
+        if let Some(ref notice) = self.notice {
+            let line = Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(Color::Yellow),
+            ));
+            frame.render_widget(Paragraph::new(line), rows[3]);
+        }
+
+        let visible = self.visible();
+        if visible.is_empty() {
+            let paragraph = match (&self.error, self.loaded) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, false) => Paragraph::new("Loading notifications..."),
+                (None, true) => Paragraph::new("(no active notifications)")
+                    .style(Style::default().fg(Color::DarkGray)),
+            };
+            frame.render_widget(paragraph, rows[1]);
+            return;
+        }
+        self.draw_list(frame, rows[1], &visible);
+        if let Some(notification) = visible.get(self.selected_index) {
+            self.draw_details(frame, rows[2], notification);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Notifications"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.typing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::notifications::parse_notifications;
+
+    fn make_pane() -> NotificationsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |c: char, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind('/', "Filter");
+        bind('x', "Dismiss");
+        let mut pane = NotificationsPane::new(keymap);
+        pane.serial = Some("DEV0".into());
+        let dump = [
+            "  Notification List:",
+            "    NotificationRecord(0x1: pkg=com.example.app id=1 tag=null importance=3 key=0|com.example.app|1|null|10123: Notification(channel=a flags=0x10))",
+            "    NotificationRecord(0x2: pkg=com.example.player id=2 tag=null importance=2 key=0|com.example.player|2|null|10124: Notification(channel=b flags=0x22))",
+        ]
+        .join("\n");
+        pane.update(&Msg::NotificationsUpdated {
+            serial: "DEV0".into(),
+            result: Ok(parse_notifications(&dump)),
+        });
+        pane
+    }
+
+    fn press(pane: &mut NotificationsPane, c: char) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char(c),
+            KeyModifiers::empty(),
+        )))
+    }
+
+    #[test]
+    fn filter_narrows_by_package_before_dismissing() {
+        let mut pane = make_pane();
+        assert_eq!(pane.visible().len(), 2);
+        press(&mut pane, '/');
+        for c in "player".chars() {
+            press(&mut pane, c);
+        }
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::empty(),
+        )));
+        assert_eq!(pane.visible().len(), 1);
+
+        // Ongoing: explained, not dismissed
+        assert!(press(&mut pane, 'x').is_empty());
+        assert!(pane.notice.is_some());
+
+        press(&mut pane, '/');
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Esc,
+            KeyModifiers::empty(),
+        )));
+        assert!(matches!(
+            press(&mut pane, 'x').as_slice(),
+            [Command::DismissNotification { key, .. }] if key == "0|com.example.app|1|null|10123"
+        ));
+    }
+}
@@ -0,0 +1,513 @@
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    adb::permissions::{AppOpMode, PackagePermissions, PermissionKind},
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum PermissionsAction {
+    Up,
+    Down,
+    Toggle,
+    ResetAll,
+    SwitchTab,
+    CycleMode,
+    Refresh,
+    Back,
+}
+
+impl FromStr for PermissionsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Toggle" => Ok(Self::Toggle),
+            "ResetAll" => Ok(Self::ResetAll),
+            "SwitchTab" => Ok(Self::SwitchTab),
+            "CycleMode" => Ok(Self::CycleMode),
+            "Refresh" => Ok(Self::Refresh),
+            "Back" => Ok(Self::Back),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tab {
+    Permissions,
+    AppOps,
+}
+
+pub struct PermissionsPane {
+    data: Option<PackagePermissions>,
+    error: Option<String>,
+    tab: Tab,
+    selected_permission: usize,
+    selected_op: usize,
+    keymap: SectionKeymap,
+}
+
+impl PermissionsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            data: None,
+            error: None,
+            tab: Tab::Permissions,
+            selected_permission: 0,
+            selected_op: 0,
+            keymap,
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        match (&self.data, self.tab) {
+            (Some(data), Tab::Permissions) => data.permissions.len(),
+            (Some(data), Tab::AppOps) => data.app_ops.len(),
+            (None, _) => 0,
+        }
+    }
+
+    fn selected_mut(&mut self) -> &mut usize {
+        match self.tab {
+            Tab::Permissions => &mut self.selected_permission,
+            Tab::AppOps => &mut self.selected_op,
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let (perms, ops) = match &self.data {
+            Some(data) => (data.permissions.len(), data.app_ops.len()),
+            None => (0, 0),
+        };
+        self.selected_permission = self.selected_permission.min(perms.saturating_sub(1));
+        self.selected_op = self.selected_op.min(ops.saturating_sub(1));
+    }
+
+    fn handle_action(&mut self, action: PermissionsAction) -> Vec<Command> {
+        match action {
+            PermissionsAction::Up => {
+                let selected = self.selected_mut();
+                *selected = selected.saturating_sub(1);
+            }
+            PermissionsAction::Down => {
+                let len = self.row_count();
+                if len > 0 {
+                    let selected = self.selected_mut();
+                    *selected = (*selected + 1).min(len - 1);
+                }
+            }
+            PermissionsAction::SwitchTab => {
+                self.tab = match self.tab {
+                    Tab::Permissions => Tab::AppOps,
+                    Tab::AppOps => Tab::Permissions,
+                };
+            }
+            PermissionsAction::Back => return vec![Command::ShowPane(Pane::Apps)],
+            PermissionsAction::Toggle => {
+                if self.tab != Tab::Permissions {
+                    return Vec::new();
+                }
+                let Some(ref data) = self.data else {
+                    return Vec::new();
+                };
+                if let Some(perm) = data.permissions.get(self.selected_permission)
+                    && perm.is_changeable()
+                {
+                    return vec![Command::SetPermission {
+                        serial: data.serial.clone(),
+                        package: data.package.clone(),
+                        permission: perm.name.clone(),
+                        grant: perm.granted != Some(true),
+                    }];
+                }
+            }
+            PermissionsAction::CycleMode => {
+                if self.tab != Tab::AppOps {
+                    return Vec::new();
+                }
+                let Some(ref data) = self.data else {
+                    return Vec::new();
+                };
+                if let Some(op) = data.app_ops.get(self.selected_op) {
+                    return vec![Command::SetAppOp {
+                        serial: data.serial.clone(),
+                        package: data.package.clone(),
+                        op: op.name.clone(),
+                        mode: op.mode.next(),
+                    }];
+                }
+            }
+            PermissionsAction::ResetAll => {
+                if let Some(ref data) = self.data {
+                    return vec![Command::ResetPermissions {
+                        serial: data.serial.clone(),
+                        package: data.package.clone(),
+                    }];
+                }
+            }
+            PermissionsAction::Refresh => {
+                if let Some(ref data) = self.data {
+                    return vec![Command::RefreshPermissions {
+                        serial: data.serial.clone(),
+                        package: data.package.clone(),
+                    }];
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    fn permission_items(data: &PackagePermissions) -> Vec<ListItem<'static>> {
+        data.permissions
+            .iter()
+            .map(|perm| {
+                let (icon, color) = match perm.granted {
+                    Some(true) => ("✔", Color::Green),
+                    Some(false) => ("✘", Color::Red),
+                    None => ("·", Color::DarkGray),
+                };
+                let kind_color = match perm.kind {
+                    PermissionKind::Runtime => Color::Cyan,
+                    PermissionKind::Install => Color::Blue,
+                    PermissionKind::Requested => Color::DarkGray,
+                };
+                let mut spans = vec![
+                    Span::styled(format!(" {icon} "), Style::default().fg(color)),
+                    Span::styled(
+                        format!("{:<10}", perm.kind.to_string()),
+                        Style::default().fg(kind_color),
+                    ),
+                    Span::raw(perm.short_name().to_string()),
+                ];
+                if !perm.flags.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  [{}]", perm.flags.join("|")),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+
+    fn app_op_items(data: &PackagePermissions) -> Vec<ListItem<'static>> {
+        data.app_ops
+            .iter()
+            .map(|op| {
+                let color = match op.mode {
+                    AppOpMode::Allow => Color::Green,
+                    AppOpMode::Foreground => Color::Yellow,
+                    AppOpMode::Default => Color::White,
+                    AppOpMode::Ignore | AppOpMode::Deny => Color::Red,
+                };
+                let scope = if op.uid_mode { " (uid)" } else { "" };
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {:<11}", op.mode.as_str()),
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(format!("{}{}", op.name, scope)),
+                ];
+                if !op.detail.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", op.detail),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+}
+
+impl Component for PermissionsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| PermissionsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::PermissionsUpdated {
+                serial,
+                package,
+                result,
+            } => {
+                let same_package = self
+                    .data
+                    .as_ref()
+                    .is_some_and(|d| &d.serial == serial && &d.package == package);
+                if !same_package {
+                    self.data = None;
+                    self.selected_permission = 0;
+                    self.selected_op = 0;
+                    self.tab = Tab::Permissions;
+                }
+                match result {
+                    Ok(data) => {
+                        self.data = Some(data.as_ref().clone());
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(format!("{package}: {e}")),
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            Msg::DeviceSelected(device) => {
+                let serial = device.as_ref().map(|d| d.serial.as_str());
+                if self.data.as_ref().map(|d| d.serial.as_str()) != serial {
+                    self.data = None;
+                    self.error = None;
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Permissions;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let mut title = vec![Span::raw(" PERMISSIONS ")];
+        if let Some(ref e) = self.error
+            && self.data.is_some()
+        {
+            title.push(Span::styled(
+                format!(" refresh failed: {e} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(title))
+            .border_style(Style::default().fg(border_color));
+
+        let Some(ref data) = self.data else {
+            let paragraph = match self.error {
+                Some(ref e) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                None => Paragraph::new("Select a package in the apps view"),
+            };
+            frame.render_widget(paragraph.block(block), area);
+            return;
+        };
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(inner);
+
+        let tab_style = |tab: Tab| {
+            if self.tab == tab {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }
+        };
+        let header = Line::from(vec![
+            Span::styled(
+                format!(" {} ", data.package),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(" Permissions ", tab_style(Tab::Permissions)),
+            Span::raw(" "),
+            Span::styled(" App ops ", tab_style(Tab::AppOps)),
+        ]);
+        frame.render_widget(Paragraph::new(header), rows[0]);
+
+        let (items, selected) = match self.tab {
+            Tab::Permissions => (Self::permission_items(data), self.selected_permission),
+            Tab::AppOps => (Self::app_op_items(data), self.selected_op),
+        };
+        if items.is_empty() {
+            let text = match self.tab {
+                Tab::Permissions => " (no permissions)",
+                Tab::AppOps => " (no app ops)",
+            };
+            frame.render_widget(Paragraph::new(text), rows[1]);
+            return;
+        }
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, rows[1], &mut list_state);
+    }
+
+    fn id(&self) -> &'static str {
+        "Permissions"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::permissions::{AppOp, Permission};
+
+    fn make_keymap() -> SectionKeymap {
+        let mut keymap = SectionKeymap::new();
+        let bind = |c: char| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        keymap.insert(bind('j'), "Down".into());
+        keymap.insert(bind('k'), "Up".into());
+        keymap.insert(bind(' '), "Toggle".into());
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+            "ResetAll".into(),
+        );
+        keymap.insert(bind('o'), "SwitchTab".into());
+        keymap.insert(bind('m'), "CycleMode".into());
+        keymap
+    }
+
+    fn key(c: char) -> Msg {
+        Msg::KeyPress(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()))
+    }
+
+    fn loaded_pane() -> PermissionsPane {
+        let mut pane = PermissionsPane::new(make_keymap());
+        let perm = |name: &str, kind, granted, flags: &[&str]| Permission {
+            name: name.into(),
+            kind,
+            granted,
+            flags: flags.iter().map(|f| f.to_string()).collect(),
+        };
+        pane.update(&Msg::PermissionsUpdated {
+            serial: "DEV0".into(),
+            package: "com.example".into(),
+            result: Ok(Box::new(PackagePermissions {
+                serial: "DEV0".into(),
+                package: "com.example".into(),
+                permissions: vec![
+                    perm(
+                        "android.permission.INTERNET",
+                        PermissionKind::Install,
+                        Some(true),
+                        &[],
+                    ),
+                    perm(
+                        "android.permission.CAMERA",
+                        PermissionKind::Runtime,
+                        Some(false),
+                        &[],
+                    ),
+                    perm(
+                        "android.permission.POST_NOTIFICATIONS",
+                        PermissionKind::Runtime,
+                        Some(true),
+                        &["SYSTEM_FIXED"],
+                    ),
+                ],
+                app_ops: vec![AppOp {
+                    name: "CAMERA".into(),
+                    mode: AppOpMode::Allow,
+                    uid_mode: false,
+                    detail: String::new(),
+                }],
+            })),
+        });
+        pane
+    }
+
+    #[test]
+    fn toggle_grants_denied_runtime_permission() {
+        let mut pane = loaded_pane();
+        pane.update(&key('j'));
+        let cmds = pane.update(&key(' '));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::SetPermission { permission, grant: true, .. }
+                if permission == "android.permission.CAMERA"
+        )));
+    }
+
+    #[test]
+    fn toggle_skips_install_and_fixed_permissions() {
+        let mut pane = loaded_pane();
+        assert!(pane.update(&key(' ')).is_empty());
+        pane.update(&key('j'));
+        pane.update(&key('j'));
+        assert!(pane.update(&key(' ')).is_empty());
+    }
+
+    #[test]
+    fn cycle_mode_on_app_ops_tab() {
+        let mut pane = loaded_pane();
+        assert!(pane.update(&key('m')).is_empty());
+        pane.update(&key('o'));
+        let cmds = pane.update(&key('m'));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::SetAppOp { op, mode: AppOpMode::Ignore, .. } if op == "CAMERA"
+        )));
+    }
+
+    #[test]
+    fn reset_all_targets_current_package() {
+        let mut pane = loaded_pane();
+        let shift_r = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT);
+        let cmds = pane.update(&Msg::KeyPress(shift_r));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::ResetPermissions { serial, package }
+                if serial == "DEV0" && package == "com.example"
+        )));
+    }
+
+    #[test]
+    fn reload_keeps_selection_for_same_package() {
+        let mut pane = loaded_pane();
+        pane.update(&key('j'));
+        let data = pane.data.clone().unwrap();
+        pane.update(&Msg::PermissionsUpdated {
+            serial: "DEV0".into(),
+            package: "com.example".into(),
+            result: Ok(Box::new(data)),
+        });
+        assert_eq!(pane.selected_permission, 1);
+    }
+
+    #[test]
+    fn failed_load_of_another_package_drops_old_data() {
+        let mut pane = loaded_pane();
+        pane.update(&Msg::PermissionsUpdated {
+            serial: "DEV0".into(),
+            package: "com.other".into(),
+            result: Err("not found".into()),
+        });
+        assert!(pane.data.is_none());
+        assert_eq!(pane.error.as_deref(), Some("com.other: not found"));
+    }
+}
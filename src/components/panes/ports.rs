@@ -0,0 +1,547 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::forward::{Direction, PortRule, RuleSet},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum PortsAction {
+    Up,
+    Down,
+    SwitchList,
+    AddForward,
+    AddReverse,
+    Remove,
+    SaveSet,
+    Apply,
+    Refresh,
+}
+
+impl FromStr for PortsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "SwitchList" => Ok(Self::SwitchList),
+            "AddForward" => Ok(Self::AddForward),
+            "AddReverse" => Ok(Self::AddReverse),
+            "Remove" => Ok(Self::Remove),
+            "SaveSet" => Ok(Self::SaveSet),
+            "Apply" => Ok(Self::Apply),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+enum Prompt {
+    AddRule(Direction, TextInput),
+    SetName(TextInput),
+    /// Waiting for y/n before deleting the named set.
+    ConfirmDelete(String),
+}
+
+pub struct PortsPane {
+    serial: Option<String>,
+    rules: Vec<PortRule>,
+    error: Option<String>,
+    loaded: bool,
+    selected_index: usize,
+    /// Named rule sets, kept in step with the config through `SavePortRuleSets`.
+    sets: BTreeMap<String, RuleSet>,
+    set_index: usize,
+    /// Whether j/k and the actions work on the rule sets instead of the live rules.
+    sets_focused: bool,
+    prompt: Option<Prompt>,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl PortsPane {
+    pub fn new(keymap: SectionKeymap, sets: BTreeMap<String, RuleSet>) -> Self {
+        Self {
+            serial: None,
+            rules: Vec::new(),
+            error: None,
+            loaded: false,
+            selected_index: 0,
+            sets,
+            set_index: 0,
+            sets_focused: false,
+            prompt: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn selected_set(&self) -> Option<(&String, &RuleSet)> {
+        self.sets.iter().nth(self.set_index)
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshPorts(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn save_sets(&self) -> Vec<Command> {
+        vec![Command::SavePortRuleSets(self.sets.clone())]
+    }
+
+    fn handle_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(prompt)) = (msg, self.prompt.take()) else {
+            return Vec::new();
+        };
+        match prompt {
+            Prompt::AddRule(direction, mut input) => match input.handle_key(key) {
+                InputOutcome::Submit => {
+                    match (PortRule::parse(direction, input.value()), &self.serial) {
+                        (Ok(rule), Some(serial)) => {
+                            self.notice = None;
+                            return vec![Command::AddPortRule {
+                                serial: serial.clone(),
+                                rule,
+                            }];
+                        }
+                        (Err(e), _) => {
+                            self.notice = Some(e.to_string());
+                            self.prompt = Some(Prompt::AddRule(direction, input));
+                        }
+                        (Ok(_), None) => {}
+                    }
+                }
+                InputOutcome::Cancel => self.notice = None,
+                _ => self.prompt = Some(Prompt::AddRule(direction, input)),
+            },
+            Prompt::SetName(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit if !input.value().trim().is_empty() => {
+                    let name = input.value().trim().to_string();
+                    // Keep the device filter of a set being overwritten
+                    let devices = self
+                        .sets
+                        .get(&name)
+                        .map(|s| s.devices.clone())
+                        .unwrap_or_default();
+                    let set = RuleSet {
+                        devices,
+                        ..RuleSet::from_rules(&self.rules)
+                    };
+                    self.notice = Some(format!("Saved {} rules as '{name}'", set.len()));
+                    self.sets.insert(name, set);
+                    return self.save_sets();
+                }
+                InputOutcome::Cancel => {}
+                _ => self.prompt = Some(Prompt::SetName(input)),
+            },
+            Prompt::ConfirmDelete(name) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.sets.remove(&name);
+                    self.set_index = self.set_index.min(self.sets.len().saturating_sub(1));
+                    return self.save_sets();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {}
+                _ => self.prompt = Some(Prompt::ConfirmDelete(name)),
+            },
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: PortsAction) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        match action {
+            PortsAction::Up if self.sets_focused => {
+                self.set_index = self.set_index.saturating_sub(1);
+            }
+            PortsAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            PortsAction::Down if self.sets_focused => {
+                self.set_index = (self.set_index + 1).min(self.sets.len().saturating_sub(1));
+            }
+            PortsAction::Down => {
+                self.selected_index =
+                    (self.selected_index + 1).min(self.rules.len().saturating_sub(1));
+            }
+            PortsAction::SwitchList => self.sets_focused = !self.sets_focused,
+            PortsAction::AddForward => {
+                self.prompt = Some(Prompt::AddRule(Direction::Forward, TextInput::new()));
+            }
+            PortsAction::AddReverse => {
+                self.prompt = Some(Prompt::AddRule(Direction::Reverse, TextInput::new()));
+            }
+            PortsAction::Remove if self.sets_focused => {
+                if let Some((name, _)) = self.selected_set() {
+                    self.prompt = Some(Prompt::ConfirmDelete(name.clone()));
+                }
+            }
+            PortsAction::Remove => {
+                if let Some(rule) = self.rules.get(self.selected_index) {
+                    return vec![Command::RemovePortRule {
+                        serial,
+                        rule: rule.clone(),
+                    }];
+                }
+            }
+            PortsAction::SaveSet => {
+                if !self.rules.is_empty() {
+                    self.prompt = Some(Prompt::SetName(TextInput::new()));
+                }
+            }
+            PortsAction::Apply => {
+                if let Some((name, set)) = self.selected_set() {
+                    match set.rules() {
+                        Ok(rules) => return vec![Command::ApplyPortRules { serial, rules }],
+                        Err(e) => self.notice = Some(format!("{name}: {e}")),
+                    }
+                }
+            }
+            PortsAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn prompt_line(&self) -> Option<Line<'static>> {
+        let line = match self.prompt.as_ref() {
+            Some(Prompt::AddRule(direction, input)) => {
+                let label = match direction {
+                    Direction::Forward => " adb forward <host> <device>: ",
+                    Direction::Reverse => " adb reverse <device> <host>: ",
+                };
+                input.line(label, true)
+            }
+            Some(Prompt::SetName(input)) => input.line(" Save rules as set: ", true),
+            Some(Prompt::ConfirmDelete(name)) => Line::from(vec![
+                Span::styled(
+                    format!(" Delete rule set '{name}'?"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    "  y confirm, n cancel",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            None => Line::from(Span::styled(
+                format!(" {}", self.notice.as_ref()?),
+                Style::default().fg(Color::Yellow),
+            )),
+        };
+        Some(line)
+    }
+
+    fn draw_rules(&self, frame: &mut Frame, area: Rect) {
+        if self.rules.is_empty() {
+            let paragraph = match (&self.error, self.loaded) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, false) => Paragraph::new("Loading port rules..."),
+                (None, true) => Paragraph::new("(no forwards or reverses)")
+                    .style(Style::default().fg(Color::DarkGray)),
+            };
+            frame.render_widget(paragraph, area);
+            return;
+        }
+        let rows: Vec<Row> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let arrow = match rule.direction {
+                    Direction::Forward => "->",
+                    Direction::Reverse => "<-",
+                };
+                Row::new([
+                    rule.direction.to_string(),
+                    rule.host.clone(),
+                    arrow.to_string(),
+                    rule.device.clone(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Percentage(40),
+                Constraint::Length(2),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(["TYPE", "HOST", "", "DEVICE"]).style(Style::default().fg(Color::DarkGray)),
+        );
+        let selected = (!self.sets_focused).then_some(self.selected_index);
+        let table = table.row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(selected);
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn draw_sets(&self, frame: &mut Frame, area: Rect) {
+        let border = if self.sets_focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .title(" RULE SETS (re-applied on connect) ")
+            .border_style(Style::default().fg(border));
+        let rows: Vec<Row> = self
+            .sets
+            .iter()
+            .map(|(name, set)| {
+                let devices = if set.devices.is_empty() {
+                    "all devices".to_string()
+                } else {
+                    set.devices.join(", ")
+                };
+                Row::new([name.clone(), format!("{} rules", set.len()), devices])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Length(10),
+                Constraint::Min(0),
+            ],
+        )
+        .block(block)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let selected = self.sets_focused.then_some(self.set_index);
+        let mut state = TableState::default().with_selected(selected);
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+impl Component for PortsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) && self.prompt.is_some() {
+            return self.handle_prompt_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| PortsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.rules.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.prompt = None;
+                self.notice = None;
+                self.refresh_command()
+            }
+            Msg::PortsUpdated { serial, rules } => {
+                if self.serial.as_ref() != Some(serial) {
+                    return Vec::new();
+                }
+                self.loaded = true;
+                match rules {
+                    Ok(rules) => {
+                        self.rules = rules.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.selected_index = self.selected_index.min(self.rules.len().saturating_sub(1));
+                Vec::new()
+            }
+            Msg::PortRulesApplied { serial, .. } if self.serial.as_ref() == Some(serial) => {
+                self.refresh_command()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Ports;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" PORTS ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+            return;
+        }
+
+        let prompt = self.prompt_line();
+        let sets_height = (self.sets.len() as u16 + 1).clamp(3, 8);
+        let rows = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(sets_height),
+            Constraint::Length(if prompt.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+
+        self.draw_rules(frame, rows[0]);
+        self.draw_sets(frame, rows[1]);
+        if let Some(prompt) = prompt {
+            frame.render_widget(Paragraph::new(prompt), rows[2]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Ports"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, Device, DeviceState};
+
+    fn make_pane() -> PortsPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |code: KeyCode, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(code, KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind(KeyCode::Char('a'), "AddForward");
+        bind(KeyCode::Char('x'), "Remove");
+        bind(KeyCode::Char('s'), "SaveSet");
+        bind(KeyCode::Char('o'), "SwitchList");
+        bind(KeyCode::Enter, "Apply");
+        let mut pane = PortsPane::new(keymap, BTreeMap::new());
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(pane: &mut PortsPane, text: &str) {
+        for c in text.chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+    }
+
+    fn loaded(pane: &mut PortsPane, specs: &[&str]) {
+        let rules = specs
+            .iter()
+            .map(|s| PortRule::parse(Direction::Reverse, s).unwrap())
+            .collect();
+        pane.update(&Msg::PortsUpdated {
+            serial: "DEV0".into(),
+            rules: Ok(rules),
+        });
+    }
+
+    #[test]
+    fn invalid_spec_keeps_the_prompt_open() {
+        let mut pane = make_pane();
+        pane.update(&key(KeyCode::Char('a')));
+        type_text(&mut pane, "tcp:abc tcp:1");
+        assert!(pane.update(&key(KeyCode::Enter)).is_empty());
+        assert!(pane.captures_input());
+        assert!(pane.notice.is_some());
+
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+        )));
+        type_text(&mut pane, "tcp:9222 localabstract:chrome_devtools_remote");
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::AddPortRule { rule, .. }] if rule.device == "localabstract:chrome_devtools_remote"
+        ));
+    }
+
+    #[test]
+    fn current_rules_are_saved_and_applied_as_a_set() {
+        let mut pane = make_pane();
+        loaded(&mut pane, &["tcp:8081 tcp:8081"]);
+
+        pane.update(&key(KeyCode::Char('s')));
+        type_text(&mut pane, "metro");
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::SavePortRuleSets(sets)] if sets["metro"].reverse == ["tcp:8081 tcp:8081"]
+        ));
+
+        pane.update(&key(KeyCode::Char('o')));
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ApplyPortRules { serial, rules }] if serial == "DEV0" && rules.len() == 1
+        ));
+    }
+
+    #[test]
+    fn removing_a_set_asks_first() {
+        let mut pane = make_pane();
+        loaded(&mut pane, &["tcp:8081 tcp:8081"]);
+        pane.sets
+            .insert("metro".into(), RuleSet::from_rules(&pane.rules));
+        pane.update(&key(KeyCode::Char('o')));
+
+        assert!(pane.update(&key(KeyCode::Char('x'))).is_empty());
+        let cmds = pane.update(&key(KeyCode::Char('y')));
+        assert!(matches!(cmds.as_slice(), [Command::SavePortRuleSets(sets)] if sets.is_empty()));
+    }
+}
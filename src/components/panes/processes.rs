@@ -0,0 +1,703 @@
+use std::cmp::Reverse;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::processes::{ProcessInfo, Signal},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+const PROCESS_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq)]
+enum ProcessesAction {
+    Up,
+    Down,
+    Filter,
+    CycleSort,
+    Kill,
+    OpenPackage,
+    Details,
+    Refresh,
+    Back,
+}
+
+impl FromStr for ProcessesAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Filter" => Ok(Self::Filter),
+            "CycleSort" => Ok(Self::CycleSort),
+            "Kill" => Ok(Self::Kill),
+            "OpenPackage" => Ok(Self::OpenPackage),
+            "Details" => Ok(Self::Details),
+            "Refresh" => Ok(Self::Refresh),
+            "Back" => Ok(Self::Back),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Cpu,
+    Rss,
+    Name,
+}
+
+impl SortKey {
+    fn next(self) -> Self {
+        match self {
+            Self::Cpu => Self::Rss,
+            Self::Rss => Self::Name,
+            Self::Name => Self::Cpu,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Rss => "rss",
+            Self::Name => "name",
+        }
+    }
+}
+
+/// The process a kill prompt was opened on, kept so a refresh that reorders
+/// the list can't move the signal onto another row.
+struct KillPrompt {
+    pid: u32,
+    name: String,
+    /// Index into `Signal::ALL`.
+    signal: usize,
+}
+
+struct ProcessDetail {
+    pid: u32,
+    name: String,
+    text: Option<Result<String, String>>,
+    scroll: u16,
+}
+
+pub struct ProcessesPane {
+    serial: Option<String>,
+    processes: Vec<ProcessInfo>,
+    error: Option<String>,
+    loaded: bool,
+    /// A refresh is running in the background; don't queue another.
+    pending: bool,
+    last_refresh: Instant,
+    sort: SortKey,
+    filter: TextInput,
+    filtering: bool,
+    selected_pid: Option<u32>,
+    kill_prompt: Option<KillPrompt>,
+    /// Why the last kill prompt sent nothing.
+    kill_error: Option<String>,
+    detail: Option<ProcessDetail>,
+    keymap: SectionKeymap,
+}
+
+impl ProcessesPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            processes: Vec::new(),
+            error: None,
+            loaded: false,
+            pending: false,
+            last_refresh: Instant::now(),
+            sort: SortKey::Cpu,
+            filter: TextInput::new(),
+            filtering: false,
+            selected_pid: None,
+            kill_prompt: None,
+            kill_error: None,
+            detail: None,
+            keymap,
+        }
+    }
+
+    fn visible_processes(&self) -> Vec<&ProcessInfo> {
+        let needle = self.filter.value().to_lowercase();
+        let mut visible: Vec<&ProcessInfo> = self
+            .processes
+            .iter()
+            .filter(|p| {
+                needle.is_empty()
+                    || p.name.to_lowercase().contains(&needle)
+                    || p.user.to_lowercase().contains(&needle)
+                    || p.pid.to_string() == needle
+            })
+            .collect();
+
+        match self.sort {
+            SortKey::Cpu => {
+                visible.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.rss_kb.cmp(&a.rss_kb)))
+            }
+            SortKey::Rss => visible.sort_by_key(|p| Reverse(p.rss_kb)),
+            SortKey::Name => visible.sort_by(|a, b| a.name.cmp(&b.name)),
+        }
+        visible
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_pid
+            .and_then(|pid| self.visible_processes().iter().position(|p| p.pid == pid))
+            .unwrap_or(0)
+    }
+
+    fn selected_process(&self) -> Option<&ProcessInfo> {
+        self.visible_processes().get(self.selected_index()).copied()
+    }
+
+    fn select_index(&mut self, index: usize) {
+        self.selected_pid = self.visible_processes().get(index).map(|p| p.pid);
+    }
+
+    fn refresh_command(&mut self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) if !self.pending => {
+                self.pending = true;
+                self.last_refresh = Instant::now();
+                vec![Command::RefreshProcesses(serial.clone())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_filter_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.filter.handle_key(key) {
+            InputOutcome::Submit => self.filtering = false,
+            InputOutcome::Cancel => {
+                self.filtering = false;
+                self.filter.clear();
+            }
+            InputOutcome::Changed | InputOutcome::Ignored => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_kill_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(prompt)) = (msg, &mut self.kill_prompt) else {
+            return Vec::new();
+        };
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                prompt.signal = prompt.signal.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                prompt.signal = (prompt.signal + 1).min(Signal::ALL.len() - 1);
+            }
+            KeyCode::Esc => self.kill_prompt = None,
+            KeyCode::Enter => {
+                let Some(prompt) = self.kill_prompt.take() else {
+                    return Vec::new();
+                };
+                let alive = self
+                    .processes
+                    .iter()
+                    .any(|p| p.pid == prompt.pid && p.name == prompt.name);
+                if !alive {
+                    self.kill_error = Some(format!(
+                        "{} ({}) has exited, no signal sent",
+                        prompt.pid, prompt.name
+                    ));
+                    return Vec::new();
+                }
+                if let Some(ref serial) = self.serial {
+                    return vec![Command::KillProcess {
+                        serial: serial.clone(),
+                        pid: prompt.pid,
+                        signal: Signal::ALL[prompt.signal],
+                    }];
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: ProcessesAction) -> Vec<Command> {
+        if let Some(ref mut detail) = self.detail {
+            match action {
+                ProcessesAction::Up => detail.scroll = detail.scroll.saturating_sub(1),
+                ProcessesAction::Down => detail.scroll = detail.scroll.saturating_add(1),
+                ProcessesAction::Back => self.detail = None,
+                _ => {}
+            }
+            return Vec::new();
+        }
+
+        match action {
+            ProcessesAction::Up => {
+                let index = self.selected_index().saturating_sub(1);
+                self.select_index(index);
+            }
+            ProcessesAction::Down => {
+                let len = self.visible_processes().len();
+                if len > 0 {
+                    let index = (self.selected_index() + 1).min(len - 1);
+                    self.select_index(index);
+                }
+            }
+            ProcessesAction::Filter => self.filtering = true,
+            ProcessesAction::CycleSort => self.sort = self.sort.next(),
+            ProcessesAction::Kill => {
+                self.kill_error = None;
+                if let Some(process) = self.selected_process() {
+                    self.kill_prompt = Some(KillPrompt {
+                        pid: process.pid,
+                        name: process.name.clone(),
+                        signal: 0,
+                    });
+                }
+            }
+            ProcessesAction::OpenPackage => {
+                if let Some(package) = self.selected_process().and_then(|p| p.package()) {
+                    return vec![Command::ShowPackage(package.to_string())];
+                }
+            }
+            ProcessesAction::Details => {
+                if let (Some(serial), Some(process)) = (&self.serial, self.selected_process()) {
+                    let cmd = Command::LoadProcessMemInfo {
+                        serial: serial.clone(),
+                        pid: process.pid,
+                    };
+                    self.detail = Some(ProcessDetail {
+                        pid: process.pid,
+                        name: process.name.clone(),
+                        text: None,
+                        scroll: 0,
+                    });
+                    return vec![cmd];
+                }
+            }
+            ProcessesAction::Refresh => return self.refresh_command(),
+            ProcessesAction::Back => {}
+        }
+        Vec::new()
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect, detail: &ProcessDetail) {
+        let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(area);
+        let header = Line::from(Span::styled(
+            format!(" meminfo {} ({})", detail.pid, detail.name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+        frame.render_widget(Paragraph::new(header), rows[0]);
+
+        let body = match &detail.text {
+            None => Paragraph::new(" Loading meminfo..."),
+            Some(Ok(text)) => Paragraph::new(text.as_str()).scroll((detail.scroll, 0)),
+            Some(Err(e)) => Paragraph::new(format!(" {e}")).style(Style::default().fg(Color::Red)),
+        };
+        frame.render_widget(body, rows[1]);
+    }
+}
+
+impl Component for ProcessesPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) {
+            if self.kill_prompt.is_some() {
+                return self.handle_kill_prompt_key(msg);
+            }
+            if self.filtering {
+                return self.handle_filter_key(msg);
+            }
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| ProcessesAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::Tick => {
+                if self.detail.is_none() && self.last_refresh.elapsed() >= PROCESS_REFRESH_INTERVAL
+                {
+                    self.refresh_command()
+                } else {
+                    Vec::new()
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.processes.clear();
+                self.error = None;
+                self.loaded = false;
+                self.detail = None;
+                self.selected_pid = None;
+                self.kill_prompt = None;
+                self.kill_error = None;
+                // A result still in flight belongs to the old device and is dropped
+                self.pending = false;
+                self.refresh_command()
+            }
+            Msg::ProcessesUpdated { serial, processes } => {
+                if self.serial.as_ref() != Some(serial) {
+                    return Vec::new();
+                }
+                self.pending = false;
+                self.loaded = true;
+                match processes {
+                    Ok(processes) => {
+                        self.processes = processes.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                Vec::new()
+            }
+            Msg::ProcessMemInfo { serial, pid, text } => {
+                if let Some(ref mut detail) = self.detail
+                    && self.serial.as_ref() == Some(serial)
+                    && detail.pid == *pid
+                {
+                    detail.text = Some(text.clone());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Processes;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let mut title = vec![Span::raw(format!(
+            " PROCESSES (sort: {}) ",
+            self.sort.label()
+        ))];
+        // With rows still listed, a failed refresh would go unnoticed below
+        if let Some(ref e) = self.error
+            && !self.processes.is_empty()
+        {
+            title.push(Span::styled(
+                format!(" refresh failed: {e} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        if let Some(ref e) = self.kill_error {
+            title.push(Span::styled(
+                format!(" {e} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(title))
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if let Some(ref detail) = self.detail {
+            self.draw_detail(frame, inner, detail);
+            return;
+        }
+
+        let rows = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(if self.kill_prompt.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        frame.render_widget(
+            Paragraph::new(self.filter.line(" / ", self.filtering)),
+            rows[0],
+        );
+
+        let processes = self.visible_processes();
+        if processes.is_empty() {
+            let paragraph = match (&self.serial, &self.error, self.loaded) {
+                (None, _, _) => Paragraph::new("Select a device to begin"),
+                (Some(_), Some(e), _) => {
+                    Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+                }
+                (Some(_), None, false) => Paragraph::new("Loading processes..."),
+                (Some(_), None, true) => Paragraph::new("(no processes)"),
+            };
+            frame.render_widget(paragraph, rows[1]);
+            return;
+        }
+
+        let header = Row::new(["PID", "USER", "CPU%", "RSS", "S", "NAME"])
+            .style(Style::default().fg(Color::DarkGray));
+        let table_rows: Vec<Row> = processes
+            .iter()
+            .map(|p| {
+                Row::new([
+                    p.pid.to_string(),
+                    p.user.clone(),
+                    format!("{:.1}", p.cpu),
+                    format_kb(p.rss_kb),
+                    p.state.clone(),
+                    p.name.clone(),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(7),
+                Constraint::Length(12),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(2),
+                Constraint::Min(10),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(Some(self.selected_index()));
+        frame.render_stateful_widget(table, rows[1], &mut state);
+
+        if let Some(ref prompt) = self.kill_prompt {
+            let mut spans = vec![Span::styled(
+                format!(" Signal to {} ({}): ", prompt.pid, prompt.name),
+                Style::default().fg(Color::Yellow),
+            )];
+            for (i, signal) in Signal::ALL.iter().enumerate() {
+                let style = if i == prompt.signal {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                spans.push(Span::styled(format!(" {} ", signal.as_str()), style));
+            }
+            spans.push(Span::styled(
+                "  ←/→ choose, Enter send, Esc cancel",
+                Style::default().fg(Color::DarkGray),
+            ));
+            frame.render_widget(Paragraph::new(Line::from(spans)), rows[2]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Processes"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.filtering || self.kill_prompt.is_some()
+    }
+
+    fn back(&mut self) -> bool {
+        self.detail.take().is_some()
+    }
+}
+
+fn format_kb(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.1}G", kb as f64 / 1_048_576.0)
+    } else if kb >= 1024 {
+        format!("{:.1}M", kb as f64 / 1024.0)
+    } else {
+        format!("{kb}K")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn make_keymap() -> SectionKeymap {
+        let mut keymap = SectionKeymap::new();
+        let bind = |c: char| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        keymap.insert(bind('j'), "Down".into());
+        keymap.insert(bind('k'), "Up".into());
+        keymap.insert(bind('s'), "CycleSort".into());
+        keymap.insert(bind('x'), "Kill".into());
+        keymap.insert(bind('p'), "OpenPackage".into());
+        keymap
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn process(pid: u32, name: &str, rss_kb: u64, cpu: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid: 1,
+            user: "u0_a1".into(),
+            rss_kb,
+            vsz_kb: 0,
+            state: "S".into(),
+            name: name.into(),
+            cpu,
+        }
+    }
+
+    fn loaded_pane() -> ProcessesPane {
+        let mut pane = ProcessesPane::new(make_keymap());
+        pane.serial = Some("DEV0".into());
+        pane.pending = true;
+        pane.update(&Msg::ProcessesUpdated {
+            serial: "DEV0".into(),
+            processes: Ok(vec![
+                process(10, "zygote", 5000, 0.5),
+                process(20, "com.example.app", 90000, 12.0),
+                process(30, "adbd", 1000, 2.0),
+            ]),
+        });
+        pane
+    }
+
+    #[test]
+    fn sorts_by_cpu_then_rss_then_name() {
+        let mut pane = loaded_pane();
+        let pids = |p: &ProcessesPane| {
+            p.visible_processes()
+                .iter()
+                .map(|p| p.pid)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pids(&pane), vec![20, 30, 10]);
+        pane.update(&key(KeyCode::Char('s')));
+        assert_eq!(pids(&pane), vec![20, 10, 30]);
+        pane.update(&key(KeyCode::Char('s')));
+        assert_eq!(pids(&pane), vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn selection_follows_pid_across_refresh() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('j')));
+        assert_eq!(pane.selected_process().unwrap().pid, 30);
+
+        pane.update(&Msg::ProcessesUpdated {
+            serial: "DEV0".into(),
+            processes: Ok(vec![
+                process(30, "adbd", 1000, 50.0),
+                process(10, "zygote", 5000, 0.5),
+            ]),
+        });
+        assert_eq!(pane.selected_index(), 0);
+        assert_eq!(pane.selected_process().unwrap().pid, 30);
+    }
+
+    #[test]
+    fn kill_prompt_sends_chosen_signal() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('x')));
+        assert!(pane.captures_input());
+        pane.update(&key(KeyCode::Right));
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::KillProcess {
+                pid: 20,
+                signal: Signal::Kill,
+                ..
+            }
+        )));
+        assert!(!pane.captures_input());
+    }
+
+    #[test]
+    fn kill_prompt_targets_the_process_it_was_opened_on() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('j')));
+        pane.update(&key(KeyCode::Char('x')));
+        // The refresh resorts the list while the prompt is open
+        pane.update(&Msg::ProcessesUpdated {
+            serial: "DEV0".into(),
+            processes: Ok(vec![
+                process(10, "zygote", 5000, 90.0),
+                process(30, "adbd", 1000, 2.0),
+            ]),
+        });
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::KillProcess { pid: 30, .. }))
+        );
+    }
+
+    #[test]
+    fn kill_prompt_sends_nothing_once_the_process_exits() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('x')));
+        pane.update(&Msg::ProcessesUpdated {
+            serial: "DEV0".into(),
+            processes: Ok(vec![process(10, "zygote", 5000, 0.5)]),
+        });
+        assert!(pane.update(&key(KeyCode::Enter)).is_empty());
+        assert!(pane.kill_error.is_some());
+        assert!(!pane.captures_input());
+    }
+
+    #[test]
+    fn open_package_uses_process_name() {
+        let mut pane = loaded_pane();
+        let cmds = pane.update(&key(KeyCode::Char('p')));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::ShowPackage(package) if package == "com.example.app"
+        )));
+    }
+
+    #[test]
+    fn tick_waits_for_pending_refresh() {
+        let mut pane = loaded_pane();
+        pane.last_refresh = Instant::now() - PROCESS_REFRESH_INTERVAL;
+        pane.pending = true;
+        assert!(pane.update(&Msg::Tick).is_empty());
+        pane.pending = false;
+        assert!(
+            pane.update(&Msg::Tick)
+                .iter()
+                .any(|c| matches!(c, Command::RefreshProcesses(s) if s == "DEV0"))
+        );
+    }
+
+    #[test]
+    fn format_kb_units() {
+        assert_eq!(format_kb(512), "512K");
+        assert_eq!(format_kb(2048), "2.0M");
+        assert_eq!(format_kb(3 * 1024 * 1024), "3.0G");
+    }
+}
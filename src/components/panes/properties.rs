@@ -0,0 +1,741 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::{
+        device::{Device, DeviceState},
+        properties::{PropDiff, PropGroup, is_writable},
+    },
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum PropertiesAction {
+    Up,
+    Down,
+    Filter,
+    CycleGroup,
+    Edit,
+    Diff,
+    Refresh,
+    Back,
+}
+
+impl FromStr for PropertiesAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Filter" => Ok(Self::Filter),
+            "CycleGroup" => Ok(Self::CycleGroup),
+            "Edit" => Ok(Self::Edit),
+            "Diff" => Ok(Self::Diff),
+            "Refresh" => Ok(Self::Refresh),
+            "Back" => Ok(Self::Back),
+            _ => Err(()),
+        }
+    }
+}
+
+struct PropEdit {
+    key: String,
+    value: TextInput,
+}
+
+struct DiffView {
+    left: Device,
+    right: Device,
+    rows: Option<Result<Vec<PropDiff>, String>>,
+    selected_index: usize,
+}
+
+pub struct PropertiesPane {
+    device: Option<Device>,
+    /// Every connected device, for choosing the other side of a diff.
+    devices: Vec<Device>,
+    props: BTreeMap<String, String>,
+    error: Option<String>,
+    loaded: bool,
+    group: PropGroup,
+    filter: TextInput,
+    filtering: bool,
+    selected_index: usize,
+    edit: Option<PropEdit>,
+    /// Index into `diff_candidates()` while choosing the device to diff against.
+    diff_picker: Option<usize>,
+    diff: Option<DiffView>,
+    /// One-line hint shown until the next key press.
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl PropertiesPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            device: None,
+            devices: Vec::new(),
+            props: BTreeMap::new(),
+            error: None,
+            loaded: false,
+            group: PropGroup::All,
+            filter: TextInput::new(),
+            filtering: false,
+            selected_index: 0,
+            edit: None,
+            diff_picker: None,
+            diff: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn matches_filter(&self, key: &str, value: Option<&str>) -> bool {
+        let needle = self.filter.value().to_lowercase();
+        needle.is_empty()
+            || key.to_lowercase().contains(&needle)
+            || value.is_some_and(|v| v.to_lowercase().contains(&needle))
+    }
+
+    fn visible_props(&self) -> Vec<(&String, &String)> {
+        self.props
+            .iter()
+            .filter(|(k, v)| self.group.contains(k) && self.matches_filter(k, Some(v)))
+            .collect()
+    }
+
+    fn visible_diff(&self) -> Vec<&PropDiff> {
+        match self.diff.as_ref().and_then(|d| d.rows.as_ref()) {
+            Some(Ok(rows)) => rows
+                .iter()
+                .filter(|d| self.group.contains(&d.key) && self.matches_filter(&d.key, None))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn diff_candidates(&self) -> Vec<&Device> {
+        let serial = self.device.as_ref().map(|d| d.serial.as_str());
+        self.devices
+            .iter()
+            .filter(|d| d.state == DeviceState::Online && Some(d.serial.as_str()) != serial)
+            .collect()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible_props().len();
+        self.selected_index = self.selected_index.min(len.saturating_sub(1));
+        let diff_len = self.visible_diff().len();
+        if let Some(ref mut diff) = self.diff {
+            diff.selected_index = diff.selected_index.min(diff_len.saturating_sub(1));
+        }
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.device {
+            Some(ref device) => vec![Command::RefreshProperties(device.serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_filter_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.filter.handle_key(key) {
+            InputOutcome::Submit => self.filtering = false,
+            InputOutcome::Cancel => {
+                self.filtering = false;
+                self.filter.clear();
+            }
+            InputOutcome::Changed => {
+                self.selected_index = 0;
+                if let Some(ref mut diff) = self.diff {
+                    diff.selected_index = 0;
+                }
+            }
+            InputOutcome::Ignored => {}
+        }
+        self.clamp_selection();
+        Vec::new()
+    }
+
+    fn handle_edit_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(edit)) = (msg, self.edit.as_mut()) else {
+            return Vec::new();
+        };
+        match edit.value.handle_key(key) {
+            InputOutcome::Submit => {
+                let edit = self.edit.take().expect("edit is open");
+                if let Some(ref device) = self.device {
+                    return vec![Command::SetProperty {
+                        serial: device.serial.clone(),
+                        key: edit.key,
+                        value: edit.value.value().to_string(),
+                    }];
+                }
+            }
+            InputOutcome::Cancel => self.edit = None,
+            InputOutcome::Changed | InputOutcome::Ignored => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_diff_picker_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(index)) = (msg, self.diff_picker) else {
+            return Vec::new();
+        };
+        let candidates = self.diff_candidates().len();
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.diff_picker = Some(index.saturating_sub(1));
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.diff_picker = Some((index + 1).min(candidates.saturating_sub(1)));
+            }
+            KeyCode::Esc => self.diff_picker = None,
+            KeyCode::Enter => {
+                self.diff_picker = None;
+                let right = self.diff_candidates().get(index).map(|d| (*d).clone());
+                if let (Some(left), Some(right)) = (self.device.clone(), right) {
+                    let cmd = Command::DiffProperties {
+                        left: left.serial.clone(),
+                        right: right.serial.clone(),
+                    };
+                    self.diff = Some(DiffView {
+                        left,
+                        right,
+                        rows: None,
+                        selected_index: 0,
+                    });
+                    return vec![cmd];
+                }
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: PropertiesAction) -> Vec<Command> {
+        let diff_len = self.visible_diff().len();
+        if let Some(ref mut diff) = self.diff {
+            match action {
+                PropertiesAction::Up => {
+                    diff.selected_index = diff.selected_index.saturating_sub(1);
+                }
+                PropertiesAction::Down => {
+                    diff.selected_index = (diff.selected_index + 1).min(diff_len.saturating_sub(1));
+                }
+                PropertiesAction::Filter => self.filtering = true,
+                PropertiesAction::CycleGroup => {
+                    self.group = self.group.next();
+                    diff.selected_index = 0;
+                }
+                PropertiesAction::Refresh => {
+                    diff.rows = None;
+                    return vec![Command::DiffProperties {
+                        left: diff.left.serial.clone(),
+                        right: diff.right.serial.clone(),
+                    }];
+                }
+                PropertiesAction::Back => self.diff = None,
+                PropertiesAction::Edit | PropertiesAction::Diff => {}
+            }
+            return Vec::new();
+        }
+
+        match action {
+            PropertiesAction::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            PropertiesAction::Down => {
+                let len = self.visible_props().len();
+                if len > 0 {
+                    self.selected_index = (self.selected_index + 1).min(len - 1);
+                }
+            }
+            PropertiesAction::Filter => self.filtering = true,
+            PropertiesAction::CycleGroup => {
+                self.group = self.group.next();
+                self.selected_index = 0;
+            }
+            PropertiesAction::Edit => {
+                let selected = self
+                    .visible_props()
+                    .get(self.selected_index)
+                    .map(|(k, v)| (k.to_string(), v.to_string()));
+                match selected {
+                    Some((key, _)) if !is_writable(&key) => {
+                        self.notice = Some(format!("{key} is read-only"));
+                    }
+                    Some((key, value)) => {
+                        self.edit = Some(PropEdit {
+                            key,
+                            value: TextInput::with_value(&value),
+                        });
+                    }
+                    None => {}
+                }
+            }
+            PropertiesAction::Diff => {
+                if self.device.is_none() {
+                    return Vec::new();
+                }
+                if self.diff_candidates().is_empty() {
+                    self.notice = Some("Connect a second device to diff against".into());
+                } else {
+                    self.diff_picker = Some(0);
+                }
+            }
+            PropertiesAction::Refresh => return self.refresh_command(),
+            PropertiesAction::Back => {}
+        }
+        Vec::new()
+    }
+
+    fn draw_groups(&self, frame: &mut Frame, area: Rect) {
+        let mut spans = Vec::new();
+        for group in PropGroup::ALL {
+            let count = self.props.keys().filter(|k| group.contains(k)).count();
+            let style = if group == self.group {
+                Style::default().fg(Color::Black).bg(Color::Green)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(
+                format!(" {} {} ", group.label(), count),
+                style,
+            ));
+            spans.push(Span::raw(" "));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn draw_props(&self, frame: &mut Frame, area: Rect) {
+        let props = self.visible_props();
+        if props.is_empty() {
+            let paragraph = match (&self.device, &self.error, self.loaded) {
+                (None, _, _) => Paragraph::new("Select a device to begin"),
+                (Some(_), Some(e), _) => {
+                    Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+                }
+                (Some(_), None, false) => Paragraph::new("Loading properties..."),
+                (Some(_), None, true) => Paragraph::new("(no matching properties)"),
+            };
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let rows: Vec<Row> = props
+            .iter()
+            .map(|(key, value)| {
+                let key_style = if is_writable(key) {
+                    Style::default().fg(Color::White)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Row::new([
+                    Span::styled(key.to_string(), key_style),
+                    Span::raw(value.to_string()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .header(Row::new(["KEY", "VALUE"]).style(Style::default().fg(Color::DarkGray)))
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn draw_diff(&self, frame: &mut Frame, area: Rect, diff: &DiffView) {
+        let rows = self.visible_diff();
+        let body = match diff.rows {
+            None => Some(Paragraph::new("Loading properties from both devices...")),
+            Some(Err(ref e)) => {
+                Some(Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)))
+            }
+            Some(Ok(_)) if rows.is_empty() => Some(Paragraph::new("(no differences)")),
+            Some(Ok(_)) => None,
+        };
+        if let Some(body) = body {
+            frame.render_widget(body, area);
+            return;
+        }
+
+        let unset = || Span::styled("<unset>", Style::default().fg(Color::DarkGray));
+        let table_rows: Vec<Row> = rows
+            .iter()
+            .map(|d| {
+                Row::new([
+                    Span::raw(d.key.clone()),
+                    d.left.clone().map(Span::raw).unwrap_or_else(unset),
+                    d.right.clone().map(Span::raw).unwrap_or_else(unset),
+                ])
+            })
+            .collect();
+        let header = Row::new([
+            "KEY".to_string(),
+            diff.left.display_name(),
+            diff.right.display_name(),
+        ])
+        .style(Style::default().fg(Color::DarkGray));
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = TableState::default().with_selected(Some(diff.selected_index));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn prompt_line(&self) -> Option<Line<'static>> {
+        if let Some(ref edit) = self.edit {
+            return Some(edit.value.line(&format!(" setprop {} ", edit.key), true));
+        }
+        if let Some(index) = self.diff_picker {
+            let mut spans = vec![Span::styled(
+                " Diff with: ",
+                Style::default().fg(Color::Yellow),
+            )];
+            for (i, device) in self.diff_candidates().iter().enumerate() {
+                let style = if i == index {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                spans.push(Span::styled(format!(" {} ", device.display_name()), style));
+            }
+            spans.push(Span::styled(
+                "  ←/→ choose, Enter diff, Esc cancel",
+                Style::default().fg(Color::DarkGray),
+            ));
+            return Some(Line::from(spans));
+        }
+        self.notice.as_ref().map(|n| {
+            Line::from(Span::styled(
+                format!(" {n}"),
+                Style::default().fg(Color::Yellow),
+            ))
+        })
+    }
+}
+
+impl Component for PropertiesPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) {
+            self.notice = None;
+            if self.edit.is_some() {
+                return self.handle_edit_key(msg);
+            }
+            if self.diff_picker.is_some() {
+                return self.handle_diff_picker_key(msg);
+            }
+            if self.filtering {
+                return self.handle_filter_key(msg);
+            }
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| PropertiesAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DevicesUpdated(devices) => {
+                self.devices = devices.clone();
+                Vec::new()
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| &d.serial);
+                if new_serial == self.device.as_ref().map(|d| &d.serial) {
+                    return Vec::new();
+                }
+                self.device = device.clone();
+                self.props.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.edit = None;
+                self.diff_picker = None;
+                self.diff = None;
+                self.refresh_command()
+            }
+            Msg::PropertiesUpdated { serial, props } => {
+                if self.device.as_ref().map(|d| &d.serial) != Some(serial) {
+                    return Vec::new();
+                }
+                self.loaded = true;
+                match props {
+                    Ok(props) => {
+                        self.props = props.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            Msg::PropertyDiff { left, right, diff } => {
+                if let Some(ref mut view) = self.diff
+                    && view.left.serial == *left
+                    && view.right.serial == *right
+                {
+                    view.rows = Some(diff.clone());
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Properties;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = match self.diff {
+            Some(ref diff) => format!(
+                " PROPERTIES: {} ↔ {} ",
+                diff.left.display_name(),
+                diff.right.display_name()
+            ),
+            None => " PROPERTIES ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let prompt = self.prompt_line();
+        let rows = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(if prompt.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+
+        self.draw_groups(frame, rows[0]);
+        frame.render_widget(
+            Paragraph::new(self.filter.line(" / ", self.filtering)),
+            rows[1],
+        );
+        match self.diff {
+            Some(ref diff) => self.draw_diff(frame, rows[2], diff),
+            None => self.draw_props(frame, rows[2]),
+        }
+        if let Some(prompt) = prompt {
+            frame.render_widget(Paragraph::new(prompt), rows[3]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Properties"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.filtering || self.edit.is_some() || self.diff_picker.is_some()
+    }
+
+    fn back(&mut self) -> bool {
+        self.diff.take().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::ConnectionType;
+
+    fn make_keymap() -> SectionKeymap {
+        let mut keymap = SectionKeymap::new();
+        let bind = |c: char| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        keymap.insert(bind('j'), "Down".into());
+        keymap.insert(bind('k'), "Up".into());
+        keymap.insert(bind('/'), "Filter".into());
+        keymap.insert(bind('g'), "CycleGroup".into());
+        keymap.insert(bind('e'), "Edit".into());
+        keymap.insert(bind('d'), "Diff".into());
+        keymap
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn device(serial: &str) -> Device {
+        Device {
+            serial: serial.into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        }
+    }
+
+    fn loaded_pane() -> PropertiesPane {
+        let mut pane = PropertiesPane::new(make_keymap());
+        pane.update(&Msg::DeviceSelected(Some(device("DEV0"))));
+        let props = [
+            ("debug.hwui.profile", "false"),
+            ("persist.sys.locale", "en-US"),
+            ("ro.build.type", "userdebug"),
+            ("sys.boot_completed", "1"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        pane.update(&Msg::PropertiesUpdated {
+            serial: "DEV0".into(),
+            props: Ok(props),
+        });
+        pane
+    }
+
+    #[test]
+    fn group_and_filter_narrow_list() {
+        let mut pane = loaded_pane();
+        assert_eq!(pane.visible_props().len(), 4);
+
+        pane.update(&key(KeyCode::Char('g')));
+        assert_eq!(pane.group, PropGroup::ReadOnly);
+        assert_eq!(pane.visible_props().len(), 1);
+
+        pane.group = PropGroup::All;
+        pane.update(&key(KeyCode::Char('/')));
+        for c in "en-us".chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+        let visible = pane.visible_props();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].0, "persist.sys.locale");
+    }
+
+    #[test]
+    fn edit_submits_setprop() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('e')));
+        assert!(pane.captures_input());
+        for _ in 0.."false".len() {
+            pane.update(&key(KeyCode::Backspace));
+        }
+        for c in "true".chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::SetProperty { serial, key, value }
+                if serial == "DEV0" && key == "debug.hwui.profile" && value == "true"
+        )));
+        assert!(!pane.captures_input());
+    }
+
+    #[test]
+    fn read_only_properties_are_not_editable() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('j')));
+        pane.update(&key(KeyCode::Char('j')));
+        pane.update(&key(KeyCode::Char('e')));
+        assert!(pane.edit.is_none());
+        assert!(pane.notice.is_some());
+    }
+
+    #[test]
+    fn diff_picks_other_online_device() {
+        let mut pane = loaded_pane();
+        let mut offline = device("DEV2");
+        offline.state = DeviceState::Offline;
+        pane.update(&Msg::DevicesUpdated(vec![
+            device("DEV0"),
+            device("DEV1"),
+            offline,
+        ]));
+        pane.update(&key(KeyCode::Char('d')));
+        assert_eq!(pane.diff_candidates().len(), 1);
+
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::DiffProperties { left, right } if left == "DEV0" && right == "DEV1"
+        )));
+
+        pane.update(&Msg::PropertyDiff {
+            left: "DEV0".into(),
+            right: "DEV1".into(),
+            diff: Ok(vec![PropDiff {
+                key: "ro.build.type".into(),
+                left: Some("userdebug".into()),
+                right: Some("user".into()),
+            }]),
+        });
+        assert_eq!(pane.visible_diff().len(), 1);
+        assert!(pane.back());
+        assert!(pane.diff.is_none());
+    }
+
+    #[test]
+    fn diff_needs_a_second_device() {
+        let mut pane = loaded_pane();
+        pane.update(&Msg::DevicesUpdated(vec![device("DEV0")]));
+        pane.update(&key(KeyCode::Char('d')));
+        assert!(pane.diff_picker.is_none());
+        assert!(pane.notice.is_some());
+    }
+
+    #[test]
+    fn stale_properties_are_ignored() {
+        let mut pane = loaded_pane();
+        pane.update(&Msg::PropertiesUpdated {
+            serial: "OTHER".into(),
+            props: Ok(BTreeMap::new()),
+        });
+        assert_eq!(pane.visible_props().len(), 4);
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    adb::{
+        device::Device,
+        input::{InputEvent, is_keycode, paste_events},
+    },
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::{SectionKeymap, key_event_to_string},
+    msg::Msg,
+};
+
+/// Recent events kept for the on-screen log.
+const LOG_LEN: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+enum RemoteAction {
+    Start,
+    Exit,
+    /// Sends the keycode named in the binding, e.g. `KEYCODE_HOME`.
+    Key(String),
+}
+
+impl FromStr for RemoteAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Start" => Ok(Self::Start),
+            "Exit" => Ok(Self::Exit),
+            _ if is_keycode(s) => Ok(Self::Key(s.to_string())),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct RemotePane {
+    device: Option<Device>,
+    /// Keys go to the device instead of lazyadb.
+    controlling: bool,
+    log: VecDeque<String>,
+    error: Option<String>,
+    keymap: SectionKeymap,
+    /// Bindings while controlling; unbound printable keys are typed as text.
+    control_keymap: SectionKeymap,
+}
+
+impl RemotePane {
+    pub fn new(keymap: SectionKeymap, control_keymap: SectionKeymap) -> Self {
+        Self {
+            device: None,
+            controlling: false,
+            log: VecDeque::new(),
+            error: None,
+            keymap,
+            control_keymap,
+        }
+    }
+
+    fn send(&mut self, event: InputEvent) -> Vec<Command> {
+        let Some(ref device) = self.device else {
+            return Vec::new();
+        };
+        let serial = device.serial.clone();
+        let entry = match event {
+            InputEvent::Key(ref code) => code.clone(),
+            InputEvent::Text(ref text) => format!("text {text:?}"),
+            InputEvent::ImeText(ref text) => format!("ime {text:?} (needs ADB Keyboard)"),
+            InputEvent::Tap(x, y) => format!("tap {x} {y}"),
+        };
+        self.log_entry(entry);
+        vec![Command::SendInput { serial, event }]
+    }
+
+    fn log_entry(&mut self, entry: String) {
+        if self.log.len() == LOG_LEN {
+            self.log.pop_front();
+        }
+        self.log.push_back(entry);
+    }
+
+    fn handle_action(&mut self, action: RemoteAction) -> Vec<Command> {
+        match action {
+            RemoteAction::Start => {
+                self.controlling = self.device.is_some();
+                self.error = None;
+            }
+            RemoteAction::Exit => self.controlling = false,
+            RemoteAction::Key(code) => return self.send(InputEvent::Key(code)),
+        }
+        Vec::new()
+    }
+
+    /// The control bindings as `key  KEYCODE` lines, sorted by keycode.
+    fn binding_lines(&self) -> Vec<Line<'static>> {
+        let mut bindings: Vec<(String, &String)> = self
+            .control_keymap
+            .iter()
+            .map(|(keys, action)| {
+                let keys: Vec<String> = keys
+                    .iter()
+                    .map(|k| format!("<{}>", key_event_to_string(k)))
+                    .collect();
+                (keys.join(""), action)
+            })
+            .collect();
+        bindings.sort_by(|a, b| a.1.cmp(b.1).then_with(|| a.0.cmp(&b.0)));
+        bindings
+            .into_iter()
+            .map(|(key, action)| {
+                let action = action.strip_prefix("KEYCODE_").unwrap_or(action);
+                Line::from(vec![
+                    Span::styled(format!(" {key:<14}"), Style::default().fg(Color::Cyan)),
+                    Span::raw(action.to_string()),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Component for RemotePane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) if self.controlling => {
+                let action = self
+                    .control_keymap
+                    .get(&vec![*key])
+                    .and_then(|s| RemoteAction::from_str(s).ok());
+                match (action, key.code) {
+                    (Some(action), _) => self.handle_action(action),
+                    (None, KeyCode::Char(c))
+                        if !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                    {
+                        self.send(InputEvent::Text(c.to_string()))
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| RemoteAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::Paste(text) if self.controlling => {
+                let Some(serial) = self.device.as_ref().map(|d| d.serial.clone()) else {
+                    return Vec::new();
+                };
+                let events = paste_events(text);
+                if events.is_empty() {
+                    return Vec::new();
+                }
+                self.log_entry(format!("paste {text:?}"));
+                vec![Command::PasteInput { serial, events }]
+            }
+            Msg::DeviceSelected(device) => {
+                if device.as_ref().map(|d| &d.serial) != self.device.as_ref().map(|d| &d.serial) {
+                    self.controlling = false;
+                    self.log.clear();
+                    self.error = None;
+                }
+                self.device = device.clone();
+                Vec::new()
+            }
+            Msg::InputFailed { serial, error } => {
+                if self.device.as_ref().is_some_and(|d| &d.serial == serial) {
+                    self.error = Some(error.clone());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Remote;
+        let border_color = if !focused {
+            Color::DarkGray
+        } else if self.controlling {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" REMOTE CONTROL ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(ref device) = self.device else {
+            let msg = Paragraph::new(" Select a device to begin")
+                .style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(msg, inner);
+            return;
+        };
+
+        let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(inner);
+        let status = if let Some(ref e) = self.error {
+            Line::from(Span::styled(
+                format!(" {e}"),
+                Style::default().fg(Color::Red),
+            ))
+        } else if self.controlling {
+            Line::from(vec![
+                Span::styled(
+                    format!(" Controlling {}", device.display_name()),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    " - typing goes to the device, Esc to stop",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        } else {
+            Line::from(Span::styled(
+                format!(" Press Enter to control {}", device.display_name()),
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+        frame.render_widget(Paragraph::new(status), rows[0]);
+
+        let columns =
+            Layout::horizontal([Constraint::Length(34), Constraint::Min(0)]).split(rows[1]);
+        frame.render_widget(
+            Paragraph::new(self.binding_lines()).block(
+                Block::default()
+                    .borders(Borders::RIGHT)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+            columns[0],
+        );
+
+        let height = columns[1].height as usize;
+        let log: Vec<Line> = self
+            .log
+            .iter()
+            .skip(self.log.len().saturating_sub(height))
+            .map(|entry| Line::from(format!(" {entry}")))
+            .collect();
+        frame.render_widget(Paragraph::new(log), columns[1]);
+    }
+
+    fn id(&self) -> &'static str {
+        "Remote"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.controlling
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyEvent;
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, DeviceState};
+
+    fn make_pane() -> RemotePane {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())],
+            "Start".into(),
+        );
+        let mut control = SectionKeymap::new();
+        control.insert(
+            vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())],
+            "KEYCODE_DPAD_CENTER".into(),
+        );
+        control.insert(
+            vec![KeyEvent::new(KeyCode::Esc, KeyModifiers::empty())],
+            "Exit".into(),
+        );
+        let mut pane = RemotePane::new(keymap, control);
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "TV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Tcp,
+        })));
+        pane
+    }
+
+    fn press(pane: &mut RemotePane, code: KeyCode, modifiers: KeyModifiers) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(code, modifiers)))
+    }
+
+    fn sent(cmds: &[Command]) -> Vec<InputEvent> {
+        cmds.iter()
+            .flat_map(|c| match c {
+                Command::SendInput { serial, event } if serial == "TV0" => vec![event.clone()],
+                Command::PasteInput { serial, events } if serial == "TV0" => events.clone(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keys_reach_the_device_only_while_controlling() {
+        let mut pane = make_pane();
+        assert!(sent(&press(&mut pane, KeyCode::Char('a'), KeyModifiers::empty())).is_empty());
+
+        press(&mut pane, KeyCode::Enter, KeyModifiers::empty());
+        assert!(pane.captures_input());
+
+        let cmds = press(&mut pane, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(sent(&cmds), [InputEvent::Key("KEYCODE_DPAD_CENTER".into())]);
+        let cmds = press(&mut pane, KeyCode::Char('Q'), KeyModifiers::SHIFT);
+        assert_eq!(sent(&cmds), [InputEvent::Text("Q".into())]);
+        assert!(press(&mut pane, KeyCode::Char('c'), KeyModifiers::CONTROL).is_empty());
+
+        press(&mut pane, KeyCode::Esc, KeyModifiers::empty());
+        assert!(!pane.captures_input());
+        assert_eq!(pane.log.len(), 2);
+    }
+
+    #[test]
+    fn pastes_are_typed_while_controlling() {
+        let mut pane = make_pane();
+        let paste = Msg::Paste("https://example.com/?q=a b\n".into());
+        assert!(pane.update(&paste).is_empty());
+
+        press(&mut pane, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(
+            sent(&pane.update(&paste)),
+            [InputEvent::Text("https://example.com/?q=a b".into())]
+        );
+    }
+
+    #[test]
+    fn bindings_must_name_a_keycode() {
+        assert_eq!(
+            RemoteAction::from_str("KEYCODE_VOLUME_UP"),
+            Ok(RemoteAction::Key("KEYCODE_VOLUME_UP".into()))
+        );
+        assert!(RemoteAction::from_str("VolumeUp").is_err());
+    }
+}
@@ -0,0 +1,321 @@
+use std::cell::{Cell, RefCell};
+use std::str::FromStr;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    adb::{capture::Screenshot, device::Device},
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    graphics::{GraphicsProtocol, half_block_lines},
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScreenshotAction {
+    Capture,
+}
+
+impl FromStr for ScreenshotAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Capture" => Ok(Self::Capture),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A half-block rendering of the screenshot at one pane size.
+struct HalfBlocks {
+    size: (u16, u16),
+    lines: Vec<Line<'static>>,
+}
+
+pub struct ScreenshotPane {
+    device: Option<Device>,
+    shot: Option<Screenshot>,
+    error: Option<String>,
+    capturing: bool,
+    protocol: GraphicsProtocol,
+    /// Cells left blank for a kitty or sixel image on the last draw; the app
+    /// paints the image there once the frame is flushed.
+    image_area: Cell<Option<Rect>>,
+    /// Kept so the full-resolution image is only scaled down again when the
+    /// screenshot or the pane size changes, not on every frame.
+    half_blocks: RefCell<Option<HalfBlocks>>,
+    keymap: SectionKeymap,
+}
+
+impl ScreenshotPane {
+    pub fn new(keymap: SectionKeymap, protocol: GraphicsProtocol) -> Self {
+        Self {
+            device: None,
+            shot: None,
+            error: None,
+            capturing: false,
+            protocol,
+            image_area: Cell::new(None),
+            half_blocks: RefCell::new(None),
+            keymap,
+        }
+    }
+
+    /// The screenshot to paint with terminal graphics, and where.
+    pub fn graphic(&self) -> Option<(Rect, &Screenshot)> {
+        Some((self.image_area.get()?, self.shot.as_ref()?))
+    }
+
+    fn handle_action(&mut self, action: ScreenshotAction) -> Vec<Command> {
+        match action {
+            ScreenshotAction::Capture => {
+                if let Some(ref device) = self.device
+                    && !self.capturing
+                {
+                    self.capturing = true;
+                    return vec![Command::TakeScreenshot(device.serial.clone())];
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    fn header(&self) -> Line<'static> {
+        let dim = Style::default().fg(Color::DarkGray);
+        if self.capturing {
+            return Line::from(Span::styled(
+                " Capturing...",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if let Some(ref e) = self.error {
+            return Line::from(Span::styled(
+                format!(" {e}"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        match (&self.shot, &self.device) {
+            (Some(shot), _) => Line::from(vec![
+                Span::styled(
+                    format!(" {}", shot.path.display()),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}x{}", shot.image.width, shot.image.height), dim),
+            ]),
+            (None, Some(device)) => Line::from(Span::styled(
+                format!(" Press c to capture {}", device.display_name()),
+                dim,
+            )),
+            (None, None) => Line::from(Span::styled(" Select a device to begin", dim)),
+        }
+    }
+}
+
+impl Component for ScreenshotPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| ScreenshotAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                self.device = device.clone();
+                Vec::new()
+            }
+            Msg::ScreenshotTaken { serial, result } => {
+                self.capturing = false;
+                if self.device.as_ref().map(|d| &d.serial) != Some(serial) {
+                    return Vec::new();
+                }
+                match result {
+                    Ok(shot) => {
+                        self.shot = Some(shot.clone());
+                        *self.half_blocks.get_mut() = None;
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Screenshot;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" SCREENSHOT ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(inner);
+        frame.render_widget(Paragraph::new(self.header()), rows[0]);
+
+        self.image_area.set(None);
+        let Some(ref shot) = self.shot else {
+            return;
+        };
+        match self.protocol {
+            GraphicsProtocol::HalfBlocks => {
+                let size = (rows[1].width, rows[1].height);
+                let mut cache = self.half_blocks.borrow_mut();
+                let blocks = match cache.take() {
+                    Some(blocks) if blocks.size == size => blocks,
+                    _ => HalfBlocks {
+                        size,
+                        lines: half_block_lines(&shot.image, size.0, size.1),
+                    },
+                };
+                frame.render_widget(Paragraph::new(blocks.lines.clone()), rows[1]);
+                *cache = Some(blocks);
+            }
+            GraphicsProtocol::Kitty | GraphicsProtocol::Sixel => {
+                self.image_area.set(Some(rows[1]));
+            }
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Screenshot"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::device::{ConnectionType, DeviceState};
+    use crate::graphics::RgbaImage;
+
+    fn make_pane(protocol: GraphicsProtocol) -> ScreenshotPane {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty())],
+            "Capture".into(),
+        );
+        let mut pane = ScreenshotPane::new(keymap, protocol);
+        pane.update(&Msg::DeviceSelected(Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: None,
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        })));
+        pane
+    }
+
+    fn taken() -> Msg {
+        Msg::ScreenshotTaken {
+            serial: "DEV0".into(),
+            result: Ok(Screenshot {
+                path: "/tmp/DEV0.png".into(),
+                image: RgbaImage {
+                    width: 2,
+                    height: 2,
+                    pixels: vec![255; 16],
+                },
+            }),
+        }
+    }
+
+    #[test]
+    fn capture_is_not_repeated_while_running() {
+        let mut pane = make_pane(GraphicsProtocol::HalfBlocks);
+        let c = Msg::KeyPress(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::empty()));
+        let cmds = pane.update(&c);
+        assert!(
+            cmds.iter()
+                .any(|cmd| matches!(cmd, Command::TakeScreenshot(s) if s == "DEV0"))
+        );
+        assert!(pane.update(&c).is_empty());
+
+        pane.update(&taken());
+        assert!(!pane.capturing);
+        assert!(pane.shot.is_some());
+    }
+
+    #[test]
+    fn graphics_area_is_only_reserved_for_image_protocols() {
+        let draw = |pane: &ScreenshotPane| {
+            let backend = ratatui::backend::TestBackend::new(40, 20);
+            let mut terminal = ratatui::Terminal::new(backend).unwrap();
+            let config = crate::config::Config::default();
+            let metrics = std::collections::HashMap::new();
+            let ctx = DrawContext {
+                focus: Pane::Screenshot,
+                config: &config,
+                metrics: &metrics,
+            };
+            terminal
+                .draw(|frame| pane.draw(frame, frame.area(), &ctx))
+                .unwrap();
+        };
+
+        let mut kitty = make_pane(GraphicsProtocol::Kitty);
+        kitty.update(&taken());
+        draw(&kitty);
+        let (area, _) = kitty.graphic().expect("kitty reserves an area");
+        assert_eq!(area, Rect::new(1, 3, 38, 16));
+
+        let mut blocks = make_pane(GraphicsProtocol::HalfBlocks);
+        blocks.update(&taken());
+        draw(&blocks);
+        assert!(blocks.graphic().is_none());
+    }
+
+    #[test]
+    fn half_blocks_are_rebuilt_only_for_a_new_size_or_shot() {
+        let draw = |pane: &ScreenshotPane, width: u16| {
+            let backend = ratatui::backend::TestBackend::new(width, 20);
+            let mut terminal = ratatui::Terminal::new(backend).unwrap();
+            let config = crate::config::Config::default();
+            let metrics = std::collections::HashMap::new();
+            let ctx = DrawContext {
+                focus: Pane::Screenshot,
+                config: &config,
+                metrics: &metrics,
+            };
+            terminal
+                .draw(|frame| pane.draw(frame, frame.area(), &ctx))
+                .unwrap();
+        };
+        let cached_size =
+            |pane: &ScreenshotPane| pane.half_blocks.borrow().as_ref().map(|b| b.size);
+
+        let mut pane = make_pane(GraphicsProtocol::HalfBlocks);
+        pane.update(&taken());
+        draw(&pane, 40);
+        assert_eq!(cached_size(&pane), Some((38, 16)));
+        draw(&pane, 30);
+        assert_eq!(cached_size(&pane), Some((28, 16)));
+
+        pane.update(&taken());
+        assert_eq!(cached_size(&pane), None);
+    }
+}
@@ -0,0 +1,697 @@
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use crate::{
+    adb::settings::{RevertLog, SettingChange, SettingsNamespace},
+    command::Command,
+    components::{
+        Component, DrawContext,
+        input::{InputOutcome, TextInput},
+        panes::Pane,
+    },
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum SettingsAction {
+    Up,
+    Down,
+    Filter,
+    SwitchTab,
+    Edit,
+    Put,
+    Delete,
+    RevertAll,
+    Refresh,
+}
+
+impl FromStr for SettingsAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Filter" => Ok(Self::Filter),
+            "SwitchTab" => Ok(Self::SwitchTab),
+            "Edit" => Ok(Self::Edit),
+            "Put" => Ok(Self::Put),
+            "Delete" => Ok(Self::Delete),
+            "RevertAll" => Ok(Self::RevertAll),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PendingChange {
+    Put { key: String, value: String },
+    Delete { key: String },
+    RevertAll,
+}
+
+enum Prompt {
+    NewKey(TextInput),
+    Value { key: String, input: TextInput },
+    Confirm(PendingChange),
+}
+
+pub struct SettingsPane {
+    serial: Option<String>,
+    namespace: SettingsNamespace,
+    settings: BTreeMap<String, String>,
+    error: Option<String>,
+    loaded: bool,
+    filter: TextInput,
+    filtering: bool,
+    selected_index: usize,
+    prompt: Option<Prompt>,
+    /// Original values of everything changed from this pane, per device serial.
+    revert_logs: HashMap<String, RevertLog>,
+    keymap: SectionKeymap,
+}
+
+impl SettingsPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            namespace: SettingsNamespace::System,
+            settings: BTreeMap::new(),
+            error: None,
+            loaded: false,
+            filter: TextInput::new(),
+            filtering: false,
+            selected_index: 0,
+            prompt: None,
+            revert_logs: HashMap::new(),
+            keymap,
+        }
+    }
+
+    fn visible_settings(&self) -> Vec<(&String, &String)> {
+        let needle = self.filter.value().to_lowercase();
+        self.settings
+            .iter()
+            .filter(|(k, _)| needle.is_empty() || k.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    fn selected_key(&self) -> Option<String> {
+        self.visible_settings()
+            .get(self.selected_index)
+            .map(|(k, _)| k.to_string())
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible_settings().len();
+        self.selected_index = self.selected_index.min(len.saturating_sub(1));
+    }
+
+    fn revert_count(&self) -> usize {
+        self.serial
+            .as_ref()
+            .and_then(|s| self.revert_logs.get(s))
+            .map_or(0, RevertLog::len)
+    }
+
+    fn refresh_command(&mut self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshSettings {
+                serial: serial.clone(),
+                namespace: self.namespace,
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, change: PendingChange) -> Vec<Command> {
+        let Some(serial) = self.serial.clone() else {
+            return Vec::new();
+        };
+        // Recorded in the revert log once the device confirms the change
+        let change_of = |key: String| SettingChange {
+            namespace: self.namespace,
+            original: self.settings.get(&key).cloned(),
+            key,
+        };
+        match change {
+            PendingChange::Put { key, value } => vec![Command::PutSetting {
+                serial,
+                change: change_of(key),
+                value,
+            }],
+            PendingChange::Delete { key } => vec![Command::DeleteSetting {
+                serial,
+                change: change_of(key),
+            }],
+            PendingChange::RevertAll => {
+                let changes = self
+                    .revert_logs
+                    .get(&serial)
+                    .map(RevertLog::changes)
+                    .unwrap_or_default();
+                if changes.is_empty() {
+                    return Vec::new();
+                }
+                vec![Command::RevertSettings { serial, changes }]
+            }
+        }
+    }
+
+    fn handle_filter_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let Msg::KeyPress(key) = msg else {
+            return Vec::new();
+        };
+        match self.filter.handle_key(key) {
+            InputOutcome::Submit => self.filtering = false,
+            InputOutcome::Cancel => {
+                self.filtering = false;
+                self.filter.clear();
+            }
+            InputOutcome::Changed => self.selected_index = 0,
+            InputOutcome::Ignored => {}
+        }
+        self.clamp_selection();
+        Vec::new()
+    }
+
+    fn handle_prompt_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(prompt)) = (msg, self.prompt.take()) else {
+            return Vec::new();
+        };
+        match prompt {
+            Prompt::NewKey(mut input) => match input.handle_key(key) {
+                InputOutcome::Submit if !input.value().trim().is_empty() => {
+                    let key = input.value().trim().to_string();
+                    let current = self.settings.get(&key).cloned().unwrap_or_default();
+                    self.prompt = Some(Prompt::Value {
+                        key,
+                        input: TextInput::with_value(&current),
+                    });
+                }
+                InputOutcome::Cancel => {}
+                _ => self.prompt = Some(Prompt::NewKey(input)),
+            },
+            Prompt::Value {
+                key: setting,
+                mut input,
+            } => match input.handle_key(key) {
+                InputOutcome::Submit => {
+                    self.prompt = Some(Prompt::Confirm(PendingChange::Put {
+                        key: setting,
+                        value: input.value().to_string(),
+                    }));
+                }
+                InputOutcome::Cancel => {}
+                _ => {
+                    self.prompt = Some(Prompt::Value {
+                        key: setting,
+                        input,
+                    })
+                }
+            },
+            Prompt::Confirm(change) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => return self.apply(change),
+                KeyCode::Char('n') | KeyCode::Esc => {}
+                _ => self.prompt = Some(Prompt::Confirm(change)),
+            },
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: SettingsAction) -> Vec<Command> {
+        match action {
+            SettingsAction::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            SettingsAction::Down => {
+                let len = self.visible_settings().len();
+                if len > 0 {
+                    self.selected_index = (self.selected_index + 1).min(len - 1);
+                }
+            }
+            SettingsAction::Filter => self.filtering = true,
+            SettingsAction::SwitchTab => {
+                self.namespace = self.namespace.next();
+                self.settings.clear();
+                self.loaded = false;
+                self.error = None;
+                self.selected_index = 0;
+                return self.refresh_command();
+            }
+            SettingsAction::Edit => {
+                if let Some(key) = self.selected_key() {
+                    let value = self.settings[&key].clone();
+                    self.prompt = Some(Prompt::Value {
+                        key,
+                        input: TextInput::with_value(&value),
+                    });
+                }
+            }
+            SettingsAction::Put => {
+                if self.serial.is_some() {
+                    self.prompt = Some(Prompt::NewKey(TextInput::new()));
+                }
+            }
+            SettingsAction::Delete => {
+                if let Some(key) = self.selected_key() {
+                    self.prompt = Some(Prompt::Confirm(PendingChange::Delete { key }));
+                }
+            }
+            SettingsAction::RevertAll => {
+                let has_changes = self
+                    .serial
+                    .as_ref()
+                    .and_then(|s| self.revert_logs.get(s))
+                    .is_some_and(|log| !log.is_empty());
+                if has_changes {
+                    self.prompt = Some(Prompt::Confirm(PendingChange::RevertAll));
+                }
+            }
+            SettingsAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn prompt_line(&self) -> Option<Line<'static>> {
+        let prompt = self.prompt.as_ref()?;
+        let warn = Style::default().fg(Color::Yellow);
+        let hint = Style::default().fg(Color::DarkGray);
+        let line = match prompt {
+            Prompt::NewKey(input) => input.line(&format!(" put {} key: ", self.namespace), true),
+            Prompt::Value { key, input } => {
+                input.line(&format!(" put {} {key} = ", self.namespace), true)
+            }
+            Prompt::Confirm(change) => {
+                let question = match change {
+                    PendingChange::Put { key, value } => {
+                        format!(" settings put {} {key} \"{value}\"?", self.namespace)
+                    }
+                    PendingChange::Delete { key } => {
+                        format!(" settings delete {} {key}?", self.namespace)
+                    }
+                    PendingChange::RevertAll => {
+                        format!(" Revert {} changed settings?", self.revert_count())
+                    }
+                };
+                Line::from(vec![
+                    Span::styled(question, warn),
+                    Span::styled("  y confirm, n cancel", hint),
+                ])
+            }
+        };
+        Some(line)
+    }
+}
+
+impl Component for SettingsPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if matches!(msg, Msg::KeyPress(_)) {
+            if self.prompt.is_some() {
+                return self.handle_prompt_key(msg);
+            }
+            if self.filtering {
+                return self.handle_filter_key(msg);
+            }
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| SettingsAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.settings.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.prompt = None;
+                self.refresh_command()
+            }
+            Msg::SettingsUpdated {
+                serial,
+                namespace,
+                settings,
+            } => {
+                if self.serial.as_ref() != Some(serial) || *namespace != self.namespace {
+                    return Vec::new();
+                }
+                self.loaded = true;
+                match settings {
+                    Ok(settings) => {
+                        self.settings = settings.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                self.clamp_selection();
+                Vec::new()
+            }
+            Msg::SettingChanged {
+                serial,
+                change,
+                result,
+                ..
+            } => {
+                if result.is_err() {
+                    return Vec::new();
+                }
+                self.revert_logs.entry(serial.clone()).or_default().record(
+                    change.namespace,
+                    &change.key,
+                    change.original.as_deref(),
+                );
+                if self.serial.as_ref() != Some(serial) || change.namespace != self.namespace {
+                    return Vec::new();
+                }
+                self.refresh_command()
+            }
+            Msg::SettingsReverted {
+                serial,
+                changes,
+                failed,
+            } => {
+                let reverted: Vec<_> = changes
+                    .iter()
+                    .filter(|c| !failed.contains(c))
+                    .cloned()
+                    .collect();
+                if let Some(log) = self.revert_logs.get_mut(serial) {
+                    log.forget(&reverted);
+                }
+                if self.serial.as_ref() != Some(serial) {
+                    return Vec::new();
+                }
+                self.refresh_command()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Settings;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let title = match self.revert_count() {
+            0 => " SETTINGS ".to_string(),
+            n => format!(" SETTINGS ({n} changed) "),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let prompt = self.prompt_line();
+        let rows = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(if prompt.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+
+        let mut tabs = Vec::new();
+        for namespace in SettingsNamespace::ALL {
+            let style = if namespace == self.namespace {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            tabs.push(Span::styled(format!(" {namespace} "), style));
+            tabs.push(Span::raw(" "));
+        }
+        frame.render_widget(Paragraph::new(Line::from(tabs)), rows[0]);
+        frame.render_widget(
+            Paragraph::new(self.filter.line(" / ", self.filtering)),
+            rows[1],
+        );
+
+        let settings = self.visible_settings();
+        if settings.is_empty() {
+            let paragraph = match (&self.serial, &self.error, self.loaded) {
+                (None, _, _) => Paragraph::new("Select a device to begin"),
+                (Some(_), Some(e), _) => {
+                    Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+                }
+                (Some(_), None, false) => Paragraph::new("Loading settings..."),
+                (Some(_), None, true) => Paragraph::new("(no matching settings)"),
+            };
+            frame.render_widget(paragraph, rows[2]);
+        } else {
+            let table_rows: Vec<Row> = settings
+                .iter()
+                .map(|(k, v)| Row::new([k.to_string(), v.to_string()]))
+                .collect();
+            let table = Table::new(
+                table_rows,
+                [Constraint::Percentage(45), Constraint::Percentage(55)],
+            )
+            .header(Row::new(["KEY", "VALUE"]).style(Style::default().fg(Color::DarkGray)))
+            .row_highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = TableState::default().with_selected(Some(self.selected_index));
+            frame.render_stateful_widget(table, rows[2], &mut state);
+        }
+
+        if let Some(prompt) = prompt {
+            frame.render_widget(Paragraph::new(prompt), rows[3]);
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "Settings"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.filtering || self.prompt.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn make_keymap() -> SectionKeymap {
+        let mut keymap = SectionKeymap::new();
+        let bind = |c: char| vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())];
+        keymap.insert(bind('j'), "Down".into());
+        keymap.insert(bind('/'), "Filter".into());
+        keymap.insert(bind('o'), "SwitchTab".into());
+        keymap.insert(bind('e'), "Edit".into());
+        keymap.insert(bind('n'), "Put".into());
+        keymap.insert(bind('x'), "Delete".into());
+        keymap.insert(
+            vec![KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT)],
+            "RevertAll".into(),
+        );
+        keymap
+    }
+
+    fn key(code: KeyCode) -> Msg {
+        Msg::KeyPress(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(pane: &mut SettingsPane, text: &str) {
+        for c in text.chars() {
+            pane.update(&key(KeyCode::Char(c)));
+        }
+    }
+
+    fn loaded_pane() -> SettingsPane {
+        let mut pane = SettingsPane::new(make_keymap());
+        pane.serial = Some("DEV0".into());
+        let settings = [
+            ("screen_brightness", "102"),
+            ("screen_off_timeout", "30000"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        pane.update(&Msg::SettingsUpdated {
+            serial: "DEV0".into(),
+            namespace: SettingsNamespace::System,
+            settings: Ok(settings),
+        });
+        pane
+    }
+
+    #[test]
+    fn edit_asks_for_confirmation_and_records_original() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('j')));
+        pane.update(&key(KeyCode::Char('e')));
+        assert!(pane.captures_input());
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+        )));
+        type_text(&mut pane, "60000");
+
+        let cmds = pane.update(&key(KeyCode::Enter));
+        assert!(cmds.is_empty());
+        assert!(matches!(pane.prompt, Some(Prompt::Confirm(_))));
+
+        let cmds = pane.update(&key(KeyCode::Char('y')));
+        let Some(Command::PutSetting {
+            serial,
+            change,
+            value,
+        }) = cmds.first()
+        else {
+            panic!("expected a put command");
+        };
+        assert_eq!(change.namespace, SettingsNamespace::System);
+        assert_eq!(change.key, "screen_off_timeout");
+        assert_eq!(change.original.as_deref(), Some("30000"));
+        assert_eq!(value, "60000");
+        assert_eq!(pane.revert_count(), 0);
+
+        pane.update(&Msg::SettingChanged {
+            serial: serial.clone(),
+            change: change.clone(),
+            value: Some(value.clone()),
+            result: Ok(()),
+        });
+        assert_eq!(pane.revert_count(), 1);
+    }
+
+    #[test]
+    fn failed_change_is_not_recorded() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('x')));
+        let cmds = pane.update(&key(KeyCode::Char('y')));
+        let Some(Command::DeleteSetting { serial, change }) = cmds.first() else {
+            panic!("expected a delete command");
+        };
+        pane.update(&Msg::SettingChanged {
+            serial: serial.clone(),
+            change: change.clone(),
+            value: None,
+            result: Err("permission denied".into()),
+        });
+        assert_eq!(pane.revert_count(), 0);
+    }
+
+    #[test]
+    fn declining_confirmation_changes_nothing() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('x')));
+        let cmds = pane.update(&key(KeyCode::Char('n')));
+        assert!(cmds.is_empty());
+        assert!(pane.prompt.is_none());
+        assert_eq!(pane.revert_count(), 0);
+    }
+
+    #[test]
+    fn put_new_key_reverts_by_deleting() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('n')));
+        type_text(&mut pane, "my_flag");
+        pane.update(&key(KeyCode::Enter));
+        type_text(&mut pane, "1");
+        pane.update(&key(KeyCode::Enter));
+        let cmds = pane.update(&key(KeyCode::Enter));
+        let Some(Command::PutSetting { change, .. }) = cmds.first() else {
+            panic!("expected a put command");
+        };
+        pane.update(&Msg::SettingChanged {
+            serial: "DEV0".into(),
+            change: change.clone(),
+            value: Some("1".into()),
+            result: Ok(()),
+        });
+
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char('R'),
+            KeyModifiers::SHIFT,
+        )));
+        let cmds = pane.update(&key(KeyCode::Char('y')));
+        let Some(Command::RevertSettings { serial, changes }) = cmds.first() else {
+            panic!("expected a revert command");
+        };
+        assert_eq!(serial, "DEV0");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "my_flag");
+        assert_eq!(changes[0].original, None);
+        assert_eq!(pane.revert_count(), 1);
+
+        // A failed revert keeps the change for another try
+        pane.update(&Msg::SettingsReverted {
+            serial: "DEV0".into(),
+            changes: changes.clone(),
+            failed: changes.clone(),
+        });
+        assert_eq!(pane.revert_count(), 1);
+        pane.update(&Msg::SettingsReverted {
+            serial: "DEV0".into(),
+            changes: changes.clone(),
+            failed: Vec::new(),
+        });
+        assert_eq!(pane.revert_count(), 0);
+    }
+
+    #[test]
+    fn switching_tab_loads_next_namespace() {
+        let mut pane = loaded_pane();
+        let cmds = pane.update(&key(KeyCode::Char('o')));
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::RefreshSettings {
+                namespace: SettingsNamespace::Secure,
+                ..
+            }
+        )));
+        assert!(pane.visible_settings().is_empty());
+
+        // A late system listing must not show up under the secure tab
+        pane.update(&Msg::SettingsUpdated {
+            serial: "DEV0".into(),
+            namespace: SettingsNamespace::System,
+            settings: Ok(BTreeMap::from([("a".into(), "b".into())])),
+        });
+        assert!(pane.visible_settings().is_empty());
+    }
+
+    #[test]
+    fn filter_matches_keys() {
+        let mut pane = loaded_pane();
+        pane.update(&key(KeyCode::Char('/')));
+        type_text(&mut pane, "bright");
+        assert_eq!(pane.visible_settings().len(), 1);
+    }
+}
@@ -0,0 +1,632 @@
+use std::cell::Cell;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    adb::device::Device,
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+const SCROLLBACK_LINES: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ShellAction {
+    Attach,
+    NewSession,
+    CloseSession,
+    NextSession,
+    PrevSession,
+    ScrollUp,
+    ScrollDown,
+    ReleaseFocus,
+}
+
+impl FromStr for ShellAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Attach" => Ok(Self::Attach),
+            "NewSession" => Ok(Self::NewSession),
+            "CloseSession" => Ok(Self::CloseSession),
+            "NextSession" => Ok(Self::NextSession),
+            "PrevSession" => Ok(Self::PrevSession),
+            "ScrollUp" => Ok(Self::ScrollUp),
+            "ScrollDown" => Ok(Self::ScrollDown),
+            "ReleaseFocus" => Ok(Self::ReleaseFocus),
+            _ => Err(()),
+        }
+    }
+}
+
+struct ShellTab {
+    id: usize,
+    serial: String,
+    label: String,
+    parser: vt100::Parser,
+    exited: bool,
+}
+
+pub struct ShellPane {
+    tabs: Vec<ShellTab>,
+    active: usize,
+    next_id: usize,
+    device: Option<Device>,
+    /// Inner size (rows, cols) from the last draw; sessions follow it on tick.
+    size: Cell<(u16, u16)>,
+    /// Whether keys go to the active session; set on attach, cleared when the
+    /// user leaves the shell.
+    attached: bool,
+    /// Keys typed so far that prefix a session binding such as `<Ctrl-a><d>`.
+    pending_keys: Vec<KeyEvent>,
+    /// Bindings while the pane is focused but not attached to a live session.
+    keymap: SectionKeymap,
+    /// Bindings checked while typing into a live session; everything else goes to the device.
+    session_keymap: SectionKeymap,
+}
+
+impl ShellPane {
+    pub fn new(keymap: SectionKeymap, session_keymap: SectionKeymap) -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+            next_id: 0,
+            device: None,
+            size: Cell::new((24, 80)),
+            attached: false,
+            pending_keys: Vec::new(),
+            keymap,
+            session_keymap,
+        }
+    }
+
+    fn active_tab(&self) -> Option<&ShellTab> {
+        self.tabs.get(self.active)
+    }
+
+    fn active_tab_mut(&mut self) -> Option<&mut ShellTab> {
+        self.tabs.get_mut(self.active)
+    }
+
+    fn handle_action(&mut self, action: ShellAction) -> Vec<Command> {
+        match action {
+            ShellAction::Attach if self.active_tab().is_some_and(|t| !t.exited) => {
+                self.attached = true;
+            }
+            ShellAction::Attach | ShellAction::NewSession => {
+                let Some(ref device) = self.device else {
+                    return Vec::new();
+                };
+                let (rows, cols) = self.size.get();
+                let id = self.next_id;
+                self.next_id += 1;
+                let same_device = self
+                    .tabs
+                    .iter()
+                    .filter(|t| t.serial == device.serial)
+                    .count();
+                let label = match same_device {
+                    0 => device.display_name(),
+                    n => format!("{} #{}", device.display_name(), n + 1),
+                };
+                self.tabs.push(ShellTab {
+                    id,
+                    serial: device.serial.clone(),
+                    label,
+                    parser: vt100::Parser::new(rows, cols, SCROLLBACK_LINES),
+                    exited: false,
+                });
+                self.active = self.tabs.len() - 1;
+                self.attached = true;
+                return vec![Command::OpenShell {
+                    id,
+                    serial: device.serial.clone(),
+                    rows,
+                    cols,
+                }];
+            }
+            ShellAction::CloseSession => {
+                if self.active < self.tabs.len() {
+                    let tab = self.tabs.remove(self.active);
+                    self.active = self.active.min(self.tabs.len().saturating_sub(1));
+                    return vec![Command::CloseShell(tab.id)];
+                }
+            }
+            ShellAction::NextSession => {
+                if !self.tabs.is_empty() {
+                    self.active = (self.active + 1) % self.tabs.len();
+                }
+            }
+            ShellAction::PrevSession => {
+                if !self.tabs.is_empty() {
+                    self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+                }
+            }
+            ShellAction::ScrollUp | ShellAction::ScrollDown => {
+                let page = (self.size.get().0 / 2).max(1) as usize;
+                if let Some(tab) = self.active_tab_mut() {
+                    let current = tab.parser.screen().scrollback();
+                    let target = if action == ShellAction::ScrollUp {
+                        current + page
+                    } else {
+                        current.saturating_sub(page)
+                    };
+                    // vt100 clamps to the lines actually kept
+                    tab.parser.set_scrollback(target);
+                }
+            }
+            ShellAction::ReleaseFocus => {
+                self.attached = false;
+                self.pending_keys.clear();
+                return vec![Command::Focus(Pane::DeviceList)];
+            }
+        }
+        Vec::new()
+    }
+
+    fn handle_session_key(&mut self, key: KeyEvent) -> Vec<Command> {
+        self.pending_keys.push(key);
+
+        if let Some(action) = self
+            .session_keymap
+            .get(&self.pending_keys)
+            .and_then(|s| ShellAction::from_str(s).ok())
+        {
+            self.pending_keys.clear();
+            return self.handle_action(action);
+        }
+        let is_prefix = self
+            .session_keymap
+            .keys()
+            .any(|seq| seq.len() > self.pending_keys.len() && seq.starts_with(&self.pending_keys));
+        if is_prefix {
+            return Vec::new();
+        }
+
+        let keys = std::mem::take(&mut self.pending_keys);
+        let Some(tab) = self.active_tab_mut() else {
+            return Vec::new();
+        };
+        let application_cursor = tab.parser.screen().application_cursor();
+        let data: Vec<u8> = keys
+            .iter()
+            .flat_map(|k| key_to_bytes(k, application_cursor))
+            .collect();
+        tab.parser.set_scrollback(0);
+        if data.is_empty() {
+            return Vec::new();
+        }
+        vec![Command::ShellInput { id: tab.id, data }]
+    }
+
+    /// Writes a paste to the session, bracketed when the program asked for it.
+    fn paste(&mut self, text: &str) -> Vec<Command> {
+        self.pending_keys.clear();
+        let Some(tab) = self.active_tab_mut() else {
+            return Vec::new();
+        };
+        tab.parser.set_scrollback(0);
+        let text = text.replace("\r\n", "\r").replace('\n', "\r");
+        let data = if tab.parser.screen().bracketed_paste() {
+            format!("\x1b[200~{text}\x1b[201~")
+        } else {
+            text
+        };
+        vec![Command::ShellInput {
+            id: tab.id,
+            data: data.into_bytes(),
+        }]
+    }
+
+    fn resize_commands(&mut self) -> Vec<Command> {
+        let (rows, cols) = self.size.get();
+        let mut commands = Vec::new();
+        for tab in self.tabs.iter_mut().filter(|t| !t.exited) {
+            if tab.parser.screen().size() != (rows, cols) {
+                tab.parser.set_size(rows, cols);
+                commands.push(Command::ResizeShell {
+                    id: tab.id,
+                    rows,
+                    cols,
+                });
+            }
+        }
+        commands
+    }
+
+    fn title(&self) -> Line<'static> {
+        let mut spans = vec![Span::raw(" SHELL ")];
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let mut style = if i == self.active {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            if tab.exited {
+                style = style.add_modifier(Modifier::CROSSED_OUT);
+            }
+            spans.push(Span::styled(format!(" {}: {} ", i + 1, tab.label), style));
+            spans.push(Span::raw(" "));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for ShellPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        match msg {
+            Msg::KeyPress(key) if self.captures_input() => self.handle_session_key(*key),
+            Msg::Paste(text) if self.captures_input() => self.paste(text),
+            Msg::KeyPress(key) => {
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| ShellAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::Tick => self.resize_commands(),
+            Msg::DeviceSelected(device) => {
+                self.device = device.clone();
+                Vec::new()
+            }
+            Msg::ShellOutput { id, data } => {
+                if let Some(tab) = self.tabs.iter_mut().find(|t| t.id == *id) {
+                    tab.parser.process(data);
+                }
+                Vec::new()
+            }
+            Msg::ShellExited { id, error } => {
+                if let Some(tab) = self.tabs.iter_mut().find(|t| t.id == *id) {
+                    tab.exited = true;
+                    let note = match error {
+                        Some(e) => format!("\r\n[{e}]\r\n"),
+                        None => "\r\n[session closed]\r\n".to_string(),
+                    };
+                    tab.parser.process(note.as_bytes());
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Shell;
+        let border_color = if !focused {
+            Color::DarkGray
+        } else if self.captures_input() {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title())
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        self.size.set((inner.height.max(1), inner.width.max(1)));
+
+        let Some(tab) = self.active_tab() else {
+            let text = match self.device {
+                Some(ref device) => {
+                    format!("Press Enter to open a shell on {}", device.display_name())
+                }
+                None => "Select a device to begin".to_string(),
+            };
+            frame.render_widget(Paragraph::new(text), inner);
+            return;
+        };
+
+        let show_cursor = focused && self.captures_input();
+        let lines = screen_lines(tab.parser.screen(), show_cursor);
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn id(&self) -> &'static str {
+        "Shell"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.attached && self.active_tab().is_some_and(|t| !t.exited)
+    }
+}
+
+fn screen_lines(screen: &vt100::Screen, show_cursor: bool) -> Vec<Line<'static>> {
+    let (rows, cols) = screen.size();
+    let cursor = (show_cursor && screen.scrollback() == 0 && !screen.hide_cursor())
+        .then(|| screen.cursor_position());
+
+    (0..rows)
+        .map(|row| {
+            let mut spans = Vec::new();
+            for col in 0..cols {
+                let Some(cell) = screen.cell(row, col) else {
+                    continue;
+                };
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                let mut style = Style::default()
+                    .fg(convert_color(cell.fgcolor()))
+                    .bg(convert_color(cell.bgcolor()));
+                if cell.bold() {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                if cell.italic() {
+                    style = style.add_modifier(Modifier::ITALIC);
+                }
+                if cell.underline() {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                if cell.inverse() != (cursor == Some((row, col))) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                let contents = if cell.has_contents() {
+                    cell.contents()
+                } else {
+                    " ".to_string()
+                };
+                spans.push(Span::styled(contents, style));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn convert_color(color: vt100::Color) -> Color {
+    match color {
+        vt100::Color::Default => Color::Reset,
+        vt100::Color::Idx(i) => Color::Indexed(i),
+        vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+/// Encodes a key press the way an xterm-compatible terminal would.
+fn key_to_bytes(key: &KeyEvent, application_cursor: bool) -> Vec<u8> {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    let cursor = |c: u8| {
+        if application_cursor {
+            vec![0x1b, b'O', c]
+        } else {
+            vec![0x1b, b'[', c]
+        }
+    };
+
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if ctrl => match c {
+            'a'..='z' => vec![c as u8 - b'a' + 1],
+            '@' | ' ' => vec![0],
+            '[' => vec![0x1b],
+            '\\' => vec![0x1c],
+            ']' => vec![0x1d],
+            _ => c.to_string().into_bytes(),
+        },
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => cursor(b'A'),
+        KeyCode::Down => cursor(b'B'),
+        KeyCode::Right => cursor(b'C'),
+        KeyCode::Left => cursor(b'D'),
+        KeyCode::Home => cursor(b'H'),
+        KeyCode::End => cursor(b'F'),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+        KeyCode::F(n) => {
+            let code = match n {
+                5 => 15,
+                6 => 17,
+                7 => 18,
+                8 => 19,
+                9 => 20,
+                10 => 21,
+                11 => 23,
+                12 => 24,
+                _ => return Vec::new(),
+            };
+            format!("\x1b[{code}~").into_bytes()
+        }
+        _ => return Vec::new(),
+    };
+
+    if alt {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::device::{ConnectionType, DeviceState};
+    use crate::config::keymap::parse_key_sequence;
+
+    fn make_pane() -> ShellPane {
+        let mut keymap = SectionKeymap::new();
+        keymap.insert(parse_key_sequence("<Enter>").unwrap(), "Attach".into());
+        keymap.insert(parse_key_sequence("<c>").unwrap(), "NewSession".into());
+        keymap.insert(parse_key_sequence("<l>").unwrap(), "NextSession".into());
+        let mut session_keymap = SectionKeymap::new();
+        session_keymap.insert(
+            parse_key_sequence("<Ctrl-a><d>").unwrap(),
+            "ReleaseFocus".into(),
+        );
+        session_keymap.insert(
+            parse_key_sequence("<Ctrl-a><c>").unwrap(),
+            "NewSession".into(),
+        );
+        let mut pane = ShellPane::new(keymap, session_keymap);
+        pane.device = Some(Device {
+            serial: "DEV0".into(),
+            state: DeviceState::Online,
+            model: Some("Pixel_7".into()),
+            product: None,
+            transport_id: None,
+            connection_type: ConnectionType::Usb,
+        });
+        pane
+    }
+
+    fn press(pane: &mut ShellPane, code: KeyCode, modifiers: KeyModifiers) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(code, modifiers)))
+    }
+
+    fn opened_pane() -> ShellPane {
+        let mut pane = make_pane();
+        let cmds = press(&mut pane, KeyCode::Enter, KeyModifiers::empty());
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::OpenShell { id: 0, .. }))
+        );
+        pane
+    }
+
+    #[test]
+    fn typing_is_forwarded_to_session() {
+        let mut pane = opened_pane();
+        assert!(pane.captures_input());
+        let cmds = press(&mut pane, KeyCode::Char('l'), KeyModifiers::empty());
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::ShellInput { id: 0, data } if data == b"l"
+        )));
+    }
+
+    #[test]
+    fn pastes_are_written_whole() {
+        let mut pane = opened_pane();
+        let cmds = pane.update(&Msg::Paste("ls\nexit".into()));
+        assert!(matches!(
+            cmds.as_slice(),
+            [Command::ShellInput { id: 0, data }] if data == b"ls\rexit"
+        ));
+    }
+
+    #[test]
+    fn escape_chord_releases_focus() {
+        let mut pane = opened_pane();
+        let cmds = press(&mut pane, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert!(cmds.is_empty());
+        let cmds = press(&mut pane, KeyCode::Char('d'), KeyModifiers::empty());
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::Focus(Pane::DeviceList)))
+        );
+    }
+
+    #[test]
+    fn released_session_keeps_keymap_until_reattached() {
+        let mut pane = opened_pane();
+        press(&mut pane, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut pane, KeyCode::Char('d'), KeyModifiers::empty());
+        assert!(!pane.captures_input());
+
+        let cmds = press(&mut pane, KeyCode::Char('c'), KeyModifiers::empty());
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::OpenShell { id: 1, .. }))
+        );
+        press(&mut pane, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut pane, KeyCode::Char('d'), KeyModifiers::empty());
+        press(&mut pane, KeyCode::Char('l'), KeyModifiers::empty());
+        assert_eq!(pane.active, 0);
+        assert!(!pane.captures_input());
+
+        // Enter attaches to the live session rather than opening another
+        let cmds = press(&mut pane, KeyCode::Enter, KeyModifiers::empty());
+        assert!(cmds.is_empty());
+        assert!(pane.captures_input());
+        assert_eq!(pane.tabs.len(), 2);
+    }
+
+    #[test]
+    fn broken_chord_flushes_buffered_keys() {
+        let mut pane = opened_pane();
+        press(&mut pane, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        let cmds = press(&mut pane, KeyCode::Char('z'), KeyModifiers::empty());
+        assert!(cmds.iter().any(|c| matches!(
+            c,
+            Command::ShellInput { data, .. } if data == &[0x01, b'z']
+        )));
+    }
+
+    #[test]
+    fn sessions_per_device_are_numbered() {
+        let mut pane = opened_pane();
+        press(&mut pane, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        let cmds = press(&mut pane, KeyCode::Char('c'), KeyModifiers::empty());
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, Command::OpenShell { id: 1, .. }))
+        );
+        assert_eq!(pane.tabs[1].label, "Pixel 7 #2");
+        assert_eq!(pane.active, 1);
+    }
+
+    #[test]
+    fn output_feeds_terminal_and_exit_stops_capture() {
+        let mut pane = opened_pane();
+        pane.update(&Msg::ShellOutput {
+            id: 0,
+            data: b"\x1b[31mhello\x1b[0m".to_vec(),
+        });
+        let screen = pane.tabs[0].parser.screen();
+        assert_eq!(screen.contents(), "hello");
+        assert_eq!(screen.cell(0, 0).unwrap().fgcolor(), vt100::Color::Idx(1));
+
+        pane.update(&Msg::ShellExited { id: 0, error: None });
+        assert!(!pane.captures_input());
+    }
+
+    #[test]
+    fn key_encoding() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(
+            key_to_bytes(&key(KeyCode::Char('c'), KeyModifiers::CONTROL), false),
+            vec![0x03]
+        );
+        assert_eq!(
+            key_to_bytes(&key(KeyCode::Up, KeyModifiers::empty()), false),
+            b"\x1b[A"
+        );
+        assert_eq!(
+            key_to_bytes(&key(KeyCode::Up, KeyModifiers::empty()), true),
+            b"\x1bOA"
+        );
+        assert_eq!(
+            key_to_bytes(&key(KeyCode::Char('b'), KeyModifiers::ALT), false),
+            b"\x1bb"
+        );
+        assert_eq!(
+            key_to_bytes(&key(KeyCode::F(5), KeyModifiers::empty()), false),
+            b"\x1b[15~"
+        );
+    }
+}
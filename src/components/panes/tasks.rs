@@ -0,0 +1,398 @@
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    adb::activities::{Display, Task},
+    command::Command,
+    components::{Component, DrawContext, panes::Pane},
+    config::keymap::SectionKeymap,
+    msg::Msg,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum TasksAction {
+    Up,
+    Down,
+    Finish,
+    OpenPackage,
+    Refresh,
+}
+
+impl FromStr for TasksAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Finish" => Ok(Self::Finish),
+            "OpenPackage" => Ok(Self::OpenPackage),
+            "Refresh" => Ok(Self::Refresh),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A line of the tree, by index into the displays, tasks and activities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Row {
+    Display(usize),
+    Task(usize, usize),
+    Activity(usize, usize, usize),
+}
+
+pub struct TasksPane {
+    serial: Option<String>,
+    displays: Vec<Display>,
+    error: Option<String>,
+    loaded: bool,
+    selected_index: usize,
+    /// Root task id awaiting y/n.
+    confirm_finish: Option<u32>,
+    notice: Option<String>,
+    keymap: SectionKeymap,
+}
+
+impl TasksPane {
+    pub fn new(keymap: SectionKeymap) -> Self {
+        Self {
+            serial: None,
+            displays: Vec::new(),
+            error: None,
+            loaded: false,
+            selected_index: 0,
+            confirm_finish: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (d, display) in self.displays.iter().enumerate() {
+            rows.push(Row::Display(d));
+            for (t, task) in display.tasks.iter().enumerate() {
+                rows.push(Row::Task(d, t));
+                rows.extend((0..task.activities.len()).map(|a| Row::Activity(d, t, a)));
+            }
+        }
+        rows
+    }
+
+    /// The task of the selected line; a display line has none.
+    fn selected_task(&self) -> Option<&Task> {
+        match self.rows().get(self.selected_index)? {
+            Row::Display(_) => None,
+            Row::Task(d, t) | Row::Activity(d, t, _) => Some(&self.displays[*d].tasks[*t]),
+        }
+    }
+
+    fn refresh_command(&self) -> Vec<Command> {
+        match self.serial {
+            Some(ref serial) => vec![Command::RefreshActivityStack(serial.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn handle_confirm_key(&mut self, msg: &Msg) -> Vec<Command> {
+        let (Msg::KeyPress(key), Some(root_id)) = (msg, self.confirm_finish.take()) else {
+            return Vec::new();
+        };
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(ref serial) = self.serial {
+                    return vec![Command::RemoveTask {
+                        serial: serial.clone(),
+                        root_id,
+                    }];
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {}
+            _ => self.confirm_finish = Some(root_id),
+        }
+        Vec::new()
+    }
+
+    fn handle_action(&mut self, action: TasksAction) -> Vec<Command> {
+        if self.serial.is_none() {
+            return Vec::new();
+        }
+        match action {
+            TasksAction::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            TasksAction::Down => {
+                let last = self.rows().len().saturating_sub(1);
+                self.selected_index = (self.selected_index + 1).min(last);
+            }
+            TasksAction::Finish => match self.selected_task() {
+                Some(task) => self.confirm_finish = Some(task.root_id),
+                None => self.notice = Some("Select a task or activity".into()),
+            },
+            TasksAction::OpenPackage => {
+                let package = match self.rows().get(self.selected_index) {
+                    Some(Row::Activity(d, t, a)) => {
+                        Some(self.displays[*d].tasks[*t].activities[*a].package())
+                    }
+                    Some(Row::Task(d, t)) => self.displays[*d].tasks[*t].package(),
+                    _ => None,
+                };
+                if let Some(package) = package {
+                    return vec![Command::ShowPackage(package.to_string())];
+                }
+            }
+            TasksAction::Refresh => return self.refresh_command(),
+        }
+        Vec::new()
+    }
+
+    fn row_line(&self, row: Row) -> Line<'_> {
+        match row {
+            Row::Display(d) => Line::from(Span::styled(
+                format!(" Display #{}", self.displays[d].id),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Row::Task(d, t) => {
+                let task = &self.displays[d].tasks[t];
+                let mut spans = vec![Span::styled(
+                    format!("   Task #{}", task.id),
+                    Style::default().fg(Color::Cyan),
+                )];
+                if let Some(ref kind) = task.kind {
+                    spans.push(Span::styled(
+                        format!("  {kind}"),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                if task.root_id != task.id {
+                    spans.push(Span::styled(
+                        format!("  in #{}", task.root_id),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            }
+            Row::Activity(d, t, a) => {
+                let activity = &self.displays[d].tasks[t].activities[a];
+                let state = activity.state.as_deref().unwrap_or("");
+                let color = match state {
+                    "RESUMED" => Color::Green,
+                    "PAUSED" | "PAUSING" => Color::Yellow,
+                    "STOPPED" | "STOPPING" | "FINISHING" | "DESTROYED" => Color::DarkGray,
+                    _ => Color::White,
+                };
+                Line::from(vec![
+                    Span::raw(format!("     {}", activity.component)),
+                    Span::styled(format!("  {state}"), Style::default().fg(color)),
+                ])
+            }
+        }
+    }
+
+    fn footer(&self) -> Option<Line<'_>> {
+        if let Some(root_id) = self.confirm_finish {
+            return Some(Line::from(vec![
+                Span::styled(
+                    format!(" Finish task #{root_id} and everything in it?"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    "  y confirm, n cancel",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        self.notice.as_ref().map(|notice| {
+            Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(Color::Yellow),
+            ))
+        })
+    }
+}
+
+impl Component for TasksPane {
+    fn update(&mut self, msg: &Msg) -> Vec<Command> {
+        if self.confirm_finish.is_some() {
+            return self.handle_confirm_key(msg);
+        }
+        match msg {
+            Msg::KeyPress(key) => {
+                self.notice = None;
+                let action = self
+                    .keymap
+                    .get(&vec![*key])
+                    .and_then(|s| TasksAction::from_str(s).ok());
+                match action {
+                    Some(action) => self.handle_action(action),
+                    None => Vec::new(),
+                }
+            }
+            Msg::DeviceSelected(device) => {
+                let new_serial = device.as_ref().map(|d| d.serial.clone());
+                if new_serial == self.serial {
+                    return Vec::new();
+                }
+                self.serial = new_serial;
+                self.displays.clear();
+                self.error = None;
+                self.loaded = false;
+                self.selected_index = 0;
+                self.refresh_command()
+            }
+            Msg::ActivityStackUpdated { serial, result }
+                if self.serial.as_ref() == Some(serial) =>
+            {
+                self.loaded = true;
+                match result {
+                    Ok(displays) => {
+                        self.displays = displays.clone();
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.clone()),
+                }
+                let last = self.rows().len().saturating_sub(1);
+                self.selected_index = self.selected_index.min(last);
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, ctx: &DrawContext) {
+        let focused = ctx.focus == Pane::Tasks;
+        let border_color = if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" TASKS ")
+            .border_style(Style::default().fg(border_color));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.serial.is_none() {
+            frame.render_widget(Paragraph::new("Select a device to begin"), inner);
+            return;
+        }
+        let footer = self.footer();
+        let rows = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(if footer.is_some() { 1 } else { 0 }),
+        ])
+        .split(inner);
+        if let Some(footer) = footer {
+            frame.render_widget(Paragraph::new(footer), rows[1]);
+        }
+
+        if self.displays.is_empty() {
+            let paragraph = match (&self.error, self.loaded) {
+                (Some(e), _) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+                (None, false) => Paragraph::new("Loading task stack..."),
+                (None, true) => Paragraph::new("(no tasks)"),
+            };
+            frame.render_widget(paragraph, rows[0]);
+            return;
+        }
+        let items: Vec<ListItem> = self
+            .rows()
+            .into_iter()
+            .map(|row| ListItem::new(self.row_line(row)))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(list, rows[0], &mut list_state);
+    }
+
+    fn id(&self) -> &'static str {
+        "Tasks"
+    }
+
+    fn captures_input(&self) -> bool {
+        self.confirm_finish.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::adb::activities::parse_activity_stack;
+
+    const DUMP: &str = "\
+Display #0 (activities from top to bottom):
+  * Task{a #12 type=standard sz=1}
+    * Hist  #0: ActivityRecord{f0 u0 com.example.app/.MainActivity t12}
+      state=RESUMED
+  * Task{b #1 type=home sz=1}
+    * Task{c #7 type=home sz=1}
+      * Hist  #0: ActivityRecord{91 u0 com.android.launcher3/.Launcher t7}
+";
+
+    fn make_pane() -> TasksPane {
+        let mut keymap = SectionKeymap::new();
+        let mut bind = |c: char, action: &str| {
+            keymap.insert(
+                vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())],
+                action.into(),
+            );
+        };
+        bind('j', "Down");
+        bind('x', "Finish");
+        bind('p', "OpenPackage");
+        let mut pane = TasksPane::new(keymap);
+        pane.serial = Some("DEV0".into());
+        pane.update(&Msg::ActivityStackUpdated {
+            serial: "DEV0".into(),
+            result: Ok(parse_activity_stack(DUMP)),
+        });
+        pane
+    }
+
+    fn press(pane: &mut TasksPane, c: char) -> Vec<Command> {
+        pane.update(&Msg::KeyPress(KeyEvent::new(
+            KeyCode::Char(c),
+            KeyModifiers::empty(),
+        )))
+    }
+
+    #[test]
+    fn package_jump_follows_the_selected_activity() {
+        let mut pane = make_pane();
+        assert!(press(&mut pane, 'p').is_empty());
+        press(&mut pane, 'j');
+        press(&mut pane, 'j');
+        assert!(matches!(
+            press(&mut pane, 'p').as_slice(),
+            [Command::ShowPackage(package)] if package == "com.example.app"
+        ));
+    }
+
+    #[test]
+    fn finishing_removes_the_root_task_after_confirming() {
+        let mut pane = make_pane();
+        for _ in 0..3 {
+            press(&mut pane, 'j');
+        }
+        assert!(press(&mut pane, 'x').is_empty());
+        assert!(pane.captures_input());
+        assert!(matches!(
+            press(&mut pane, 'y').as_slice(),
+            [Command::RemoveTask { root_id: 1, .. }]
+        ));
+    }
+}
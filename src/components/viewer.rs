@@ -0,0 +1,221 @@
+use std::collections::BTreeSet;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::adb::dumpsys::section_ends;
+
+/// Read-only text with a line cursor, sections folded by indentation and
+/// case-insensitive search.
+pub struct TextViewer {
+    lines: Vec<String>,
+    /// `section_ends` of `lines`.
+    ends: Vec<usize>,
+    folded: BTreeSet<usize>,
+    /// Index into `lines`; always a visible line.
+    cursor: usize,
+}
+
+impl TextViewer {
+    pub fn new(text: &str) -> Self {
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        Self {
+            ends: section_ends(&lines),
+            lines,
+            folded: BTreeSet::new(),
+            cursor: 0,
+        }
+    }
+
+    fn foldable(&self, i: usize) -> bool {
+        self.ends.get(i).is_some_and(|end| *end > i)
+    }
+
+    /// Indexes of the lines not hidden inside a folded section.
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut i = 0;
+        while i < self.lines.len() {
+            visible.push(i);
+            i = if self.folded.contains(&i) {
+                self.ends[i] + 1
+            } else {
+                i + 1
+            };
+        }
+        visible
+    }
+
+    /// Moves the cursor `delta` visible lines, stopping at either end.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let visible = self.visible();
+        let pos = visible.iter().position(|i| *i == self.cursor).unwrap_or(0);
+        let pos = pos
+            .saturating_add_signed(delta)
+            .min(visible.len().saturating_sub(1));
+        self.cursor = visible.get(pos).copied().unwrap_or(0);
+    }
+
+    /// The innermost foldable section containing line `i`.
+    fn parent(&self, i: usize) -> Option<usize> {
+        (0..i).rev().find(|k| self.ends[*k] >= i)
+    }
+
+    /// Folds or unfolds the section under the cursor; on a line that opens
+    /// nothing, folds the section around it.
+    pub fn toggle_fold(&mut self) {
+        if self.foldable(self.cursor) {
+            if !self.folded.remove(&self.cursor) {
+                self.folded.insert(self.cursor);
+            }
+        } else if let Some(parent) = self.parent(self.cursor) {
+            self.folded.insert(parent);
+            self.cursor = parent;
+        }
+    }
+
+    pub fn fold_all(&mut self) {
+        self.folded = (0..self.lines.len())
+            .filter(|i| self.foldable(*i))
+            .collect();
+        while let Some(parent) = self.parent(self.cursor) {
+            if !self.visible().contains(&self.cursor) {
+                self.cursor = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn unfold_all(&mut self) {
+        self.folded.clear();
+    }
+
+    /// Unfolds every section hiding line `i`.
+    fn reveal(&mut self, i: usize) {
+        let ends = &self.ends;
+        self.folded.retain(|k| !(*k < i && ends[*k] >= i));
+    }
+
+    /// Moves to the next line containing `needle`, wrapping around, and
+    /// unfolds whatever hides it.
+    pub fn find(&mut self, needle: &str, forward: bool) -> bool {
+        let needle = needle.to_lowercase();
+        if needle.is_empty() || self.lines.is_empty() {
+            return false;
+        }
+        let len = self.lines.len();
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (self.cursor + step) % len
+                } else {
+                    (self.cursor + len - step) % len
+                }
+            })
+            .find(|i| self.lines[*i].to_lowercase().contains(&needle));
+        match found {
+            Some(i) => {
+                self.reveal(i);
+                self.cursor = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// Draws the visible lines around the cursor, highlighting those
+    /// containing `needle`.
+    pub fn draw(&self, frame: &mut Frame, area: Rect, needle: &str) {
+        let height = area.height as usize;
+        let visible = self.visible();
+        let pos = visible.iter().position(|i| *i == self.cursor).unwrap_or(0);
+        let offset = pos.saturating_sub(height.saturating_sub(1));
+        let needle = needle.to_lowercase();
+        let lines: Vec<Line> = visible
+            .iter()
+            .skip(offset)
+            .take(height)
+            .map(|i| {
+                let marker = match (self.foldable(*i), self.folded.contains(i)) {
+                    (true, true) => "\u{25b8} ",
+                    (true, false) => "\u{25be} ",
+                    (false, _) => "  ",
+                };
+                let text = &self.lines[*i];
+                let mut style = Style::default().fg(Color::White);
+                if !needle.is_empty() && text.to_lowercase().contains(&needle) {
+                    style = style.fg(Color::Yellow);
+                }
+                if *i == self.cursor {
+                    style = style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                }
+                let mut spans = vec![
+                    Span::styled(marker, Style::default().fg(Color::DarkGray)),
+                    Span::styled(text.clone(), style),
+                ];
+                if self.folded.contains(i) {
+                    spans.push(Span::styled(
+                        format!("  \u{2026} {} lines", self.ends[*i] - i),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "\
+WINDOW MANAGER
+  Display 0:
+    mDisplayId=0
+    mRotation=1
+  Display 1:
+    mDisplayId=1
+INPUT
+  focus=none";
+
+    #[test]
+    fn folding_hides_deeper_lines() {
+        let mut viewer = TextViewer::new(DUMP);
+        viewer.cursor = 2;
+        viewer.toggle_fold();
+        assert_eq!(viewer.cursor, 1);
+        assert_eq!(viewer.visible(), [0, 1, 4, 5, 6, 7]);
+        viewer.move_cursor(1);
+        assert_eq!(viewer.cursor, 4);
+
+        viewer.fold_all();
+        assert_eq!(viewer.visible(), [0, 6]);
+        assert_eq!(viewer.cursor, 0);
+    }
+
+    #[test]
+    fn search_wraps_and_unfolds_the_match() {
+        let mut viewer = TextViewer::new(DUMP);
+        viewer.fold_all();
+        viewer.cursor = 6;
+        assert!(viewer.find("ROTATION", true));
+        assert_eq!(viewer.cursor, 3);
+        assert!(viewer.visible().contains(&3));
+        assert!(viewer.folded.contains(&4));
+        assert!(!viewer.find("missing", false));
+    }
+}
@@ -7,13 +7,28 @@ mod styles;
 pub use keymap::KeyBindings;
 pub use styles::Styles;
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use color_eyre::Result;
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::adb::display::DisplayPreset;
+use crate::adb::forward::RuleSet;
+use crate::adb::screenrecord::RecordOptions;
+
+const PORT_RULES_FILE: &str = "port_rules.toml";
+const DISPLAY_PRESETS_FILE: &str = "display_presets.toml";
+const INTENT_HISTORY_FILE: &str = "intent_history.toml";
+
 const DEFAULT_CONFIG: &str = include_str!("../../.config/config.toml");
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -22,6 +37,36 @@ pub struct AppConfig {
     pub data_dir: PathBuf,
     #[serde(default)]
     pub config_dir: PathBuf,
+    /// Where screenshots are saved; defaults to `<data_dir>/captures`.
+    #[serde(default)]
+    pub capture_dir: Option<PathBuf>,
+    /// `kitty`, `sixel` or `halfblocks`; detected from the terminal when unset.
+    #[serde(default)]
+    pub preview_protocol: Option<String>,
+    #[serde(default)]
+    pub screenrecord: RecordOptions,
+    #[serde(default)]
+    pub port_rules: BTreeMap<String, RuleSet>,
+    /// Rule sets from the main config file rather than saved from the ports
+    /// view; they are never copied into `port_rules.toml`.
+    #[serde(skip)]
+    pub config_rule_sets: BTreeSet<String>,
+    #[serde(default)]
+    pub display_presets: BTreeMap<String, DisplayPreset>,
+    /// Presets from the main config file rather than saved from the display
+    /// view; they are never copied into `display_presets.toml`.
+    #[serde(skip)]
+    pub config_display_presets: BTreeSet<String>,
+    /// Seconds of device metrics charted in the content pane.
+    #[serde(default)]
+    pub history_window: Option<u64>,
+    /// Host port the network view's proxy toggle points the device at.
+    #[serde(default)]
+    pub proxy_port: Option<u16>,
+    /// Whether to capture the mouse; on when unset. Off leaves the terminal's
+    /// own text selection working.
+    #[serde(default)]
+    pub mouse: Option<bool>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -62,6 +107,15 @@ impl Config {
             ("config.yaml", config::FileFormat::Yaml),
             ("config.ini", config::FileFormat::Ini),
         ];
+        // Rule sets and presets saved from the ports and display views; the
+        // main config file wins on conflicts
+        for file in [PORT_RULES_FILE, DISPLAY_PRESETS_FILE] {
+            builder = builder.add_source(
+                config::File::from(config_dir.join(file))
+                    .format(config::FileFormat::Toml)
+                    .required(false),
+            );
+        }
         let mut found_config = false;
         for (file, format) in &config_files {
             let source = config::File::from(config_dir.join(file))
@@ -78,6 +132,30 @@ impl Config {
 
         let mut cfg: Self = builder.build()?.try_deserialize()?;
 
+        let mut saved = config::Config::builder();
+        for file in [PORT_RULES_FILE, DISPLAY_PRESETS_FILE] {
+            saved = saved.add_source(
+                config::File::from(config_dir.join(file))
+                    .format(config::FileFormat::Toml)
+                    .required(false),
+            );
+        }
+        let saved: AppConfig = saved.build()?.try_deserialize()?;
+        cfg.config.config_rule_sets = cfg
+            .config
+            .port_rules
+            .keys()
+            .filter(|name| !saved.port_rules.contains_key(*name))
+            .cloned()
+            .collect();
+        cfg.config.config_display_presets = cfg
+            .config
+            .display_presets
+            .keys()
+            .filter(|name| !saved.display_presets.contains_key(*name))
+            .cloned()
+            .collect();
+
         for (key, action) in default_config.keybindings.global.iter() {
             cfg.keybindings
                 .global
@@ -103,6 +181,52 @@ impl Config {
     }
 }
 
+impl Config {
+    pub fn capture_dir(&self) -> PathBuf {
+        self.config
+            .capture_dir
+            .clone()
+            .unwrap_or_else(|| self.config.data_dir.join("captures"))
+    }
+
+    pub fn bugreport_dir(&self) -> PathBuf {
+        self.config.data_dir.join("bugreports")
+    }
+
+    pub fn history_window(&self) -> Duration {
+        Duration::from_secs(self.config.history_window.unwrap_or(600))
+    }
+
+    pub fn proxy_port(&self) -> u16 {
+        self.config.proxy_port.unwrap_or(8080)
+    }
+
+    pub fn mouse(&self) -> bool {
+        self.config.mouse.unwrap_or(true)
+    }
+
+    pub fn port_rules_path(&self) -> PathBuf {
+        self.config.config_dir.join(PORT_RULES_FILE)
+    }
+
+    pub fn display_presets_path(&self) -> PathBuf {
+        self.config.config_dir.join(DISPLAY_PRESETS_FILE)
+    }
+
+    pub fn intent_history_path(&self) -> PathBuf {
+        self.config.data_dir.join(INTENT_HISTORY_FILE)
+    }
+}
+
+/// Writes `value` to `path` as TOML, creating the directory first.
+pub fn save_toml(path: &Path, value: &impl Serialize) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, toml::to_string(value)?)?;
+    Ok(())
+}
+
 pub fn get_data_dir() -> PathBuf {
     if let Some(s) = DATA_FOLDER.clone() {
         s
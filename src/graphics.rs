@@ -0,0 +1,423 @@
+//! Local image decoding and scaling, and the terminal encodings used to preview
+//! images: kitty and sixel graphics, or half-block characters as a fallback.
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use base64::Engine;
+use color_eyre::{Result, eyre::eyre};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Deletes every kitty image placement and frees its data.
+pub const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Cell size assumed when the terminal doesn't report its pixel dimensions.
+pub const DEFAULT_CELL_SIZE: (u16, u16) = (8, 16);
+
+const KITTY_CHUNK: usize = 4096;
+
+/// 8-bit RGBA pixels, row by row.
+#[derive(Clone, PartialEq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+// Pixel data would flood the debug log with every message
+impl fmt::Debug for RgbaImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RgbaImage({}x{})", self.width, self.height)
+    }
+}
+
+impl RgbaImage {
+    pub fn decode_png(data: &[u8]) -> Result<Self> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| eyre!("Invalid PNG: {}", e))?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(|e| eyre!("Invalid PNG: {}", e))?;
+        buf.truncate(info.buffer_size());
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buf,
+            png::ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::Indexed => return Err(eyre!("Unexpanded palette PNG")),
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// The pixel at `(x, y)`, composited over black.
+    pub fn rgb(&self, x: u32, y: u32) -> [u8; 3] {
+        let i = ((y * self.width + x) * 4) as usize;
+        let p = &self.pixels[i..i + 4];
+        let alpha = p[3] as u32;
+        [0, 1, 2].map(|c| (p[c] as u32 * alpha / 255) as u8)
+    }
+
+    /// Scales with a box filter, averaging every source pixel a target pixel covers.
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+
+        for y in 0..height {
+            let y0 = y * self.height / height;
+            let y1 = ((y + 1) * self.height / height).max(y0 + 1);
+            for x in 0..width {
+                let x0 = x * self.width / width;
+                let x1 = ((x + 1) * self.width / width).max(x0 + 1);
+
+                let mut sum = [0u64; 4];
+                for sy in y0..y1 {
+                    let row = (sy * self.width) as usize;
+                    for sx in x0..x1 {
+                        let i = (row + sx as usize) * 4;
+                        for (c, total) in sum.iter_mut().enumerate() {
+                            *total += self.pixels[i + c] as u64;
+                        }
+                    }
+                }
+                let count = ((y1 - y0) * (x1 - x0)) as u64;
+                pixels.extend(sum.map(|total| (total / count) as u8));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// Largest size with the image's aspect ratio inside `max_width` x `max_height`,
+/// never larger than the image itself.
+pub fn fit(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        return (0, 0);
+    }
+    let scale = f64::min(
+        max_width as f64 / width as f64,
+        max_height as f64 / height as f64,
+    )
+    .min(1.0);
+    (
+        ((width as f64 * scale) as u32).max(1),
+        ((height as f64 * scale) as u32).max(1),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+    HalfBlocks,
+}
+
+impl GraphicsProtocol {
+    /// Honours an explicit `preview_protocol` setting, otherwise guesses from
+    /// the environment the terminal exports.
+    pub fn detect(preference: Option<&str>) -> Self {
+        match preference {
+            Some("kitty") => Self::Kitty,
+            Some("sixel") => Self::Sixel,
+            Some("halfblocks") => Self::HalfBlocks,
+            _ => {
+                let var = |name| std::env::var(name).unwrap_or_default();
+                Self::from_env(&var("TERM"), &var("TERM_PROGRAM"), &var("KITTY_WINDOW_ID"))
+            }
+        }
+    }
+
+    fn from_env(term: &str, term_program: &str, kitty_window_id: &str) -> Self {
+        let term_program = term_program.to_lowercase();
+        if !kitty_window_id.is_empty()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || matches!(term_program.as_str(), "wezterm" | "ghostty")
+        {
+            Self::Kitty
+        } else if term.contains("sixel")
+            || term.starts_with("foot")
+            || term.starts_with("mlterm")
+            || matches!(term_program.as_str(), "iterm.app" | "mintty")
+        {
+            Self::Sixel
+        } else {
+            Self::HalfBlocks
+        }
+    }
+}
+
+/// Where an image lands in a `cols` x `rows` cell area: its pixel size and
+/// the cells it actually covers.
+pub fn fit_cells(
+    image: &RgbaImage,
+    cols: u16,
+    rows: u16,
+    cell: (u16, u16),
+) -> (u32, u32, u16, u16) {
+    let (cell_w, cell_h) = (cell.0.max(1) as u32, cell.1.max(1) as u32);
+    let (w, h) = fit(
+        image.width,
+        image.height,
+        cols as u32 * cell_w,
+        rows as u32 * cell_h,
+    );
+    (
+        w,
+        h,
+        w.div_ceil(cell_w).min(cols as u32) as u16,
+        h.div_ceil(cell_h).min(rows as u32) as u16,
+    )
+}
+
+/// Renders the image with `▀`, the foreground painting the upper pixel and the
+/// background the lower one.
+pub fn half_block_lines(image: &RgbaImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    let (w, h) = fit(image.width, image.height, cols as u32, rows as u32 * 2);
+    if w == 0 {
+        return Vec::new();
+    }
+    let scaled = image.resize(w, h);
+    let color = |[r, g, b]: [u8; 3]| Color::Rgb(r, g, b);
+
+    (0..h.div_ceil(2))
+        .map(|row| {
+            let spans: Vec<Span> = (0..w)
+                .map(|x| {
+                    let top = scaled.rgb(x, row * 2);
+                    let style = match row * 2 + 1 {
+                        y if y < h => Style::default().fg(color(top)).bg(color(scaled.rgb(x, y))),
+                        _ => Style::default().fg(color(top)),
+                    };
+                    Span::styled("▀", style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Transmits and places the image over `cols` x `rows` cells at the cursor.
+pub fn kitty_sequence(image: &RgbaImage, cols: u16, rows: u16) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(&image.pixels);
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|c| std::str::from_utf8(c).expect("base64 is ascii"))
+        .collect();
+
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},c={cols},r={rows},q=2,m={more};{chunk}\x1b\\",
+                image.width, image.height
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+    out
+}
+
+/// Encodes the image as sixels with a fixed 6x6x6 colour cube.
+pub fn sixel_sequence(image: &RgbaImage) -> String {
+    let level = |c: u8| (c as u32 * 5 + 127) / 255;
+    let indices: Vec<u8> = (0..image.height)
+        .flat_map(|y| (0..image.width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let [r, g, b] = image.rgb(x, y);
+            (level(r) * 36 + level(g) * 6 + level(b)) as u8
+        })
+        .collect();
+
+    let mut out = format!("\x1bPq\"1;1;{};{}", image.width, image.height);
+    let mut used = [false; 216];
+    for &i in &indices {
+        used[i as usize] = true;
+    }
+    for (i, _) in used.iter().enumerate().filter(|(_, u)| **u) {
+        let percent = |l: usize| l * 100 / 5;
+        let _ = write!(
+            out,
+            "#{i};2;{};{};{}",
+            percent(i / 36),
+            percent(i / 6 % 6),
+            percent(i % 6)
+        );
+    }
+
+    let width = image.width as usize;
+    for band in indices.chunks(width * 6) {
+        let band_rows = band.len() / width;
+        let mut colors: Vec<u8> = band.to_vec();
+        colors.sort_unstable();
+        colors.dedup();
+
+        for (n, &color) in colors.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            let _ = write!(out, "#{color}");
+            let sixels = (0..width).map(|x| {
+                let bits = (0..band_rows)
+                    .filter(|row| band[row * width + x] == color)
+                    .fold(0u8, |acc, row| acc | (1 << row));
+                (63 + bits) as char
+            });
+            push_run_length(&mut out, sixels);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_run_length(out: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |out: &mut String, (c, n): (char, usize)| {
+        if n > 3 {
+            let _ = write!(out, "!{n}{c}");
+        } else {
+            out.extend(std::iter::repeat_n(c, n));
+        }
+    };
+    for c in sixels {
+        run = match run {
+            Some((prev, n)) if prev == c => Some((prev, n + 1)),
+            Some(prev) => {
+                flush(out, prev);
+                Some((c, 1))
+            }
+            None => Some((c, 1)),
+        };
+    }
+    if let Some(last) = run {
+        flush(out, last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4x2 RGB image: red, green, blue, white over black, black, black, gray.
+    const FIXTURE: &[u8] = include_bytes!("../tests/fixtures/screenshot_4x2.png");
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: rgba.repeat((width * height) as usize),
+        }
+    }
+
+    #[test]
+    fn decodes_fixture_png() {
+        let image = RgbaImage::decode_png(FIXTURE).unwrap();
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.rgb(0, 0), [255, 0, 0]);
+        assert_eq!(image.rgb(2, 0), [0, 0, 255]);
+        assert_eq!(image.rgb(3, 1), [128, 128, 128]);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(RgbaImage::decode_png(b"not a png").is_err());
+    }
+
+    #[test]
+    fn resize_averages_pixels() {
+        let image = RgbaImage::decode_png(FIXTURE).unwrap();
+        let half = image.resize(2, 1);
+        assert_eq!((half.width, half.height), (2, 1));
+        // Left half: red, green and two black pixels
+        assert_eq!(half.rgb(0, 0), [63, 63, 0]);
+    }
+
+    #[test]
+    fn fit_keeps_aspect_and_never_upscales() {
+        assert_eq!(fit(1080, 2400, 100, 100), (45, 100));
+        assert_eq!(fit(10, 10, 100, 100), (10, 10));
+        assert_eq!(fit(0, 10, 100, 100), (0, 0));
+    }
+
+    #[test]
+    fn half_blocks_pair_rows() {
+        let image = RgbaImage::decode_png(FIXTURE).unwrap();
+        let lines = half_block_lines(&image, 10, 10);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans.len(), 4);
+        let first = lines[0].spans[0].style;
+        assert_eq!(first.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(first.bg, Some(Color::Rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn kitty_chunks_payload() {
+        let image = solid(64, 64, [1, 2, 3, 255]);
+        let seq = kitty_sequence(&image, 8, 4);
+        assert!(seq.starts_with("\x1b_Ga=T,f=32,s=64,v=64,c=8,r=4,q=2,m=1;"));
+        assert!(seq.ends_with("\x1b\\"));
+        assert!(seq.contains("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn sixel_encodes_bands_with_run_length() {
+        let image = solid(10, 7, [255, 255, 255, 255]);
+        let seq = sixel_sequence(&image);
+        assert!(seq.starts_with("\x1bPq\"1;1;10;7#215;2;100;100;100"));
+        // A full band of six rows, then a band with only the seventh row
+        assert!(seq.contains("#215!10~-#215!10@-"));
+        assert!(seq.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        assert_eq!(
+            GraphicsProtocol::from_env("xterm-kitty", "", ""),
+            GraphicsProtocol::Kitty
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env("xterm-256color", "WezTerm", ""),
+            GraphicsProtocol::Kitty
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env("foot", "", ""),
+            GraphicsProtocol::Sixel
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env("xterm-256color", "Apple_Terminal", ""),
+            GraphicsProtocol::HalfBlocks
+        );
+        assert_eq!(
+            GraphicsProtocol::detect(Some("sixel")),
+            GraphicsProtocol::Sixel
+        );
+    }
+}
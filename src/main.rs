@@ -1,8 +1,11 @@
 mod adb;
 mod app;
+mod clipboard;
 mod command;
 mod components;
 mod config;
+mod graphics;
+mod metrics;
 mod msg;
 mod tui;
 
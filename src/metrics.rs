@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::adb::{
+    cpu::{CpuLoad, CpuStat},
+    device_info::DeviceInfo,
+};
+
+/// Longer than this between readings and the CPU delta would average over
+/// the whole gap, e.g. while another device was selected, so it's dropped.
+const MAX_CPU_GAP: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Cpu,
+    Ram,
+    Battery,
+    Temperature,
+    Storage,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [
+        Self::Cpu,
+        Self::Ram,
+        Self::Battery,
+        Self::Temperature,
+        Self::Storage,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Ram => "RAM",
+            Self::Battery => "Battery",
+            Self::Temperature => "Temp",
+            Self::Storage => "Storage",
+        }
+    }
+
+    /// The top of the chart; temperatures scale to the highest reading.
+    pub fn max(&self) -> Option<f64> {
+        match self {
+            Self::Temperature => None,
+            _ => Some(100.0),
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Self::Temperature => format!("{value:.1}\u{b0}C"),
+            _ => format!("{value:.0}%"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|m| m == self).unwrap_or(0)
+    }
+}
+
+struct Sample {
+    at: Instant,
+    values: [Option<f64>; Metric::ALL.len()],
+}
+
+/// One device's readings over the last `window`, oldest first.
+pub struct MetricsHistory {
+    window: Duration,
+    samples: VecDeque<Sample>,
+    /// CPU load needs two readings; this is the previous one.
+    last_cpu: Option<CpuStat>,
+    cpu_load: Option<CpuLoad>,
+}
+
+impl MetricsHistory {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+            last_cpu: None,
+            cpu_load: None,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn record(&mut self, info: &DeviceInfo, at: Instant) {
+        let recent = self
+            .samples
+            .back()
+            .is_some_and(|s| at.duration_since(s.at) <= MAX_CPU_GAP);
+        self.cpu_load = match (&info.cpu, &self.last_cpu) {
+            (Some(now), Some(before)) if recent => Some(now.load_since(before)),
+            _ => None,
+        };
+        self.last_cpu = info.cpu.clone();
+
+        let percent = |used: f64, total: f64| (total > 0.0).then(|| 100.0 * used / total);
+        let mut values = [None; Metric::ALL.len()];
+        values[Metric::Cpu.index()] = self.cpu_load.as_ref().and_then(|l| l.total);
+        values[Metric::Ram.index()] = info
+            .ram
+            .as_ref()
+            .and_then(|r| percent(r.used_gb, r.total_gb));
+        values[Metric::Battery.index()] = info.battery.as_ref().map(|b| b.level as f64);
+        values[Metric::Temperature.index()] = info
+            .battery
+            .as_ref()
+            .and_then(|b| b.temperature_c)
+            .map(f64::from);
+        values[Metric::Storage.index()] = info
+            .storage
+            .as_ref()
+            .and_then(|s| percent(s.used_gb, s.total_gb));
+
+        self.samples.push_back(Sample { at, values });
+        while let Some(oldest) = self.samples.front() {
+            if at.duration_since(oldest.at) <= self.window {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    /// The metric's readings, oldest first, skipping samples without one.
+    pub fn values(&self, metric: Metric) -> Vec<f64> {
+        self.samples
+            .iter()
+            .filter_map(|s| s.values[metric.index()])
+            .collect()
+    }
+
+    /// Total and per-core load between the two latest readings.
+    pub fn cpu_load(&self) -> Option<&CpuLoad> {
+        self.cpu_load.as_ref()
+    }
+
+    pub fn latest(&self, metric: Metric) -> Option<f64> {
+        self.samples
+            .iter()
+            .rev()
+            .find_map(|s| s.values[metric.index()])
+    }
+}
+
+/// Averages `values` into at most `width` buckets so the whole window fits.
+pub fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+    if width == 0 || values.len() <= width {
+        return values.to_vec();
+    }
+    (0..width)
+        .map(|i| {
+            let bucket = &values[i * values.len() / width..(i + 1) * values.len() / width];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+/// `10m`, `90s` or `1h` for chart titles.
+pub fn format_window(window: Duration) -> String {
+    let secs = window.as_secs();
+    if secs >= 3600 && secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::{
+        cpu::CpuTimes,
+        device_info::{RamInfo, parse_battery},
+    };
+
+    fn info(ram_used: f64, cpu: CpuTimes) -> DeviceInfo {
+        DeviceInfo {
+            serial: "DEV0".into(),
+            model: String::new(),
+            android_version: String::new(),
+            api_level: String::new(),
+            state: String::new(),
+            connection_type: String::new(),
+            abi: String::new(),
+            locale: String::new(),
+            timezone: String::new(),
+            battery: parse_battery("  level: 90\n  temperature: 312\n"),
+            storage: None,
+            ram: Some(RamInfo {
+                used_gb: ram_used,
+                total_gb: 8.0,
+            }),
+            screen: None,
+            wifi: None,
+            cpu: Some(CpuStat {
+                total: cpu,
+                cores: Vec::new(),
+            }),
+            cpu_freqs: Vec::new(),
+            thermal: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_only_the_window_and_derives_cpu_load() {
+        let start = Instant::now();
+        let mut history = MetricsHistory::new(Duration::from_secs(4));
+        let cpu = |total, idle| CpuTimes { total, idle };
+        history.record(&info(2.0, cpu(1000, 800)), start);
+        history.record(&info(4.0, cpu(1100, 825)), start + Duration::from_secs(2));
+        history.record(&info(6.0, cpu(1200, 900)), start + Duration::from_secs(6));
+
+        assert_eq!(history.values(Metric::Ram), [50.0, 75.0]);
+        assert_eq!(history.values(Metric::Cpu), [75.0, 25.0]);
+        assert_eq!(history.latest(Metric::Temperature), Some(31.2f32 as f64));
+        assert!(history.values(Metric::Storage).is_empty());
+    }
+
+    #[test]
+    fn cpu_load_is_not_averaged_over_a_gap() {
+        let start = Instant::now();
+        let mut history = MetricsHistory::new(Duration::from_secs(600));
+        let cpu = |total, idle| CpuTimes { total, idle };
+        history.record(&info(2.0, cpu(1000, 800)), start);
+        history.record(&info(2.0, cpu(9000, 1000)), start + Duration::from_secs(60));
+        assert!(history.cpu_load().is_none());
+        history.record(&info(2.0, cpu(9100, 1050)), start + Duration::from_secs(62));
+        assert_eq!(history.values(Metric::Cpu), [50.0]);
+    }
+
+    #[test]
+    fn downsample_averages_buckets() {
+        assert_eq!(downsample(&[1.0, 3.0, 5.0, 7.0], 2), [2.0, 6.0]);
+        assert_eq!(downsample(&[1.0, 2.0], 10), [1.0, 2.0]);
+        assert_eq!(format_window(Duration::from_secs(600)), "10m");
+        assert_eq!(format_window(Duration::from_secs(90)), "90s");
+    }
+}
@@ -1,13 +1,212 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
 
+use crate::adb::activities::Display;
+use crate::adb::bugreport::{BugreportContents, BugreportSummary};
+use crate::adb::capture::Screenshot;
+use crate::adb::console::ConsoleCommand;
 use crate::adb::device::Device;
 use crate::adb::device_info::DeviceInfo;
+use crate::adb::display::DisplayState;
+use crate::adb::forward::PortRule;
+use crate::adb::intent::{AmResult, Intent};
+use crate::adb::locale::LocaleState;
+use crate::adb::network::NetworkInfo;
+use crate::adb::notifications::Notification;
+use crate::adb::permissions::PackagePermissions;
+use crate::adb::processes::ProcessInfo;
+use crate::adb::properties::PropDiff;
+use crate::adb::settings::{SettingChange, SettingsNamespace};
+use crate::adb::ui_hierarchy::UiNode;
+
+/// The view a device change shows up in, refreshed once the change is done.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Affects {
+    Devices,
+    DeviceInfo,
+    DevOptions,
+    Display,
+    PortRules,
+    Network,
+    Tasks,
+    Notifications,
+    Processes,
+    Properties,
+    /// The permissions and app ops of this package.
+    Permissions(String),
+}
 
 #[derive(Debug)]
 pub enum Msg {
     Tick,
     DevicesUpdated(Vec<Device>),
     DeviceSelected(Option<Device>),
-    DeviceInfoUpdated(DeviceInfo),
+    DeviceInfoUpdated {
+        serial: String,
+        result: Result<Box<DeviceInfo>, String>,
+    },
+    PackagesUpdated {
+        serial: String,
+        packages: Result<Vec<String>, String>,
+    },
+    PermissionsUpdated {
+        serial: String,
+        package: String,
+        result: Result<Box<PackagePermissions>, String>,
+    },
+    FocusPackage(String),
+    ProcessesUpdated {
+        serial: String,
+        processes: Result<Vec<ProcessInfo>, String>,
+    },
+    ProcessMemInfo {
+        serial: String,
+        pid: u32,
+        text: Result<String, String>,
+    },
+    PropertiesUpdated {
+        serial: String,
+        props: Result<BTreeMap<String, String>, String>,
+    },
+    PropertyDiff {
+        left: String,
+        right: String,
+        diff: Result<Vec<PropDiff>, String>,
+    },
+    SettingsUpdated {
+        serial: String,
+        namespace: SettingsNamespace,
+        settings: Result<BTreeMap<String, String>, String>,
+    },
+    /// A put (`value` set) or delete finished.
+    SettingChanged {
+        serial: String,
+        change: SettingChange,
+        value: Option<String>,
+        result: Result<(), String>,
+    },
+    /// `failed` lists the `changes` that are still in effect.
+    SettingsReverted {
+        serial: String,
+        changes: Vec<SettingChange>,
+        failed: Vec<SettingChange>,
+    },
+    ScreenshotTaken {
+        serial: String,
+        result: Result<Screenshot, String>,
+    },
+    /// `files` holds every segment that was saved, even when `error` cut the
+    /// recording short.
+    RecordingFinished {
+        serial: String,
+        files: Vec<PathBuf>,
+        error: Option<String>,
+    },
+    PortsUpdated {
+        serial: String,
+        rules: Result<Vec<PortRule>, String>,
+    },
+    PortRulesApplied {
+        serial: String,
+        result: Result<usize, String>,
+    },
+    NetworkUpdated {
+        serial: String,
+        result: Result<NetworkInfo, String>,
+    },
+    DisplayUpdated {
+        serial: String,
+        result: Result<DisplayState, String>,
+    },
+    DevOptionsUpdated {
+        serial: String,
+        result: Result<BTreeMap<String, String>, String>,
+    },
+    DumpsysServices {
+        serial: String,
+        result: Result<Vec<String>, String>,
+    },
+    DumpsysOutput {
+        serial: String,
+        service: String,
+        result: Result<String, String>,
+    },
+    ForegroundActivity {
+        serial: String,
+        activity: Option<String>,
+    },
+    ActivityStackUpdated {
+        serial: String,
+        result: Result<Vec<Display>, String>,
+    },
+    NotificationsUpdated {
+        serial: String,
+        result: Result<Vec<Notification>, String>,
+    },
+    LocaleStateUpdated {
+        serial: String,
+        result: Result<LocaleState, String>,
+    },
+    UiHierarchyUpdated {
+        serial: String,
+        result: Result<Vec<UiNode>, String>,
+    },
+    /// What the emulator console printed for `command`.
+    EmulatorConsoleReplied {
+        serial: String,
+        command: ConsoleCommand,
+        result: Result<String, String>,
+    },
+    BugreportProgress {
+        serial: String,
+        done: u64,
+        total: u64,
+    },
+    /// The local path of the pulled zip.
+    BugreportCaptured {
+        serial: String,
+        result: Result<PathBuf, String>,
+    },
+    BugreportsListed(Result<Vec<BugreportSummary>, String>),
+    BugreportOpened {
+        path: PathBuf,
+        result: Result<Arc<BugreportContents>, String>,
+    },
+    IntentLaunched {
+        serial: String,
+        intent: Intent,
+        result: Result<AmResult, String>,
+    },
+    /// A change made through `App::spawn_change`; `Ok` holds the status text.
+    ChangeFinished {
+        serial: String,
+        affects: Affects,
+        result: Result<String, String>,
+    },
+    InputFailed {
+        serial: String,
+        error: String,
+    },
+    ShellOutput {
+        id: usize,
+        data: Vec<u8>,
+    },
+    ShellExited {
+        id: usize,
+        error: Option<String>,
+    },
     KeyPress(KeyEvent),
+    /// A bracketed paste, for the focused pane.
+    Paste(String),
+    /// A left click on terminal row `row`, in the pane or popup drawn in
+    /// `area`. `double` when it repeats the previous click.
+    Click {
+        area: Rect,
+        row: u16,
+        double: bool,
+    },
 }